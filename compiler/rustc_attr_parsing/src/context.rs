@@ -186,6 +186,7 @@ mod late {
         Single<DeprecatedParser>,
         Single<DoNotRecommendParser>,
         Single<ExportNameParser>,
+        Single<FutureSizeLimitParser>,
         Single<IgnoreParser>,
         Single<InlineParser>,
         Single<InstructionSetParser>,
@@ -98,6 +98,9 @@ pub(crate) fn import_function<'tcx>(
     module: &mut dyn Module,
     inst: Instance<'tcx>,
 ) -> FuncId {
+    // Refer to the mono item that is shared by all instantiations that only differ in arguments
+    // the body does not depend on.
+    let inst = inst.polymorphize(tcx);
     let name = tcx.symbol_name(inst).name;
     let sig = get_function_sig(tcx, module.target_config().default_call_conv, inst);
     match module.declare_function(name, Linkage::Import, &sig) {
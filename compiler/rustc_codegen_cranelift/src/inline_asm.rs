@@ -117,7 +117,8 @@ pub(crate) fn codegen_inline_asm_terminator<'tcx>(
                         def_id,
                         args,
                     )
-                    .unwrap();
+                    .unwrap()
+                    .polymorphize(fx.tcx);
                     let symbol = fx.tcx.symbol_name(instance);
 
                     // Pass a wrapper rather than the function itself as the function itself may not
@@ -20,6 +20,7 @@ pub fn get_fn<'gcc, 'tcx>(cx: &CodegenCx<'gcc, 'tcx>, instance: Instance<'tcx>)
     assert!(!instance.args.has_infer());
     assert!(!instance.args.has_escaping_bound_vars());
 
+    let instance = instance.polymorphize(tcx);
     let sym = tcx.symbol_name(instance).name;
 
     if let Some(&func) = cx.function_instances.borrow().get(&instance) {
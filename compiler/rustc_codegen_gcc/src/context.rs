@@ -404,6 +404,7 @@ fn get_fn(&self, instance: Instance<'tcx>) -> Function<'gcc> {
     }
 
     fn get_fn_addr(&self, instance: Instance<'tcx>) -> RValue<'gcc> {
+        let instance = instance.polymorphize(self.tcx);
         let func_name = self.tcx.symbol_name(instance).name;
 
         let func = if let Some(variable) = self.get_declared_value(func_name) {
@@ -623,12 +623,25 @@ pub(crate) unsafe fn llvm_optimize(
         .collect();
     let sanitize_dataflow_abilist_ptrs: Vec<_> =
         sanitize_dataflow_abilist.iter().map(|file| file.as_ptr()).collect();
+    let sanitize_coverage_ignorelist: Vec<_> = config
+        .sanitizer_coverage_ignorelist
+        .iter()
+        .map(|file| CString::new(file.as_str()).unwrap())
+        .collect();
+    let sanitize_coverage_ignorelist_ptrs: Vec<_> =
+        sanitize_coverage_ignorelist.iter().map(|file| file.as_ptr()).collect();
     // Sanitizer instrumentation is only inserted during the pre-link optimization stage.
     let sanitizer_options = if !is_lto {
         Some(llvm::SanitizerOptions {
             sanitize_address: config.sanitizer.contains(SanitizerSet::ADDRESS),
             sanitize_address_recover: config.sanitizer_recover.contains(SanitizerSet::ADDRESS),
             sanitize_cfi: config.sanitizer.contains(SanitizerSet::CFI),
+            sanitize_coverage_trace_pc_guard: config.sanitizer_coverage.trace_pc_guard,
+            sanitize_coverage_trace_cmp: config.sanitizer_coverage.trace_cmp,
+            sanitize_coverage_inline_8bit_counters: config.sanitizer_coverage.inline_8bit_counters,
+            sanitize_coverage_pc_table: config.sanitizer_coverage.pc_table,
+            sanitize_coverage_ignorelist: sanitize_coverage_ignorelist_ptrs.as_ptr(),
+            sanitize_coverage_ignorelist_len: sanitize_coverage_ignorelist_ptrs.len(),
             sanitize_dataflow: config.sanitizer.contains(SanitizerSet::DATAFLOW),
             sanitize_dataflow_abilist: sanitize_dataflow_abilist_ptrs.as_ptr(),
             sanitize_dataflow_abilist_len: sanitize_dataflow_abilist_ptrs.len(),
@@ -1987,4 +1987,47 @@ pub(crate) fn instrprof_increment(
     ) {
         self.call_intrinsic("llvm.instrprof.increment", &[], &[fn_name, hash, num_counters, index]);
     }
+
+    /// Emits a call to `llvm.instrprof.mcdc.parameters`, which declares the
+    /// number of bits in the function's MC/DC test vector bitmap. Used by
+    /// coverage instrumentation.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn mcdc_parameters(
+        &mut self,
+        fn_name: &'ll Value,
+        hash: &'ll Value,
+        bitmap_bits: &'ll Value,
+    ) {
+        self.call_intrinsic("llvm.instrprof.mcdc.parameters", &[], &[fn_name, hash, bitmap_bits]);
+    }
+
+    /// Emits a call to `llvm.instrprof.mcdc.tvbitmap.update`, which records
+    /// the test vector held by the condition bitmap `mcdc_temp`, and then
+    /// resets the condition bitmap. Used by coverage instrumentation.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn mcdc_tvbitmap_update(
+        &mut self,
+        fn_name: &'ll Value,
+        hash: &'ll Value,
+        bitmap_index: &'ll Value,
+        mcdc_temp: &'ll Value,
+    ) {
+        self.call_intrinsic(
+            "llvm.instrprof.mcdc.tvbitmap.update",
+            &[],
+            &[fn_name, hash, bitmap_index, mcdc_temp],
+        );
+        let align = self.tcx.data_layout.i32_align;
+        self.store(self.const_i32(0), mcdc_temp, align);
+    }
+
+    /// Adds `cond_index` to the condition bitmap `mcdc_temp`. Used by coverage
+    /// instrumentation.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn mcdc_condbitmap_update(&mut self, cond_index: &'ll Value, mcdc_temp: &'ll Value) {
+        let align = self.tcx.data_layout.i32_align;
+        let current_tv_index = self.load(self.cx.type_i32(), mcdc_temp, align);
+        let new_tv_index = self.add(current_tv_index, cond_index);
+        self.store(new_tv_index, mcdc_temp, align);
+    }
 }
@@ -23,6 +23,10 @@ pub(crate) fn get_fn<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>, instance: Instance<'t
     assert!(!instance.args.has_infer());
     assert!(!instance.args.has_escaping_bound_vars());
 
+    // Refer to the mono item that is shared by all instantiations that only differ in arguments
+    // the body does not depend on.
+    let instance = instance.polymorphize(tcx);
+
     if let Some(&llfn) = cx.instances.borrow().get(&instance) {
         return llfn;
     }
@@ -1,3 +1,5 @@
+use rustc_middle::mir::coverage::{ConditionId, ConditionInfo, DecisionInfo};
+
 /// Must match the layout of `LLVMRustCounterKind`.
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
@@ -98,3 +100,59 @@ pub(crate) struct BranchRegion {
     pub(crate) true_counter: Counter,
     pub(crate) false_counter: Counter,
 }
+
+/// Must match the layout of `LLVMRustMCDCBranchParameters`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct MCDCBranchParameters {
+    pub(crate) condition_id: i16,
+    /// IDs of the conditions to evaluate next if this condition is false
+    /// and true, in that order, or -1 if the decision is complete.
+    pub(crate) condition_ids: [i16; 2],
+}
+
+impl From<ConditionInfo> for MCDCBranchParameters {
+    fn from(value: ConditionInfo) -> Self {
+        let to_llvm_cond_id = |cond_id: Option<ConditionId>| {
+            cond_id.and_then(|id| i16::try_from(id.as_usize()).ok()).unwrap_or(-1)
+        };
+        let ConditionInfo { condition_id, true_next_id, false_next_id } = value;
+        Self {
+            condition_id: to_llvm_cond_id(Some(condition_id)),
+            condition_ids: [to_llvm_cond_id(false_next_id), to_llvm_cond_id(true_next_id)],
+        }
+    }
+}
+
+/// Must match the layout of `LLVMRustMCDCDecisionParameters`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct MCDCDecisionParameters {
+    pub(crate) bitmap_idx: u32,
+    pub(crate) num_conditions: u16,
+}
+
+impl From<DecisionInfo> for MCDCDecisionParameters {
+    fn from(value: DecisionInfo) -> Self {
+        let DecisionInfo { bitmap_idx, num_conditions } = value;
+        Self { bitmap_idx, num_conditions }
+    }
+}
+
+/// Must match the layout of `LLVMRustCoverageMCDCBranchRegion`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct MCDCBranchRegion {
+    pub(crate) cov_span: CoverageSpan,
+    pub(crate) true_counter: Counter,
+    pub(crate) false_counter: Counter,
+    pub(crate) mcdc_branch_params: MCDCBranchParameters,
+}
+
+/// Must match the layout of `LLVMRustCoverageMCDCDecisionRegion`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub(crate) struct MCDCDecisionRegion {
+    pub(crate) cov_span: CoverageSpan,
+    pub(crate) mcdc_decision_params: MCDCDecisionParameters,
+}
@@ -594,7 +594,8 @@ fn get_template_parameters<'ll, 'tcx>(
                 let names = get_parameter_names(cx, generics);
                 iter::zip(args, names)
                     .filter_map(|(kind, name)| {
-                        kind.as_type().map(|ty| {
+                        // Polymorphized instances keep the parameters their body does not use.
+                        kind.as_type().filter(|ty| !ty.has_param()).map(|ty| {
                             let actual_type = cx.tcx.normalize_erasing_regions(cx.typing_env(), ty);
                             let actual_type_metadata = type_di_node(cx, actual_type);
                             Some(cx.create_template_type_parameter(
@@ -451,6 +451,12 @@ pub(crate) struct SanitizerOptions {
     pub sanitize_address: bool,
     pub sanitize_address_recover: bool,
     pub sanitize_cfi: bool,
+    pub sanitize_coverage_trace_pc_guard: bool,
+    pub sanitize_coverage_trace_cmp: bool,
+    pub sanitize_coverage_inline_8bit_counters: bool,
+    pub sanitize_coverage_pc_table: bool,
+    pub sanitize_coverage_ignorelist: *const *const c_char,
+    pub sanitize_coverage_ignorelist_len: size_t,
     pub sanitize_dataflow: bool,
     pub sanitize_dataflow_abilist: *const *const c_char,
     pub sanitize_dataflow_abilist_len: size_t,
@@ -2118,6 +2124,10 @@ pub(crate) fn LLVMRustCoverageWriteFunctionMappingsToBuffer(
         NumExpansionRegions: size_t,
         BranchRegions: *const crate::coverageinfo::ffi::BranchRegion,
         NumBranchRegions: size_t,
+        MCDCBranchRegions: *const crate::coverageinfo::ffi::MCDCBranchRegion,
+        NumMCDCBranchRegions: size_t,
+        MCDCDecisionRegions: *const crate::coverageinfo::ffi::MCDCDecisionRegion,
+        NumMCDCDecisionRegions: size_t,
         BufferOut: &RustString,
     );
 
@@ -1,3 +1,4 @@
+mod map;
 mod raw_dylib;
 
 use std::collections::BTreeSet;
@@ -843,6 +844,12 @@ fn link_natively(
         should_archive.then(|| tmpdir.join(out_filename.file_name().unwrap()).with_extension("so"));
     let temp_filename = archive_member.as_deref().unwrap_or(out_filename);
 
+    // Don't report on a map that is left over from an earlier link.
+    let linker_map = map::linker_map_path(sess, temp_filename);
+    if let Some(map_path) = &linker_map {
+        ensure_removed(sess.dcx(), map_path);
+    }
+
     let mut cmd = linker_with_args(
         &linker_path,
         flavor,
@@ -1079,6 +1086,10 @@ fn link_natively(
         }
     }
 
+    if let Some(map_path) = &linker_map {
+        map::write_size_report(sess, out_filename, map_path);
+    }
+
     match sess.split_debuginfo() {
         // If split debug information is disabled or located in individual files
         // there's nothing to do here.
@@ -2776,6 +2787,10 @@ fn add_order_independent_options(
         cmd.ehcont_guard();
     }
 
+    if let Some(map_path) = map::linker_map_path(sess, out_filename) {
+        cmd.linker_map(&map_path);
+    }
+
     add_rpath_args(cmd, sess, crate_info, out_filename);
 }
 
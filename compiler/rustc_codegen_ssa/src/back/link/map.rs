@@ -0,0 +1,646 @@
+//! Support for `-Zlinker-map-report`: reads the map that the linker wrote for an artifact, and
+//! attributes the sizes of its sections to crates, modules and symbols.
+//!
+//! Three map formats are understood:
+//!
+//! - the one of GNU ld, which lists the input sections of every output section, followed by the
+//!   global symbols defined in them;
+//! - the one of lld for ELF targets, a table of output sections, input sections and symbols;
+//! - the one of `link.exe`, which `lld-link` writes as well. It lists the sections, and the
+//!   public and static symbols with their address, but not the input sections.
+//!
+//! A symbol covers everything from its address up to the next symbol of the same input section,
+//! or up to the end of the input section. Input sections without symbols, e.g. because GNU ld
+//! does not list local symbols, are attributed to the symbol in their name, like the sections
+//! `.text._ZN...` that are created for every function.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_session::Session;
+use rustc_session::config::SwitchWithOptPath;
+use serde_json::{Value, json};
+
+use crate::errors;
+
+#[cfg(test)]
+mod tests;
+
+/// Returns the path of the map that the linker should write for the artifact at `out_filename`,
+/// or `None` if `-Zlinker-map-report` is not enabled.
+pub(super) fn linker_map_path(sess: &Session, out_filename: &Path) -> Option<PathBuf> {
+    let SwitchWithOptPath::Enabled(dir) = &sess.opts.unstable_opts.linker_map_report else {
+        return None;
+    };
+    let dir = match dir {
+        Some(dir) => {
+            if let Err(error) = fs::create_dir_all(dir) {
+                sess.dcx().emit_fatal(errors::FailedToWrite { path: dir.clone(), error });
+            }
+            dir.as_path()
+        }
+        None => out_filename.parent().unwrap_or(Path::new("")),
+    };
+    let mut file_name = out_filename.file_name()?.to_owned();
+    file_name.push(".map");
+    Some(dir.join(file_name))
+}
+
+/// Reads the map at `map_path` that the linker wrote for the artifact at `out_filename`, and
+/// writes the size report next to it.
+pub(super) fn write_size_report(sess: &Session, out_filename: &Path, map_path: &Path) {
+    let map = match fs::read(map_path) {
+        Ok(map) => map,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            sess.dcx().emit_warn(errors::LinkerMapNotWritten { path: map_path });
+            return;
+        }
+        Err(error) => {
+            sess.dcx().emit_err(errors::LinkerMapReadFailure { path: map_path, error });
+            return;
+        }
+    };
+    let Some(map) = LinkerMap::parse(&String::from_utf8_lossy(&map)) else {
+        sess.dcx().emit_warn(errors::LinkerMapUnrecognized { path: map_path });
+        return;
+    };
+
+    let report = size_report(&map, out_filename);
+    let report_path = map_path.with_extension("size.json");
+    if let Err(error) = fs::write(&report_path, format!("{report:#}\n")) {
+        sess.dcx().emit_err(errors::FailedToWrite { path: report_path, error });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MapFormat {
+    Gnu,
+    Lld,
+    Msvc,
+}
+
+impl MapFormat {
+    fn name(self) -> &'static str {
+        match self {
+            MapFormat::Gnu => "gnu",
+            MapFormat::Lld => "lld",
+            MapFormat::Msvc => "msvc",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LinkerMap {
+    format: MapFormat,
+    sections: Vec<OutputSection>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct OutputSection {
+    name: String,
+    address: u64,
+    size: u64,
+    inputs: Vec<InputSection>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct InputSection {
+    name: String,
+    address: u64,
+    size: u64,
+    /// The object file the section comes from, e.g. `/path/liba.rlib(a.o)`.
+    object: Option<String>,
+    symbols: Vec<MapSymbol>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct MapSymbol {
+    name: String,
+    address: u64,
+    /// The object file that defines the symbol, if the map lists it separately from the section.
+    object: Option<String>,
+}
+
+impl LinkerMap {
+    fn parse(map: &str) -> Option<LinkerMap> {
+        let header = map.lines().find(|line| !line.trim().is_empty())?;
+        if let Some(columns) = LldColumns::from_header(header) {
+            Some(parse_lld(map, &columns))
+        } else if map.lines().any(|line| line.contains("Publics by Value")) {
+            Some(parse_msvc(map))
+        } else if map.lines().any(|line| line.starts_with("Linker script and memory map")) {
+            Some(parse_gnu(map))
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_hex(s: &str) -> Option<u64> {
+    u64::from_str_radix(s.strip_prefix("0x").unwrap_or(s), 16).ok()
+}
+
+/// Parses a map written by GNU ld with `-Map`. The relevant part looks like this, where the
+/// address and size of a section are moved to the next line if its name is too long:
+///
+/// ```text
+/// Linker script and memory map
+///
+/// .text           0x0000000000401000      0x1c5
+///  *(.text.unlikely .text.*_unlikely .text.unlikely.*)
+///  .text._ZN3foo4main17h0123456789abcdefE
+///                 0x0000000000401000       0x2c /tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o
+///  .text.main     0x0000000000401030       0x2c /tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o
+///                 0x0000000000401030                main
+/// ```
+fn parse_gnu(map: &str) -> LinkerMap {
+    let mut sections: Vec<OutputSection> = Vec::new();
+    // Whether the input sections that follow belong to the last output section.
+    let mut in_section = false;
+    // A section whose address and size are on the next line, and whether it's an output section.
+    let mut wrapped: Option<(&str, bool)> = None;
+
+    let lines = map.lines().skip_while(|line| !line.starts_with("Linker script and memory map"));
+    for line in lines.skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let Some((name, is_output)) = wrapped.take() {
+            if let [address, size, ref object @ ..] = fields[..]
+                && let (Some(address), Some(size)) = (parse_hex(address), parse_hex(size))
+            {
+                push_gnu_section(&mut sections, is_output, name, address, size, object);
+                in_section |= is_output;
+                continue;
+            }
+            // Output sections without an address, like `/DISCARD/`, don't contain anything
+            // that ends up in the artifact.
+            in_section &= !is_output;
+        }
+        let Some(&first) = fields.first() else { continue };
+
+        if !line.starts_with(' ') {
+            // An output section, or a statement of the linker script like `LOAD file.o`.
+            in_section = false;
+            match fields[..] {
+                [name] => wrapped = Some((name, true)),
+                [name, address, size, ..] => {
+                    if let (Some(address), Some(size)) = (parse_hex(address), parse_hex(size)) {
+                        push_gnu_section(&mut sections, true, name, address, size, &[]);
+                        in_section = true;
+                    }
+                }
+                _ => {}
+            }
+        } else if !line.starts_with("  ") {
+            // An input section, an input section pattern like `*(.text .text.*)`, or padding.
+            if !in_section || first.starts_with('*') {
+                continue;
+            }
+            match fields[..] {
+                [name] => wrapped = Some((name, false)),
+                [name, address, size, ref object @ ..] => {
+                    if let (Some(address), Some(size)) = (parse_hex(address), parse_hex(size)) {
+                        push_gnu_section(&mut sections, false, name, address, size, object);
+                    }
+                }
+                _ => {}
+            }
+        } else if in_section
+            && let [address, name] = fields[..]
+            && let Some(address) = parse_hex(address)
+            && let Some(input) = sections.last_mut().and_then(|section| section.inputs.last_mut())
+        {
+            // A symbol. Assignments like `__bss_start = .` have more fields.
+            input.symbols.push(MapSymbol { name: name.to_owned(), address, object: None });
+        }
+    }
+
+    LinkerMap { format: MapFormat::Gnu, sections }
+}
+
+fn push_gnu_section(
+    sections: &mut Vec<OutputSection>,
+    is_output: bool,
+    name: &str,
+    address: u64,
+    size: u64,
+    object: &[&str],
+) {
+    let name = name.to_owned();
+    if is_output {
+        sections.push(OutputSection { name, address, size, inputs: Vec::new() });
+    } else if let Some(section) = sections.last_mut() {
+        let object = if object.is_empty() { None } else { Some(object.join(" ")) };
+        section.inputs.push(InputSection { name, address, size, object, symbols: Vec::new() });
+    }
+}
+
+/// The layout of the table in a map written by lld, which starts with a header like this one:
+///
+/// ```text
+///              VMA              LMA     Size Align Out     In      Symbol
+/// ```
+///
+/// The numeric columns are right-aligned to the header. The text of a row starts under `Out` for
+/// output sections, under `In` for input sections and under `Symbol` for symbols.
+struct LldColumns {
+    /// The number of numeric columns, the first of which is the address.
+    numbers: usize,
+    /// The index of the size among the numeric columns.
+    size: usize,
+    /// The offsets of the `Out`, `In` and `Symbol` columns.
+    out: usize,
+    input: usize,
+    symbol: usize,
+}
+
+impl LldColumns {
+    fn from_header(header: &str) -> Option<LldColumns> {
+        let names: Vec<&str> = header.split_whitespace().collect();
+        let numbers = names.iter().position(|&name| name == "Out")?;
+        if names[numbers..] != ["Out", "In", "Symbol"] {
+            return None;
+        }
+        let size = names[..numbers].iter().position(|&name| name == "Size")?;
+        let out = header.find(" Out ")? + 1;
+        let input = header.find(" In ")? + 1;
+        let symbol = header.find(" Symbol")? + 1;
+        Some(LldColumns { numbers, size, out, input, symbol })
+    }
+}
+
+/// Parses a map written by lld with `-Map`:
+///
+/// ```text
+///              VMA              LMA     Size Align Out     In      Symbol
+///           201120           201120       5c    16 .text
+///           201120           201120       2c    16         foo.foo.1a2b3c4d-cgu.0.rcgu.o:(.text.main)
+///           201120           201120       2c     1                 main
+/// ```
+fn parse_lld(map: &str, columns: &LldColumns) -> LinkerMap {
+    let mut sections: Vec<OutputSection> = Vec::new();
+    for line in map.lines().skip_while(|line| line.trim().is_empty()).skip(1) {
+        let (Some(numbers), Some(text)) = (line.get(..columns.out), line.get(columns.out..)) else {
+            continue;
+        };
+        let numbers: Vec<&str> = numbers.split_whitespace().collect();
+        if numbers.len() != columns.numbers {
+            continue;
+        }
+        let (Some(address), Some(size)) = (parse_hex(numbers[0]), parse_hex(numbers[columns.size]))
+        else {
+            continue;
+        };
+        let indent = columns.out + text.len() - text.trim_start().len();
+        let text = text.trim();
+
+        if indent < columns.input {
+            let name = text.to_owned();
+            sections.push(OutputSection { name, address, size, inputs: Vec::new() });
+        } else if indent < columns.symbol {
+            // Input sections are written as `file:(section)`, statements of the linker script
+            // like `. = ALIGN(8)` are skipped.
+            let Some(section) = sections.last_mut() else { continue };
+            let Some((object, name)) = text.rsplit_once(":(") else { continue };
+            let name = name.strip_suffix(')').unwrap_or(name).to_owned();
+            let object = Some(object.to_owned());
+            section.inputs.push(InputSection { name, address, size, object, symbols: Vec::new() });
+        } else if !text.contains(char::is_whitespace)
+            && let Some(input) = sections.last_mut().and_then(|section| section.inputs.last_mut())
+        {
+            input.symbols.push(MapSymbol { name: text.to_owned(), address, object: None });
+        }
+    }
+    LinkerMap { format: MapFormat::Lld, sections }
+}
+
+/// Parses an address of a map written by `link.exe`, like `0001:00000040`, into its section
+/// number and offset.
+fn parse_msvc_address(address: &str) -> Option<(u32, u64)> {
+    let (section, offset) = address.split_once(':')?;
+    Some((u32::from_str_radix(section, 16).ok()?, parse_hex(offset)?))
+}
+
+/// Parses a map written by `link.exe` or `lld-link` with `/MAP`:
+///
+/// ```text
+///  Start         Length     Name                   Class
+///  0001:00000000 00000120H .text$mn                CODE
+///
+///   Address         Publics by Value              Rva+Base               Lib:Object
+///
+///  0001:00000000       _ZN3foo4main17h0123456789abcdefE 0000000140001000 f   foo.foo.1a2b3c4d-cgu.0.rcgu.o
+///  0001:00000040       main                       0000000140001040 f   foo.foo.1a2b3c4d-cgu.0.rcgu.o
+/// ```
+///
+/// Symbols are given as offsets into the sections, so that's what the input sections use as well.
+fn parse_msvc(map: &str) -> LinkerMap {
+    let mut sections: FxIndexMap<u32, OutputSection> = FxIndexMap::default();
+    let mut in_symbols = false;
+    for line in map.lines() {
+        if line.contains("Publics by Value") || line.trim() == "Static symbols" {
+            in_symbols = true;
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let Some((number, offset)) = fields.first().and_then(|field| parse_msvc_address(field))
+        else {
+            continue;
+        };
+
+        if !in_symbols {
+            // A part of a section, like `.text$mn` of `.text`.
+            let [_, length, name, ..] = fields[..] else { continue };
+            let Some(length) = length.strip_suffix('H').and_then(parse_hex) else { continue };
+            let section = sections.entry(number).or_insert_with(|| {
+                let name = name.split('$').next().unwrap_or(name).to_owned();
+                let input = InputSection {
+                    name: name.clone(),
+                    address: 0,
+                    size: 0,
+                    object: None,
+                    symbols: Vec::new(),
+                };
+                OutputSection { name, address: 0, size: 0, inputs: vec![input] }
+            });
+            section.size = section.size.max(offset + length);
+            section.inputs[0].size = section.size;
+        } else if let [_, name, address, .., object] = fields[..]
+            && let Some(section) = sections.get_mut(&number)
+        {
+            // Symbols of section 0 are absolute, and don't take any space.
+            if section.address == 0
+                && let Some(address) = parse_hex(address).and_then(|a| a.checked_sub(offset))
+            {
+                section.address = address;
+            }
+            let symbol = MapSymbol {
+                name: name.to_owned(),
+                address: offset,
+                object: Some(object.to_owned()),
+            };
+            section.inputs[0].symbols.push(symbol);
+        }
+    }
+    LinkerMap { format: MapFormat::Msvc, sections: sections.into_values().collect() }
+}
+
+/// Whether the output section `name` is loaded as part of the artifact. Debug information and
+/// other sections that are not are listed in the report, but not attributed to symbols.
+fn is_loaded(name: &str) -> bool {
+    !(name.starts_with(".debug")
+        || name.starts_with(".zdebug")
+        || matches!(
+            name,
+            ".comment"
+                | ".symtab"
+                | ".strtab"
+                | ".shstrtab"
+                | ".gnu_debuglink"
+                | ".gnu_debugdata"
+                | ".note.GNU-stack"
+        ))
+}
+
+/// A part of an output section that is attributed to one symbol.
+#[derive(Debug, PartialEq, Eq)]
+struct SymbolSize<'a> {
+    section: &'a str,
+    symbol: &'a str,
+    object: Option<&'a str>,
+    size: u64,
+}
+
+fn symbol_sizes(map: &LinkerMap) -> Vec<SymbolSize<'_>> {
+    let mut sizes = Vec::new();
+    for section in map.sections.iter().filter(|section| is_loaded(&section.name)) {
+        for input in &section.inputs {
+            let end = input.address.saturating_add(input.size);
+            let mut symbols: Vec<&MapSymbol> = input
+                .symbols
+                .iter()
+                .filter(|symbol| (input.address..end).contains(&symbol.address))
+                .collect();
+            // Aliases share the size of the first symbol at their address.
+            symbols.sort_by_key(|symbol| symbol.address);
+            symbols.dedup_by_key(|symbol| symbol.address);
+
+            let first = symbols.first().map_or(end, |symbol| symbol.address);
+            if first > input.address {
+                sizes.push(SymbolSize {
+                    section: &section.name,
+                    symbol: section_symbol(&input.name, &section.name),
+                    object: input.object.as_deref(),
+                    size: first - input.address,
+                });
+            }
+            for (i, symbol) in symbols.iter().enumerate() {
+                let next = symbols.get(i + 1).map_or(end, |next| next.address);
+                sizes.push(SymbolSize {
+                    section: &section.name,
+                    symbol: &symbol.name,
+                    object: symbol.object.as_deref().or(input.object.as_deref()),
+                    size: next - symbol.address,
+                });
+            }
+        }
+    }
+    sizes
+}
+
+/// Returns the symbol that an input section like `.text._ZN3foo4mainE` of `.text` was created
+/// for, or the name of the input section if there is none.
+fn section_symbol<'a>(input: &'a str, output: &str) -> &'a str {
+    let Some(symbol) = input.strip_prefix(output).and_then(|rest| rest.strip_prefix('.')) else {
+        return input;
+    };
+    let symbol = ["unlikely.", "hot.", "startup.", "exit.", "split."]
+        .iter()
+        .find_map(|prefix| symbol.strip_prefix(prefix))
+        .unwrap_or(symbol);
+    if symbol.is_empty() { input } else { symbol }
+}
+
+/// Where a symbol comes from, as far as can be told from its name and object file.
+#[derive(Debug, PartialEq, Eq)]
+struct SymbolOrigin {
+    /// The demangled name of the symbol, or its name if it's not a Rust symbol.
+    name: String,
+    /// The crate that defines the item of the symbol, or that the object file was compiled from.
+    krate: Option<String>,
+    /// The module that defines the item of the symbol.
+    module: Option<String>,
+}
+
+fn symbol_origin(symbol: &str, object: Option<&str>) -> SymbolOrigin {
+    let krate_of_object = || object.and_then(object_crate).map(str::to_owned);
+    let Ok(demangled) = rustc_demangle::try_demangle(symbol) else {
+        return SymbolOrigin { name: symbol.to_owned(), krate: krate_of_object(), module: None };
+    };
+    let name = format!("{demangled:#}");
+    let Some(path) = item_path(&name) else {
+        return SymbolOrigin { name, krate: krate_of_object(), module: None };
+    };
+
+    // Closures and other anonymous items belong to the item they are nested in.
+    let mut segments: Vec<&str> =
+        path.split("::").filter(|segment| !segment.starts_with('{')).collect();
+    if segments.len() > 1 {
+        segments.pop();
+    }
+    // Associated items belong to the module of their type or trait. Types can't be told apart
+    // from modules in a path, but are named in upper camel case by convention.
+    while segments.len() > 1
+        && segments.last().is_some_and(|segment| segment.starts_with(char::is_uppercase))
+    {
+        segments.pop();
+    }
+    let krate = segments.first().map(|krate| krate.to_string());
+    SymbolOrigin { name, krate, module: Some(segments.join("::")) }
+}
+
+/// Returns the path of the item of a demangled symbol, without generic arguments. Associated
+/// items like `<alloc::vec::Vec<T> as core::ops::Drop>::drop` are given as a path through their
+/// type if it has one, like `alloc::vec::Vec::drop`, or through their trait otherwise.
+fn item_path(name: &str) -> Option<String> {
+    let Some(qualified) = name.strip_prefix('<') else {
+        return Some(strip_generic_args(name).to_owned());
+    };
+
+    // Find the end of the type and of the trait, i.e. of `T` and `Trait` in `<T as Trait>::f`.
+    let bytes = qualified.as_bytes();
+    let mut depth = 0;
+    let mut type_end = None;
+    let mut end = None;
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'<' | b'(' | b'[' => depth += 1,
+            // The return type of function pointers, `fn() -> T`.
+            b'>' if i > 0 && bytes[i - 1] == b'-' => {}
+            b'>' | b')' | b']' if depth > 0 => depth -= 1,
+            b'>' => {
+                end = Some(i);
+                break;
+            }
+            b' ' if depth == 0 && qualified[i..].starts_with(" as ") => type_end = Some(i),
+            _ => {}
+        }
+    }
+    let end = end?;
+    let item = qualified[end + 1..].strip_prefix("::")?;
+    let ty = &qualified[..type_end.unwrap_or(end)];
+    let trait_ = type_end.map(|type_end| &qualified[type_end + " as ".len()..end]);
+
+    [Some(ty), trait_].into_iter().flatten().map(strip_generic_args).find_map(|path| {
+        let is_path = path.contains("::")
+            && path.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && !path.starts_with("dyn ");
+        is_path.then(|| format!("{path}::{}", strip_generic_args(item)))
+    })
+}
+
+fn strip_generic_args(path: &str) -> &str {
+    path.find('<').map_or(path, |generics| &path[..generics]).trim_end_matches("::")
+}
+
+/// Returns the crate that an object file was compiled from by rustc, possibly as a member of an
+/// rlib. The name of the codegen unit follows the name of the crate, like in
+/// `/path/libfoo-0123abcd.rlib(foo-0123abcd.foo.1a2b3c4d-cgu.0.rcgu.o)`.
+fn object_crate(object: &str) -> Option<&str> {
+    let member = object.strip_suffix(')').and_then(|object| object.rsplit_once('('));
+    let file = member.map_or(object, |(_, member)| member).rsplit(['/', '\\', ':']).next()?;
+    let parts: Vec<&str> = file.strip_suffix(".rcgu.o")?.split('.').collect();
+    let cgu = parts.iter().position(|part| part.ends_with("-cgu"))?;
+    let krate = parts[..cgu].last()?;
+    Some(krate.split_once('-').map_or(krate, |(krate, _hash)| krate))
+}
+
+/// Returns the library or file that an object file comes from, for symbols that are not
+/// attributed to a crate.
+fn object_origin(object: &str) -> &str {
+    let path = match object.strip_suffix(')').and_then(|object| object.rsplit_once('(')) {
+        Some((archive, _member)) => archive,
+        None => object,
+    };
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+#[derive(Default)]
+struct Total<'a> {
+    size: u64,
+    sections: BTreeMap<&'a str, u64>,
+}
+
+impl<'a> Total<'a> {
+    fn add(&mut self, section: &'a str, size: u64) {
+        self.size += size;
+        *self.sections.entry(section).or_default() += size;
+    }
+}
+
+fn size_report(map: &LinkerMap, artifact: &Path) -> Value {
+    let mut crates: FxIndexMap<String, Total<'_>> = FxIndexMap::default();
+    let mut modules: FxIndexMap<(String, String), u64> = FxIndexMap::default();
+    let mut others: FxIndexMap<&str, Total<'_>> = FxIndexMap::default();
+    let mut symbols = Vec::new();
+
+    for size in symbol_sizes(map) {
+        let origin = symbol_origin(size.symbol, size.object);
+        match &origin.krate {
+            Some(krate) => crates.entry(krate.clone()).or_default().add(size.section, size.size),
+            None => {
+                let other = size.object.map_or("<unknown>", object_origin);
+                others.entry(other).or_default().add(size.section, size.size)
+            }
+        }
+        if let (Some(krate), Some(module)) = (&origin.krate, &origin.module) {
+            *modules.entry((krate.clone(), module.clone())).or_default() += size.size;
+        }
+        symbols.push((size, origin));
+    }
+
+    crates.sort_by(|a_name, a, b_name, b| b.size.cmp(&a.size).then(a_name.cmp(b_name)));
+    modules.sort_by(|a_name, a, b_name, b| b.cmp(a).then(a_name.cmp(b_name)));
+    others.sort_by(|a_name, a, b_name, b| b.size.cmp(&a.size).then(a_name.cmp(b_name)));
+    symbols.sort_by(|(a, a_origin), (b, b_origin)| {
+        b.size.cmp(&a.size).then_with(|| a_origin.name.cmp(&b_origin.name))
+    });
+
+    json!({
+        "artifact": artifact.display().to_string(),
+        "map_format": map.format.name(),
+        "sections": map.sections.iter().map(|section| json!({
+            "name": section.name,
+            "address": section.address,
+            "size": section.size,
+        })).collect::<Vec<_>>(),
+        "crates": totals_json(crates),
+        "modules": modules.into_iter().map(|((krate, module), size)| json!({
+            "crate": krate,
+            "name": module,
+            "size": size,
+        })).collect::<Vec<_>>(),
+        "others": totals_json(others),
+        "symbols": symbols.into_iter().map(|(size, origin)| json!({
+            "name": origin.name,
+            "symbol": size.symbol,
+            "crate": origin.krate,
+            "module": origin.module,
+            "section": size.section,
+            "object": size.object,
+            "size": size.size,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn totals_json<K: Into<Value>>(totals: FxIndexMap<K, Total<'_>>) -> Vec<Value> {
+    totals
+        .into_iter()
+        .map(|(name, total)| {
+            let name: Value = name.into();
+            json!({ "name": name, "size": total.size, "sections": total.sections })
+        })
+        .collect()
+}
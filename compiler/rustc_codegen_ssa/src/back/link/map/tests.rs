@@ -0,0 +1,253 @@
+use super::*;
+
+const GNU_MAP: &str = "\
+Archive member included to satisfy reference by file (symbol)
+
+Linker script and memory map
+
+LOAD /tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o
+.interp         0x0000000000400318       0x1c
+ *(.interp)
+ .interp        0x0000000000400318       0x1c /usr/lib/crt1.o
+
+.text           0x0000000000401000       0x90
+ *(.text.unlikely .text.*_unlikely .text.unlikely.*)
+ .text.unlikely._ZN3foo4main17h0123456789abcdefE
+                0x0000000000401000       0x40 /tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o
+                0x0000000000401000                _ZN3foo4main17h0123456789abcdefE
+ *fill*         0x0000000000401040       0x10
+ .text._ZN3foo3bar17h0123456789abcdefE
+                0x0000000000401050       0x20 /tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o
+ .text          0x0000000000401070       0x20 /usr/lib/libc.a(memcpy.o)
+                0x0000000000401070                memcpy
+                0x0000000000401080                __memcpy_chk
+
+/DISCARD/
+ *(.note.GNU-stack)
+
+.debug_info     0x0000000000000000      0x100
+ .debug_info    0x0000000000000000      0x100 /tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o
+";
+
+const LLD_MAP: &str = "             VMA              LMA     Size Align Out     In      Symbol
+             2e0              2e0       1c     1 .interp
+             2e0              2e0       1c     1         <internal>:(.interp)
+            1000             1000       60    16 .text
+            1000             1000       40    16         foo.foo.1a2b3c4d-cgu.0.rcgu.o:(.text._ZN3foo4main17h0123456789abcdefE)
+            1000             1000       40     1                 _ZN3foo4main17h0123456789abcdefE
+            1040             1040        0     1         . = ALIGN(16)
+            1040             1040       20    16         /usr/lib/libc.a(memcpy.o):(.text)
+            1040             1040       20     1                 memcpy
+               0                0      100     1 .debug_info
+               0                0      100     1         foo.foo.1a2b3c4d-cgu.0.rcgu.o:(.debug_info)
+";
+
+const MSVC_MAP: &str = " foo
+
+ Timestamp is 6543210f (Mon Jan  1 00:00:00 2024)
+
+ Preferred load address is 0000000140000000
+
+ Start         Length     Name                   Class
+ 0001:00000000 00000100H .text$mn                CODE
+ 0001:00000100 00000020H .text$x                 CODE
+ 0002:00000000 00000040H .rdata                  DATA
+
+  Address         Publics by Value              Rva+Base               Lib:Object
+
+ 0000:00000000       __ImageBase                0000000140000000     <linker-defined>
+ 0001:00000000       _ZN3foo4main17h0123456789abcdefE 0000000140001000 f   foo.foo.1a2b3c4d-cgu.0.rcgu.o
+ 0001:00000080       memcpy                     0000000140001080 f   libvcruntime:memcpy.obj
+
+ entry point at        0001:00000000
+
+ Static symbols
+
+ 0001:00000100       _ZN3foo3bar17h0123456789abcdefE 0000000140001100 f   foo.foo.1a2b3c4d-cgu.0.rcgu.o
+";
+
+fn sizes(map: &LinkerMap) -> Vec<(&str, &str, u64)> {
+    symbol_sizes(map).iter().map(|size| (size.section, size.symbol, size.size)).collect()
+}
+
+#[test]
+fn parse_gnu_map() {
+    let map = LinkerMap::parse(GNU_MAP).unwrap();
+    assert_eq!(map.format, MapFormat::Gnu);
+    let names: Vec<_> = map.sections.iter().map(|section| section.name.as_str()).collect();
+    assert_eq!(names, [".interp", ".text", ".debug_info"]);
+
+    let text = &map.sections[1];
+    assert_eq!((text.address, text.size), (0x401000, 0x90));
+    assert_eq!(text.inputs.len(), 3);
+    assert_eq!(text.inputs[0].name, ".text.unlikely._ZN3foo4main17h0123456789abcdefE");
+    assert_eq!(text.inputs[0].object.as_deref(), Some("/tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o"));
+    assert_eq!(text.inputs[2].object.as_deref(), Some("/usr/lib/libc.a(memcpy.o)"));
+    assert_eq!(text.inputs[2].symbols.len(), 2);
+
+    assert_eq!(
+        sizes(&map),
+        [
+            (".interp", ".interp", 0x1c),
+            (".text", "_ZN3foo4main17h0123456789abcdefE", 0x40),
+            (".text", "_ZN3foo3bar17h0123456789abcdefE", 0x20),
+            (".text", "memcpy", 0x10),
+            (".text", "__memcpy_chk", 0x10),
+        ]
+    );
+}
+
+#[test]
+fn parse_lld_map() {
+    let map = LinkerMap::parse(LLD_MAP).unwrap();
+    assert_eq!(map.format, MapFormat::Lld);
+    let names: Vec<_> = map.sections.iter().map(|section| section.name.as_str()).collect();
+    assert_eq!(names, [".interp", ".text", ".debug_info"]);
+
+    let text = &map.sections[1];
+    assert_eq!((text.address, text.size), (0x1000, 0x60));
+    assert_eq!(text.inputs.len(), 2);
+    assert_eq!(text.inputs[0].object.as_deref(), Some("foo.foo.1a2b3c4d-cgu.0.rcgu.o"));
+    assert_eq!(text.inputs[1].object.as_deref(), Some("/usr/lib/libc.a(memcpy.o)"));
+
+    assert_eq!(
+        sizes(&map),
+        [
+            (".interp", ".interp", 0x1c),
+            (".text", "_ZN3foo4main17h0123456789abcdefE", 0x40),
+            (".text", "memcpy", 0x20),
+        ]
+    );
+}
+
+#[test]
+fn parse_msvc_map() {
+    let map = LinkerMap::parse(MSVC_MAP).unwrap();
+    assert_eq!(map.format, MapFormat::Msvc);
+    let names: Vec<_> = map.sections.iter().map(|section| section.name.as_str()).collect();
+    assert_eq!(names, [".text", ".rdata"]);
+    assert_eq!((map.sections[0].address, map.sections[0].size), (0x140001000, 0x120));
+
+    let sizes: Vec<_> = symbol_sizes(&map)
+        .iter()
+        .map(|size| (size.section, size.symbol, size.object, size.size))
+        .collect();
+    assert_eq!(
+        sizes,
+        [
+            (
+                ".text",
+                "_ZN3foo4main17h0123456789abcdefE",
+                Some("foo.foo.1a2b3c4d-cgu.0.rcgu.o"),
+                0x80
+            ),
+            (".text", "memcpy", Some("libvcruntime:memcpy.obj"), 0x80),
+            (
+                ".text",
+                "_ZN3foo3bar17h0123456789abcdefE",
+                Some("foo.foo.1a2b3c4d-cgu.0.rcgu.o"),
+                0x20
+            ),
+            (".rdata", ".rdata", None, 0x40),
+        ]
+    );
+}
+
+#[test]
+fn parse_unknown_map() {
+    assert_eq!(LinkerMap::parse(""), None);
+    assert_eq!(LinkerMap::parse("Allocating common symbols\n"), None);
+}
+
+#[test]
+fn section_symbols() {
+    assert_eq!(section_symbol(".text._ZN3foo4mainE", ".text"), "_ZN3foo4mainE");
+    assert_eq!(section_symbol(".text.unlikely._ZN3foo4mainE", ".text"), "_ZN3foo4mainE");
+    assert_eq!(section_symbol(".rodata..L__unnamed_1", ".rodata"), ".L__unnamed_1");
+    assert_eq!(section_symbol(".text.unlikely.", ".text"), ".text.unlikely.");
+    assert_eq!(section_symbol(".text", ".text"), ".text");
+}
+
+#[test]
+fn object_crates() {
+    assert_eq!(object_crate("/tmp/rustc/foo.foo.1a2b3c4d-cgu.0.rcgu.o"), Some("foo"));
+    assert_eq!(object_crate("foo_bin.foo.1a2b3c4d-cgu.12.rcgu.o"), Some("foo"));
+    assert_eq!(
+        object_crate("/path/libstd-0123abcd.rlib(std-0123abcd.std.1a2b3c4d-cgu.0.rcgu.o)"),
+        Some("std")
+    );
+    assert_eq!(object_crate("foo.45ubuxvia0etc1jhw1hp7blmr.rcgu.o"), None);
+    assert_eq!(object_crate("/usr/lib/libc.a(memcpy.o)"), None);
+    assert_eq!(object_crate("libvcruntime:memcpy.obj"), None);
+
+    assert_eq!(object_origin("/usr/lib/libc.a(memcpy.o)"), "libc.a");
+    assert_eq!(object_origin("/usr/lib/crt1.o"), "crt1.o");
+}
+
+#[test]
+fn item_paths() {
+    assert_eq!(item_path("foo::bar::baz").as_deref(), Some("foo::bar::baz"));
+    assert_eq!(item_path("foo::bar::<u8>").as_deref(), Some("foo::bar"));
+    assert_eq!(
+        item_path("<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop").as_deref(),
+        Some("alloc::vec::Vec::drop")
+    );
+    assert_eq!(
+        item_path("<[T] as core::fmt::Debug>::fmt").as_deref(),
+        Some("core::fmt::Debug::fmt")
+    );
+    assert_eq!(
+        item_path("<fn() -> u8 as core::ops::function::FnOnce<()>>::call_once").as_deref(),
+        Some("core::ops::function::FnOnce::call_once")
+    );
+    assert_eq!(item_path("<u8>::fmt"), None);
+}
+
+#[test]
+fn symbol_origins() {
+    let origin = symbol_origin("_ZN3foo3bar4main17h0123456789abcdefE", None);
+    assert_eq!(origin.name, "foo::bar::main");
+    assert_eq!(origin.krate.as_deref(), Some("foo"));
+    assert_eq!(origin.module.as_deref(), Some("foo::bar"));
+
+    // A method of a type.
+    let origin = symbol_origin("_ZN5alloc3vec16Vec$LT$T$C$A$GT$4push17h0123456789abcdefE", None);
+    assert_eq!(origin.krate.as_deref(), Some("alloc"));
+    assert_eq!(origin.module.as_deref(), Some("alloc::vec"));
+
+    // A closure in a function.
+    let origin = symbol_origin(
+        "_ZN3std2rt10lang_start28_$u7b$$u7b$closure$u7d$$u7d$17h0123456789abcdefE",
+        None,
+    );
+    assert_eq!(origin.name, "std::rt::lang_start::{{closure}}");
+    assert_eq!(origin.module.as_deref(), Some("std::rt"));
+
+    // Symbols that are not mangled by rustc are attributed through their object file.
+    let origin = symbol_origin("memcpy", Some("/usr/lib/libc.a(memcpy.o)"));
+    assert_eq!(origin, SymbolOrigin { name: "memcpy".to_owned(), krate: None, module: None });
+    let origin = symbol_origin("main", Some("foo.foo.1a2b3c4d-cgu.0.rcgu.o"));
+    assert_eq!(origin.krate.as_deref(), Some("foo"));
+    assert_eq!(origin.module, None);
+}
+
+#[test]
+fn size_report_totals() {
+    let map = LinkerMap::parse(GNU_MAP).unwrap();
+    let report = size_report(&map, Path::new("foo"));
+    assert_eq!(report["map_format"], "gnu");
+    assert_eq!(
+        report["crates"],
+        json!([{ "name": "foo", "size": 0x60, "sections": { ".text": 0x60 } }])
+    );
+    assert_eq!(
+        report["others"],
+        json!([
+            { "name": "libc.a", "size": 0x20, "sections": { ".text": 0x20 } },
+            { "name": "crt1.o", "size": 0x1c, "sections": { ".interp": 0x1c } },
+        ])
+    );
+    assert_eq!(report["modules"], json!([{ "crate": "foo", "name": "foo", "size": 0x60 }]));
+    assert_eq!(report["symbols"][0]["name"], "foo::main");
+    assert_eq!(report["symbols"][0]["size"], 0x40);
+}
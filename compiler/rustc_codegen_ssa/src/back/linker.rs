@@ -352,6 +352,9 @@ fn add_eh_frame_header(&mut self) {}
     fn add_no_exec(&mut self) {}
     fn add_as_needed(&mut self) {}
     fn reset_per_library_state(&mut self) {}
+    /// Asks the linker to write a map of the linked artifact to `path`, for linkers whose map
+    /// format is understood by `-Zlinker-map-report`.
+    fn linker_map(&mut self, _path: &Path) {}
 }
 
 impl dyn Linker + '_ {
@@ -931,6 +934,21 @@ fn add_as_needed(&mut self) {
             self.link_args(&["-z", "ignore"]);
         }
     }
+
+    fn linker_map(&mut self, path: &Path) {
+        // Both GNU ld and lld write their own map format, see `back::link::map`. The native
+        // linkers of Apple targets and illumos write formats that are not supported.
+        if self.is_gnu && !self.sess.target.is_like_solaris {
+            let mut arg = OsString::from("-Map=");
+            arg.push(path);
+            self.link_arg(arg);
+            // Demangled names can't be told apart from the other text of the map, so ask for
+            // mangled ones and demangle them in the report instead. This also affects the
+            // diagnostics of the linker, e.g. undefined symbols are printed mangled, which is
+            // documented for `-Zlinker-map-report`.
+            self.link_arg("--no-demangle");
+        }
+    }
 }
 
 struct MsvcLinker<'a> {
@@ -1191,6 +1209,13 @@ fn linker_plugin_lto(&mut self) {
     fn add_no_exec(&mut self) {
         self.link_arg("/NXCOMPAT");
     }
+
+    fn linker_map(&mut self, path: &Path) {
+        // `lld-link` writes the same format as `link.exe`.
+        let mut arg = OsString::from("/MAP:");
+        arg.push(path);
+        self.link_arg(arg);
+    }
 }
 
 struct EmLinker<'a> {
@@ -28,7 +28,7 @@
 use rustc_session::Session;
 use rustc_session::config::{
     self, CrateType, Lto, OptLevel, OutFileName, OutputFilenames, OutputType, Passes,
-    SwitchWithOptPath,
+    SanitizerCoverageOptions, SwitchWithOptPath,
 };
 use rustc_span::source_map::SourceMap;
 use rustc_span::{FileName, InnerSpan, Span, SpanData};
@@ -90,6 +90,8 @@ pub struct ModuleConfig {
     pub sanitizer_recover: SanitizerSet,
     pub sanitizer_dataflow_abilist: Vec<String>,
     pub sanitizer_memory_track_origins: usize,
+    pub sanitizer_coverage: SanitizerCoverageOptions,
+    pub sanitizer_coverage_ignorelist: Vec<String>,
 
     // Flags indicating which outputs to produce.
     pub emit_pre_lto_bc: bool,
@@ -188,6 +190,14 @@ macro_rules! if_regular {
                 sess.opts.unstable_opts.sanitizer_memory_track_origins,
                 0
             ),
+            sanitizer_coverage: if_regular!(
+                sess.opts.unstable_opts.sanitizer_coverage,
+                SanitizerCoverageOptions::default()
+            ),
+            sanitizer_coverage_ignorelist: if_regular!(
+                sess.opts.unstable_opts.sanitizer_coverage_ignorelist.clone(),
+                Vec::new()
+            ),
 
             emit_pre_lto_bc: if_regular!(
                 save_temps || need_pre_lto_bitcode_for_incr_comp(sess),
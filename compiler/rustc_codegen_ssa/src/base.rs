@@ -443,7 +443,7 @@ pub fn codegen_global_asm<'tcx, Cx>(cx: &mut Cx, item_id: ItemId)
                         _ => span_bug!(*op_sp, "asm sym is not a function"),
                     };
 
-                    GlobalAsmOperandRef::SymFn { instance }
+                    GlobalAsmOperandRef::SymFn { instance: instance.polymorphize(cx.tcx()) }
                 }
                 rustc_hir::InlineAsmOperand::SymStatic { path: _, def_id } => {
                     GlobalAsmOperandRef::SymStatic { def_id }
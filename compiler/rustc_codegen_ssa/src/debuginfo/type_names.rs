@@ -443,8 +443,13 @@ fn push_debuginfo_type_name<'tcx>(
                 push_close_angle_bracket(cpp_like_debuginfo, output);
             }
         }
-        ty::Param(_)
-        | ty::Error(_)
+        ty::Param(param) => {
+            // Polymorphized instances keep the parameters that their body does not depend on,
+            // see `Instance::polymorphize`. Those only show up in names, e.g. of the instance
+            // itself or of a `PhantomData<T>`, and are named after the parameter.
+            output.push_str(param.name.as_str());
+        }
+        ty::Error(_)
         | ty::Infer(_)
         | ty::Placeholder(..)
         | ty::Alias(..)
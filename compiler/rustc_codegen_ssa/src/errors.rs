@@ -612,6 +612,26 @@ pub(crate) struct UnableToWriteDebuggerVisualizer {
     pub error: Error,
 }
 
+#[derive(Diagnostic)]
+#[diag("the linker did not write a map to `{$path}`")]
+#[note("`-Z linker-map-report` is only supported with GNU ld, lld and MSVC-style linkers")]
+pub(crate) struct LinkerMapNotWritten<'a> {
+    pub path: &'a Path,
+}
+
+#[derive(Diagnostic)]
+#[diag("failed to read the linker map `{$path}`: {$error}")]
+pub(crate) struct LinkerMapReadFailure<'a> {
+    pub path: &'a Path,
+    pub error: Error,
+}
+
+#[derive(Diagnostic)]
+#[diag("the format of the linker map `{$path}` is not recognized")]
+pub(crate) struct LinkerMapUnrecognized<'a> {
+    pub path: &'a Path,
+}
+
 #[derive(Diagnostic)]
 #[diag("failed to build archive from rlib at `{$path}`: {$error}")]
 pub(crate) struct RlibArchiveBuildFailure {
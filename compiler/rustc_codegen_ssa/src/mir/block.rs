@@ -1410,7 +1410,8 @@ fn codegen_asm_terminator(
                             def_id,
                             args,
                         )
-                        .unwrap();
+                        .unwrap()
+                        .polymorphize(bx.tcx());
                         InlineAsmOperandRef::SymFn { instance }
                     } else {
                         span_bug!(span, "invalid type for asm sym (fn)");
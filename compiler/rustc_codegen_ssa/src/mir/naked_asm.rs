@@ -101,7 +101,7 @@ fn inline_to_global_operand<'a, 'tcx, Cx: LayoutOf<'tcx, LayoutOfResult = TyAndL
                 _ => bug!("asm sym is not a function"),
             };
 
-            GlobalAsmOperandRef::SymFn { instance }
+            GlobalAsmOperandRef::SymFn { instance: instance.polymorphize(cx.tcx()) }
         }
         InlineAsmOperand::SymStatic { def_id } => {
             GlobalAsmOperandRef::SymStatic { def_id: *def_id }
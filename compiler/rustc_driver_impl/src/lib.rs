@@ -43,7 +43,7 @@
 use rustc_interface::passes::collect_crate_types;
 use rustc_interface::util::{self, get_codegen_backend};
 use rustc_interface::{Linker, create_and_enter_global_ctxt, interface, passes};
-use rustc_lint::unerased_lint_store;
+use rustc_lint::{LintStore, unerased_lint_store};
 use rustc_metadata::creader::MetadataLoader;
 use rustc_metadata::locator;
 use rustc_middle::ty::TyCtxt;
@@ -224,6 +224,17 @@ pub fn run_compiler(at_args: &[String], callbacks: &mut (dyn Callbacks + Send))
 
     callbacks.config(&mut config);
 
+    if !config.opts.unstable_opts.lint_plugin.is_empty() {
+        let register_lints = config.register_lints.take();
+        config.register_lints =
+            Some(Box::new(move |sess: &Session, lint_store: &mut LintStore| {
+                if let Some(register_lints) = &register_lints {
+                    register_lints(sess, lint_store);
+                }
+                rustc_public::rustc_internal::lint_plugin::register_lint_plugins(sess, lint_store);
+            }));
+    }
+
     let registered_lints = config.register_lints.is_some();
 
     interface::run_compiler(config, |compiler| {
@@ -1083,6 +1083,10 @@ pub struct DeriveResolution {
 pub trait ResolverExpand {
     fn next_node_id(&mut self) -> NodeId;
     fn invocation_parent(&self, id: LocalExpnId) -> LocalDefId;
+    /// Returns the path of the definition containing the invocation, relative
+    /// to the crate root. Definitions without a name are represented with a
+    /// placeholder like `{impl#0}`.
+    fn invocation_parent_path(&self, id: LocalExpnId) -> Vec<Symbol>;
 
     fn resolve_dollar_crates(&self);
     fn visit_ast_fragment_with_placeholders(
@@ -3,6 +3,8 @@
 #![feature(associated_type_defaults)]
 #![feature(macro_metavar_expr)]
 #![feature(proc_macro_diagnostic)]
+#![feature(proc_macro_diagnostic_suggestion)]
+#![feature(proc_macro_expansion_context)]
 #![feature(proc_macro_internals)]
 #![feature(try_blocks)]
 #![feature(yeet_expr)]
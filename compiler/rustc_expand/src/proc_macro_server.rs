@@ -12,11 +12,14 @@
 use rustc_parse::parser::Parser;
 use rustc_parse::{exp, new_parser_from_source_str, source_str_to_stream};
 use rustc_proc_macro::bridge::{
-    DelimSpan, Diagnostic, ExpnGlobals, Group, Ident, LitKind, Literal, Punct, TokenTree, server,
+    Cfg, DelimSpan, Diagnostic, ExpnGlobals, Group, Ident, LitKind, Literal, Punct, Suggestion,
+    TokenTree, server,
 };
-use rustc_proc_macro::{Delimiter, Level};
+use rustc_proc_macro::context::Edition;
+use rustc_proc_macro::{Applicability, Delimiter, Level};
 use rustc_session::parse::ParseSess;
 use rustc_span::def_id::CrateNum;
+use rustc_span::edition;
 use rustc_span::{BytePos, FileName, Pos, Span, Symbol, sym};
 use smallvec::{SmallVec, smallvec};
 
@@ -415,6 +418,49 @@ fn to_internal(self) -> rustc_errors::Level {
     }
 }
 
+impl FromInternal<edition::Edition> for Edition {
+    fn from_internal(edition: edition::Edition) -> Edition {
+        match edition {
+            edition::Edition::Edition2015 => Edition::Edition2015,
+            edition::Edition::Edition2018 => Edition::Edition2018,
+            edition::Edition::Edition2021 => Edition::Edition2021,
+            edition::Edition::Edition2024 => Edition::Edition2024,
+            edition::Edition::EditionFuture => Edition::EditionFuture,
+        }
+    }
+}
+
+impl ToInternal<rustc_errors::Applicability> for Applicability {
+    fn to_internal(self) -> rustc_errors::Applicability {
+        match self {
+            Applicability::MachineApplicable => rustc_errors::Applicability::MachineApplicable,
+            Applicability::MaybeIncorrect => rustc_errors::Applicability::MaybeIncorrect,
+            Applicability::HasPlaceholders => rustc_errors::Applicability::HasPlaceholders,
+            Applicability::Unspecified => rustc_errors::Applicability::Unspecified,
+            _ => unreachable!("unknown proc_macro::Applicability variant: {:?}", self),
+        }
+    }
+}
+
+/// Attaches a suggestion from a proc macro to `diag`.
+///
+/// The diagnostic machinery asserts that a suggestion has at least one part,
+/// that no part deletes nothing and inserts nothing, and that its parts don't
+/// overlap. Proc macros can't be trusted to uphold this, so suggestions
+/// violating it are dropped.
+fn add_suggestion(diag: &mut Diag<'_, ()>, suggestion: Suggestion<Span>) {
+    let mut parts: Vec<(Span, String)> =
+        suggestion.parts.into_iter().map(|part| (part.span, part.snippet)).collect();
+    parts.sort_unstable_by_key(|(span, _)| *span);
+    let valid = !parts.is_empty()
+        && parts.iter().all(|(span, snippet)| !span.is_empty() || !snippet.is_empty())
+        && parts.array_windows().all(|[(a, _), (b, _)]| !a.overlaps(*b));
+    if valid {
+        let applicability = suggestion.applicability.to_internal();
+        diag.multipart_suggestion(suggestion.message, parts, applicability);
+    }
+}
+
 fn cancel_diags_into_string(diags: Vec<Diag<'_>>) -> String {
     let mut messages = diags.into_iter().flat_map(Diag::cancel_into_message);
     let msg = messages.next().expect("no diagnostic has a message");
@@ -549,12 +595,51 @@ fn emit_diagnostic(&mut self, diagnostic: Diagnostic<Self::Span>) {
         let mut diag: Diag<'_, ()> =
             Diag::new(self.psess().dcx(), diagnostic.level.to_internal(), message);
         diag.span(MultiSpan::from_spans(diagnostic.spans));
+        for suggestion in diagnostic.suggestions {
+            add_suggestion(&mut diag, suggestion);
+        }
         for child in diagnostic.children {
             diag.sub(child.level.to_internal(), child.message, MultiSpan::from_spans(child.spans));
+            for suggestion in child.suggestions {
+                add_suggestion(&mut diag, suggestion);
+            }
         }
         diag.emit();
     }
 
+    fn expansion_crate_name(&mut self) -> String {
+        self.ecx.ecfg.crate_name.to_string()
+    }
+
+    fn expansion_edition(&mut self) -> Edition {
+        FromInternal::from_internal(self.ecx.sess.edition())
+    }
+
+    fn expansion_cfgs(&mut self) -> Vec<Cfg> {
+        self.psess()
+            .config
+            .iter()
+            .map(|&(name, value)| Cfg {
+                name: name.to_string(),
+                value: value.map(|value| value.to_string()),
+            })
+            .collect()
+    }
+
+    fn expansion_module_path(&mut self) -> String {
+        let mod_path = &self.ecx.current_expansion.module.mod_path;
+        mod_path.iter().map(|ident| ident.as_str()).collect::<Vec<_>>().join("::")
+    }
+
+    fn expansion_item_path(&mut self) -> String {
+        let parent_path = self.ecx.resolver.invocation_parent_path(self.ecx.current_expansion.id);
+        std::iter::once(self.ecx.ecfg.crate_name)
+            .chain(parent_path)
+            .map(|segment| segment.to_string())
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
     fn ts_drop(&mut self, stream: Self::TokenStream) {
         drop(stream);
     }
@@ -691,6 +691,10 @@ pub struct BuiltinAttribute {
         move_size_limit, CrateLevel, template!(NameValueStr: "N"), ErrorFollowing,
         EncodeCrossCrate::No, large_assignments, experimental!(move_size_limit)
     ),
+    gated!(
+        future_size_limit, CrateLevel, template!(NameValueStr: "N"), ErrorFollowing,
+        EncodeCrossCrate::No, large_futures, experimental!(future_size_limit)
+    ),
 
     // Entry point:
     ungated!(
@@ -554,6 +554,8 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, lahfsahf_target_feature, "1.78.0", Some(150251)),
     /// Allows setting the threshold for the `large_assignments` lint.
     (unstable, large_assignments, "1.52.0", Some(83518)),
+    /// Allows setting the threshold for the `large_futures` lint.
+    (unstable, large_futures, "CURRENT_RUSTC_VERSION", None),
     /// Allow to have type alias types for inter-crate use.
     (incomplete, lazy_type_alias, "1.72.0", Some(112792)),
     /// Allows using `#[link(kind = "link-arg", name = "...")]`
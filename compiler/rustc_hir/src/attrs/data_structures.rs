@@ -1057,6 +1057,13 @@ pub enum AttributeKind {
     /// Represents `#[fundamental]`.
     Fundamental,
 
+    /// Represents `#[future_size_limit]`
+    FutureSizeLimit {
+        attr_span: Span,
+        limit_span: Span,
+        limit: Limit,
+    },
+
     /// Represents `#[ignore]`
     Ignore {
         span: Span,
@@ -47,6 +47,7 @@ pub fn encode_cross_crate(&self) -> EncodeCrossCrate {
             FfiConst(..) => No,
             FfiPure(..) => No,
             Fundamental { .. } => Yes,
+            FutureSizeLimit { .. } => No,
             Ignore { .. } => No,
             Inline(..) => No,
             InstructionSet(..) => No,
@@ -2,6 +2,7 @@
 //! - recursion_limit: there are various parts of the compiler that must impose arbitrary limits
 //!   on how deeply they recurse to prevent stack overflow.
 //! - move_size_limit
+//! - future_size_limit
 //! - type_length_limit
 //! - pattern_complexity_limit
 //!
@@ -20,6 +21,8 @@ pub(crate) fn provide(providers: &mut Providers) {
             recursion_limit: get_recursion_limit(tcx.hir_krate_attrs(), tcx.sess),
             move_size_limit: find_attr!(attrs, MoveSizeLimit { limit, .. } => *limit)
                 .unwrap_or(Limit::new(tcx.sess.opts.unstable_opts.move_size_limit.unwrap_or(0))),
+            future_size_limit: find_attr!(attrs, FutureSizeLimit { limit, .. } => *limit)
+                .unwrap_or(Limit::new(tcx.sess.opts.unstable_opts.future_size_limit.unwrap_or(0))),
             type_length_limit: find_attr!(attrs, TypeLengthLimit { limit, .. } => *limit)
                 .unwrap_or(Limit::new(2usize.pow(24))),
             pattern_complexity_limit:
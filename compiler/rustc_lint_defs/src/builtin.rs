@@ -60,6 +60,7 @@
         INVALID_TYPE_PARAM_DEFAULT,
         IRREFUTABLE_LET_PATTERNS,
         LARGE_ASSIGNMENTS,
+        LARGE_FUTURES,
         LATE_BOUND_LIFETIME_ARGUMENTS,
         LEGACY_DERIVE_HELPERS,
         LINKER_INFO,
@@ -2999,6 +3000,52 @@
     "detects large moves or copies",
 }
 
+declare_lint! {
+    /// The `large_futures` lint detects `async` functions and blocks whose
+    /// futures are larger than the limit set with `#![future_size_limit]`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (depends on the layout of the future)
+    /// #![feature(large_futures)]
+    /// #![future_size_limit = "1024"]
+    ///
+    /// async fn foo() {
+    ///     let buf = [0u8; 4096];
+    ///     bar().await;
+    ///     drop(buf);
+    /// }
+    /// ```
+    ///
+    /// produces:
+    ///
+    /// ```text
+    /// warning: this future is 4098 bytes, which is larger than the limit of 1024 bytes
+    ///   --> src/lib.rs:4:1
+    ///    |
+    /// LL | async fn foo() {
+    ///    | ^^^^^^^^^^^^
+    /// LL |     let buf = [0u8; 4096];
+    ///    |         --- `[u8; 4096]` (4096 bytes) is held across an await point
+    /// LL |     bar().await;
+    ///    |     -----------
+    ///    |     |     |
+    ///    |     |     this await point holds 4098 bytes
+    ///    |     `{async fn body of bar()}` (1 bytes) is held across an await point
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// Futures store every local that is alive across an `.await`, so large
+    /// locals make the future itself large. Large futures can overflow the
+    /// stack of the executor polling them, which is only noticed at runtime.
+    /// The lint points at the locals that contribute most to the size of the
+    /// future, which can then be boxed or dropped before the `.await`.
+    pub LARGE_FUTURES,
+    Warn,
+    "detects `async` functions and blocks with large futures",
+}
+
 declare_lint! {
     /// The `unexpected_cfgs` lint detects unexpected conditional compilation conditions.
     ///
@@ -46,6 +46,7 @@
 #include "llvm/Transforms/Instrumentation/InstrProfiling.h"
 #include "llvm/Transforms/Instrumentation/MemorySanitizer.h"
 #include "llvm/Transforms/Instrumentation/RealtimeSanitizer.h"
+#include "llvm/Transforms/Instrumentation/SanitizerCoverage.h"
 #include "llvm/Transforms/Instrumentation/ThreadSanitizer.h"
 #include "llvm/Transforms/Scalar/AnnotationRemarks.h"
 #include "llvm/Transforms/Utils/CanonicalizeAliases.h"
@@ -527,6 +528,12 @@ struct LLVMRustSanitizerOptions {
   bool SanitizeAddress;
   bool SanitizeAddressRecover;
   bool SanitizeCFI;
+  bool SanitizeCoverageTracePCGuard;
+  bool SanitizeCoverageTraceCmp;
+  bool SanitizeCoverageInline8bitCounters;
+  bool SanitizeCoveragePCTable;
+  char **SanitizeCoverageIgnorelist;
+  size_t SanitizeCoverageIgnorelistLen;
   bool SanitizeDataFlow;
   char **SanitizeDataFlowABIList;
   size_t SanitizeDataFlowABIListLen;
@@ -711,6 +718,31 @@ extern "C" LLVMRustResult LLVMRustOptimize(
   }
 
   if (SanitizerOptions) {
+    if (SanitizerOptions->SanitizeCoverageTracePCGuard ||
+        SanitizerOptions->SanitizeCoverageTraceCmp ||
+        SanitizerOptions->SanitizeCoverageInline8bitCounters) {
+      SanitizerCoverageOptions Options;
+      // The pass does nothing at all without a coverage type, and edges are
+      // what fuzzers expect, like clang's default.
+      Options.CoverageType = SanitizerCoverageOptions::SCK_Edge;
+      Options.TracePCGuard = SanitizerOptions->SanitizeCoverageTracePCGuard;
+      Options.TraceCmp = SanitizerOptions->SanitizeCoverageTraceCmp;
+      Options.Inline8bitCounters =
+          SanitizerOptions->SanitizeCoverageInline8bitCounters;
+      Options.PCTable = SanitizerOptions->SanitizeCoveragePCTable;
+      std::vector<std::string> IgnorelistFiles(
+          SanitizerOptions->SanitizeCoverageIgnorelist,
+          SanitizerOptions->SanitizeCoverageIgnorelist +
+              SanitizerOptions->SanitizeCoverageIgnorelistLen);
+      OptimizerLastEPCallbacks.push_back(
+          [Options, IgnorelistFiles](ModulePassManager &MPM,
+                                     OptimizationLevel Level,
+                                     ThinOrFullLTOPhase phase) {
+            MPM.addPass(SanitizerCoveragePass(
+                Options, /*AllowlistFiles=*/{}, IgnorelistFiles));
+          });
+    }
+
     if (SanitizerOptions->SanitizeDataFlow) {
       std::vector<std::string> ABIListFiles(
           SanitizerOptions->SanitizeDataFlowABIList,
@@ -113,6 +113,12 @@ fn clone(&self) -> Self { *self }
 
     /// Serializes all eligible query return values into the on-disk cache.
     hook encode_query_values(encoder: &mut CacheEncoder<'_, 'tcx>) -> ();
+
+    /// Runs the MIR passes registered by drivers (e.g. through `rustc_public`) on a body that
+    /// went through the optimization pipeline. Returns whether the body was changed.
+    ///
+    /// The default provider registers no passes and leaves the body untouched.
+    hook run_external_mir_passes(body: &mut mir::Body<'tcx>) -> bool;
 }
 
 #[cold]
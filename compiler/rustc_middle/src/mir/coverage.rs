@@ -50,6 +50,23 @@ pub struct CounterId {}
     pub struct ExpressionId {}
 }
 
+rustc_index::newtype_index! {
+    /// ID of a condition within an MC/DC decision. Values ascend from 0, in
+    /// the order in which the conditions of the decision are first visited.
+    ///
+    /// LLVM handles condition IDs as `int16_t`, so a decision can have at most
+    /// `i16::MAX` conditions.
+    #[stable_hash]
+    #[encodable]
+    #[max = 0x7FFF]
+    #[debug_format = "ConditionId({})"]
+    pub struct ConditionId {}
+}
+
+impl ConditionId {
+    pub const START: Self = Self::from_usize(0);
+}
+
 /// Enum that can hold a constant zero value, the ID of an physical coverage
 /// counter, or the ID of a coverage-counter expression.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -90,6 +107,21 @@ pub enum CoverageKind {
     /// During codegen, this might be lowered to `llvm.instrprof.increment` or
     /// to a no-op, depending on the outcome of counter-creation.
     VirtualCounter { bcb: BasicCoverageBlock },
+
+    /// Marks a point in MIR control flow where an MC/DC condition has been
+    /// evaluated, by setting bit `index` of the condition bitmap of the
+    /// decision at `decision_depth`.
+    ///
+    /// During codegen, this is lowered to an update of a local variable that
+    /// holds the condition bitmap.
+    CondBitmapUpdate { index: u32, decision_depth: u16 },
+
+    /// Marks a point in MIR control flow where an MC/DC decision has been
+    /// fully evaluated, so that the test vector recorded in the condition
+    /// bitmap of the decision at `decision_depth` can be stored.
+    ///
+    /// During codegen, this is lowered to `llvm.instrprof.mcdc.tvbitmap.update`.
+    TestVectorBitmapUpdate { bitmap_idx: u32, decision_depth: u16 },
 }
 
 impl Debug for CoverageKind {
@@ -99,6 +131,12 @@ fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
             SpanMarker => write!(fmt, "SpanMarker"),
             BlockMarker { id } => write!(fmt, "BlockMarker({:?})", id.index()),
             VirtualCounter { bcb } => write!(fmt, "VirtualCounter({bcb:?})"),
+            CondBitmapUpdate { index, decision_depth } => {
+                write!(fmt, "CondBitmapUpdate(index={index:?}, depth={decision_depth:?})")
+            }
+            TestVectorBitmapUpdate { bitmap_idx, decision_depth } => {
+                write!(fmt, "TestVectorUpdate({bitmap_idx:?}, depth={decision_depth:?})")
+            }
         }
     }
 }
@@ -135,6 +173,15 @@ pub enum MappingKind {
     Code { bcb: BasicCoverageBlock },
     /// Associates a branch region with separate counters for true and false.
     Branch { true_bcb: BasicCoverageBlock, false_bcb: BasicCoverageBlock },
+    /// Associates a branch region with separate counters for true and false,
+    /// and with its position in the condition tree of an MC/DC decision.
+    MCDCBranch {
+        true_bcb: BasicCoverageBlock,
+        false_bcb: BasicCoverageBlock,
+        mcdc_params: ConditionInfo,
+    },
+    /// Associates a decision region with the bitmap that records its test vectors.
+    MCDCDecision(DecisionInfo),
 }
 
 #[derive(Clone, Debug)]
@@ -158,6 +205,13 @@ pub struct FunctionCoverageInfo {
     pub priority_list: Vec<BasicCoverageBlock>,
 
     pub mappings: Vec<Mapping>,
+
+    /// Number of bits in the test vector bitmaps of all MC/DC decisions in
+    /// this function.
+    pub mcdc_bitmap_bits: usize,
+    /// Number of condition bitmaps that codegen needs to allocate, which is
+    /// one more than the depth of the most deeply nested MC/DC decision.
+    pub mcdc_num_condition_bitmaps: usize,
 }
 
 /// Coverage information for a function, recorded during MIR building and
@@ -174,6 +228,11 @@ pub struct CoverageInfoHi {
     /// data structures without having to scan the entire body first.
     pub num_block_markers: usize,
     pub branch_spans: Vec<BranchSpan>,
+    /// MC/DC decisions, each with the branch spans of its conditions.
+    ///
+    /// Conditions that could not be made part of a decision (e.g. because
+    /// the decision has too many conditions) are in `branch_spans` instead.
+    pub mcdc_spans: Vec<(MCDCDecisionSpan, Vec<MCDCBranchSpan>)>,
 }
 
 #[derive(Clone, Debug)]
@@ -184,6 +243,48 @@ pub struct BranchSpan {
     pub false_marker: BlockMarkerId,
 }
 
+/// Position of a condition in the condition tree of an MC/DC decision.
+///
+/// A "next" ID of `None` means that the decision is fully evaluated when the
+/// condition takes the corresponding value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(TyEncodable, TyDecodable, Hash, HashStable)]
+pub struct ConditionInfo {
+    pub condition_id: ConditionId,
+    pub true_next_id: Option<ConditionId>,
+    pub false_next_id: Option<ConditionId>,
+}
+
+#[derive(Clone, Debug)]
+#[derive(TyEncodable, TyDecodable, Hash, HashStable)]
+pub struct MCDCBranchSpan {
+    pub span: Span,
+    pub condition_info: ConditionInfo,
+    pub true_marker: BlockMarkerId,
+    pub false_marker: BlockMarkerId,
+}
+
+#[derive(Copy, Clone, Debug)]
+#[derive(TyEncodable, TyDecodable, Hash, HashStable)]
+pub struct DecisionInfo {
+    /// Index one past the last bit of this decision in the function's test
+    /// vector bitmap, which is how LLVM's coverage mapping format locates it.
+    pub bitmap_idx: u32,
+    pub num_conditions: u16,
+}
+
+#[derive(Clone, Debug)]
+#[derive(TyEncodable, TyDecodable, Hash, HashStable)]
+pub struct MCDCDecisionSpan {
+    pub span: Span,
+    /// Markers of the blocks in which the decision is known to be fully evaluated.
+    pub end_markers: Vec<BlockMarkerId>,
+    /// Number of decisions that this decision is nested within, e.g. when an
+    /// `if` appears inside the condition of another `if`.
+    pub decision_depth: u16,
+    pub num_conditions: usize,
+}
+
 /// Contains information needed during codegen, obtained by inspecting the
 /// function's MIR after MIR optimizations.
 ///
@@ -120,7 +120,7 @@
 use crate::ty::util::AlwaysRequiresDrop;
 use crate::ty::{
     self, CrateInherentImpls, GenericArg, GenericArgsRef, LitToConstInput, PseudoCanonicalInput,
-    SizedTraitKind, Ty, TyCtxt, TyCtxtFeed,
+    SizedTraitKind, Ty, TyCtxt, TyCtxtFeed, UnusedGenericParams,
 };
 use crate::{mir, thir};
 
@@ -2714,6 +2714,13 @@
         cache_on_disk_if { true }
     }
 
+    /// Determines which generic parameters the body of `key` does not depend on, so that
+    /// instantiations differing only in those parameters can share a mono item. All parameters
+    /// are considered used unless `-Zpolymorphize` is enabled.
+    query unused_generic_params(key: ty::InstanceKind<'tcx>) -> UnusedGenericParams {
+        desc { "determining which generic parameters are unused by `{}`", tcx.def_path_str(key.def_id()) }
+    }
+
     query size_estimate(key: ty::Instance<'tcx>) -> usize {
         desc { "estimating codegen size of `{}`", key }
         cache_on_disk_if { true }
@@ -383,6 +383,7 @@ impl Erasable for $ty {
     rustc_middle::ty::fast_reject::SimplifiedType,
     rustc_middle::ty::ImplPolarity,
     rustc_middle::ty::util::AlwaysRequiresDrop,
+    rustc_middle::ty::UnusedGenericParams,
     rustc_middle::ty::Visibility<rustc_span::def_id::DefId>,
     rustc_middle::middle::codegen_fn_attrs::SanitizerFnAttrs,
     rustc_session::config::CrateType,
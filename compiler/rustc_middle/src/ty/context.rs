@@ -1643,6 +1643,10 @@ pub fn move_size_limit(self) -> Limit {
         self.limits(()).move_size_limit
     }
 
+    pub fn future_size_limit(self) -> Limit {
+        self.limits(()).future_size_limit
+    }
+
     pub fn pattern_complexity_limit(self) -> Limit {
         self.limits(()).pattern_complexity_limit
     }
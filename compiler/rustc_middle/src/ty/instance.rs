@@ -190,6 +190,31 @@ pub fn ty(&self, tcx: TyCtxt<'tcx>, typing_env: ty::TypingEnv<'tcx>) -> Ty<'tcx>
         tcx.instantiate_and_normalize_erasing_regions(self.args, typing_env, ty)
     }
 
+    /// Replaces the generic arguments for the parameters that the body of this instance does not
+    /// depend on with the parameters themselves, so that all instantiations that only differ in
+    /// those arguments share the same mono item.
+    ///
+    /// This is a no-op unless `-Zpolymorphize` is enabled, see `unused_generic_params`.
+    pub fn polymorphize(self, tcx: TyCtxt<'tcx>) -> Self {
+        let InstanceKind::Item(def_id) = self.def else {
+            return self;
+        };
+        let unused = tcx.unused_generic_params(self.def);
+        if unused.all_used() {
+            return self;
+        }
+
+        debug!("polymorphize: unused={:?} instance={:?}", unused, self);
+        let args = GenericArgs::for_item(tcx, def_id, |param, _| {
+            if unused.is_unused(param.index) {
+                tcx.mk_param_from_def(param)
+            } else {
+                self.args[param.index as usize]
+            }
+        });
+        Self { def: self.def, args }
+    }
+
     /// Finds a crate that contains a monomorphization of this instance that
     /// can be linked to from the local crate. A return value of `None` means
     /// no upstream crate provides such an exported monomorphization.
@@ -940,3 +965,37 @@ fn needs_fn_once_adapter_shim(
         (ty::ClosureKind::FnMut | ty::ClosureKind::FnOnce, _) => Err(()),
     }
 }
+
+/// The generic parameters of an item that its body does not depend on, as computed by the
+/// `unused_generic_params` query. Only the first 32 parameters are tracked, any other parameter
+/// is always considered used.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, HashStable)]
+pub struct UnusedGenericParams(u32);
+
+impl UnusedGenericParams {
+    pub fn new_all_unused(amount: u32) -> Self {
+        Self(if amount >= u32::BITS { u32::MAX } else { (1 << amount) - 1 })
+    }
+
+    pub fn new_all_used() -> Self {
+        Self(0)
+    }
+
+    pub fn mark_used(&mut self, idx: u32) {
+        if idx < u32::BITS {
+            self.0 &= !(1 << idx);
+        }
+    }
+
+    pub fn is_unused(&self, idx: u32) -> bool {
+        idx < u32::BITS && self.0 & (1 << idx) != 0
+    }
+
+    pub fn is_used(&self, idx: u32) -> bool {
+        !self.is_unused(idx)
+    }
+
+    pub fn all_used(&self) -> bool {
+        self.0 == 0
+    }
+}
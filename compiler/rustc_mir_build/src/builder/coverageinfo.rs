@@ -8,8 +8,11 @@
 use rustc_middle::ty::TyCtxt;
 use rustc_span::def_id::LocalDefId;
 
+use crate::builder::coverageinfo::mcdc::MCDCInfoBuilder;
 use crate::builder::{Builder, CFG};
 
+mod mcdc;
+
 /// Collects coverage-related information during MIR building, to eventually be
 /// turned into a function's [`CoverageInfoHi`] when MIR building is complete.
 pub(crate) struct CoverageInfoBuilder {
@@ -20,6 +23,8 @@ pub(crate) struct CoverageInfoBuilder {
 
     /// Present if branch coverage is enabled.
     branch_info: Option<BranchInfo>,
+    /// Present if MC/DC coverage is enabled.
+    mcdc_info: Option<MCDCInfoBuilder>,
 }
 
 #[derive(Default)]
@@ -78,6 +83,7 @@ pub(crate) fn new_if_enabled(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Self
             nots: FxHashMap::default(),
             markers: BlockMarkerGen::default(),
             branch_info: tcx.sess.instrument_coverage_branch().then(BranchInfo::default),
+            mcdc_info: tcx.sess.instrument_coverage_mcdc().then(MCDCInfoBuilder::new),
         })
     }
 
@@ -129,6 +135,7 @@ fn visit_with_not_info(&mut self, thir: &Thir<'_>, expr_id: ExprId, not_info: No
 
     fn register_two_way_branch<'tcx>(
         &mut self,
+        tcx: TyCtxt<'tcx>,
         cfg: &mut CFG<'tcx>,
         source_info: SourceInfo,
         true_block: BasicBlock,
@@ -140,37 +147,49 @@ fn register_two_way_branch<'tcx>(
         let true_marker = self.markers.inject_block_marker(cfg, source_info, true_block);
         let false_marker = self.markers.inject_block_marker(cfg, source_info, false_block);
 
-        branch_info.branch_spans.push(BranchSpan {
-            span: source_info.span,
-            true_marker,
-            false_marker,
-        });
+        let branch_span = BranchSpan { span: source_info.span, true_marker, false_marker };
+
+        // If MC/DC is enabled, the branch may be a condition of a decision.
+        match self.mcdc_info.as_mut() {
+            Some(mcdc_info) => {
+                mcdc_info.visit_evaluated_condition(tcx, branch_span, &mut branch_info.branch_spans)
+            }
+            None => branch_info.branch_spans.push(branch_span),
+        }
     }
 
     pub(crate) fn into_done(self) -> Box<CoverageInfoHi> {
-        let Self { nots: _, markers: BlockMarkerGen { num_block_markers }, branch_info } = self;
+        let Self { nots: _, markers: BlockMarkerGen { num_block_markers }, branch_info, mcdc_info } =
+            self;
 
         let branch_spans =
             branch_info.map(|branch_info| branch_info.branch_spans).unwrap_or_default();
+        let mcdc_spans = mcdc_info.map(MCDCInfoBuilder::into_mcdc_spans).unwrap_or_default();
 
         // For simplicity, always return an info struct (without Option), even
         // if there's nothing interesting in it.
-        Box::new(CoverageInfoHi { num_block_markers, branch_spans })
+        Box::new(CoverageInfoHi { num_block_markers, branch_spans, mcdc_spans })
     }
 
     pub(crate) fn as_done(&self) -> Box<CoverageInfoHi> {
-        let &Self { nots: _, markers: BlockMarkerGen { num_block_markers }, ref branch_info } =
-            self;
+        let &Self {
+            nots: _,
+            markers: BlockMarkerGen { num_block_markers },
+            ref branch_info,
+            ref mcdc_info,
+        } = self;
 
         let branch_spans = branch_info
             .as_ref()
             .map(|branch_info| branch_info.branch_spans.as_slice())
             .unwrap_or_default()
             .to_owned();
+        let mcdc_spans =
+            mcdc_info.as_ref().map(MCDCInfoBuilder::mcdc_spans).unwrap_or_default().to_owned();
 
         // For simplicity, always return an info struct (without Option), even
         // if there's nothing interesting in it.
-        Box::new(CoverageInfoHi { num_block_markers, branch_spans })
+        Box::new(CoverageInfoHi { num_block_markers, branch_spans, mcdc_spans })
     }
 }
 
@@ -223,7 +242,13 @@ pub(crate) fn visit_coverage_standalone_condition(
             mir::TerminatorKind::if_(mir::Operand::Copy(place), true_block, false_block),
         );
 
-        coverage_info.register_two_way_branch(&mut self.cfg, source_info, true_block, false_block);
+        coverage_info.register_two_way_branch(
+            self.tcx,
+            &mut self.cfg,
+            source_info,
+            true_block,
+            false_block,
+        );
 
         let join_block = self.cfg.start_new_block();
         self.cfg.goto(true_block, source_info, join_block);
@@ -254,13 +279,21 @@ pub(crate) fn visit_coverage_branch_condition(
 
         let source_info = SourceInfo { span: self.thir[expr_id].span, scope: self.source_scope };
 
-        coverage_info.register_two_way_branch(&mut self.cfg, source_info, then_block, else_block);
+        coverage_info.register_two_way_branch(
+            self.tcx,
+            &mut self.cfg,
+            source_info,
+            then_block,
+            else_block,
+        );
     }
 
     /// If branch coverage is enabled, inject marker statements into `true_block`
     /// and `false_block`, and record their IDs in the table of branches.
     ///
-    /// Used to instrument let-else and if-let (including let-chains) for branch coverage.
+    /// Used to instrument let-else and if-let (including let-chains and if-let
+    /// guards) for branch coverage. If MC/DC is enabled, a `let` in a let-chain
+    /// is a condition of the chain's decision.
     pub(crate) fn visit_coverage_conditional_let(
         &mut self,
         pattern: &Pat<'tcx>, // Pattern that has been matched when the true path is taken
@@ -271,6 +304,12 @@ pub(crate) fn visit_coverage_conditional_let(
         let Some(coverage_info) = self.coverage_info.as_mut() else { return };
 
         let source_info = SourceInfo { span: pattern.span, scope: self.source_scope };
-        coverage_info.register_two_way_branch(&mut self.cfg, source_info, true_block, false_block);
+        coverage_info.register_two_way_branch(
+            self.tcx,
+            &mut self.cfg,
+            source_info,
+            true_block,
+            false_block,
+        );
     }
 }
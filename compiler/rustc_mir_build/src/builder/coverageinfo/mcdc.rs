@@ -0,0 +1,304 @@
+use rustc_data_structures::fx::FxHashSet;
+use rustc_middle::bug;
+use rustc_middle::mir::coverage::{
+    BranchSpan, ConditionId, ConditionInfo, MCDCBranchSpan, MCDCDecisionSpan,
+};
+use rustc_middle::thir::{ExprId, ExprKind, LogicalOp, Thir};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
+
+use crate::builder::Builder;
+use crate::errors::MCDCExceedsConditionLimit;
+
+/// LLVM uses `int16_t` to represent condition IDs, so `i16::MAX` is the hard
+/// limit for the number of conditions in a decision.
+const MAX_CONDITIONS_IN_DECISION: usize = i16::MAX as usize;
+
+/// State of the decision that is currently being lowered at one level of
+/// decision nesting.
+#[derive(Default)]
+struct MCDCDecisionCtx {
+    /// Condition infos of the operands that have been assigned a position in
+    /// the condition tree but have not been lowered yet. Operands are visited
+    /// in pre-order, so the next operand to be lowered is on top.
+    decision_stack: Vec<ConditionInfo>,
+    processing_decision: Option<MCDCDecisionSpan>,
+    conditions: Vec<MCDCBranchSpan>,
+}
+
+struct MCDCState {
+    /// One context per level of decision nesting. The bottom context is never
+    /// popped.
+    decision_ctx_stack: Vec<MCDCDecisionCtx>,
+}
+
+impl MCDCState {
+    fn new() -> Self {
+        Self { decision_ctx_stack: vec![MCDCDecisionCtx::default()] }
+    }
+
+    /// Decision depth is given as a `u16` to keep `CoverageKind` small, as it is
+    /// very unlikely that decisions are ever nested that deeply.
+    fn decision_depth(&self) -> u16 {
+        match self.decision_ctx_stack.len().checked_sub(1) {
+            Some(depth) => u16::try_from(depth).expect("decision depth did not fit in u16"),
+            None => bug!("unexpected empty decision stack"),
+        }
+    }
+
+    /// Assigns condition IDs to both operands of a `&&` or `||` expression.
+    ///
+    /// The LHS inherits the ID of the expression itself, and the RHS gets a new
+    /// ID. For example, `(A && B) || C` is visited like this:
+    ///
+    /// ```text
+    /// (A && B) || C    (A && B) => ID 0, C => ID 1
+    ///  A && B          A => ID 0, B => ID 2
+    /// ```
+    ///
+    /// Operands are visited in pre-order, so the condition info of an operator
+    /// is always known when its operands are visited:
+    /// - For `&&`, the LHS continues with the RHS if it is true, and both
+    ///   operands continue like the whole expression if they are false.
+    /// - For `||`, the LHS continues with the RHS if it is false, and both
+    ///   operands continue like the whole expression if they are true.
+    fn record_conditions(&mut self, op: LogicalOp, span: Span) {
+        let decision_depth = self.decision_depth();
+        let Some(decision_ctx) = self.decision_ctx_stack.last_mut() else {
+            bug!("unexpected empty decision stack")
+        };
+        let decision = match decision_ctx.processing_decision.as_mut() {
+            Some(decision) => {
+                decision.span = decision.span.to(span);
+                decision
+            }
+            None => decision_ctx.processing_decision.insert(MCDCDecisionSpan {
+                span,
+                end_markers: vec![],
+                decision_depth,
+                num_conditions: 0,
+            }),
+        };
+
+        let parent_condition = decision_ctx.decision_stack.pop().unwrap_or_else(|| {
+            assert_eq!(
+                decision.num_conditions, 0,
+                "decision stack must be empty only for a new decision"
+            );
+            decision.num_conditions += 1;
+            ConditionInfo {
+                condition_id: ConditionId::START,
+                true_next_id: None,
+                false_next_id: None,
+            }
+        });
+        let lhs_id = parent_condition.condition_id;
+
+        // Decisions with too many conditions are degraded to plain branches
+        // when they are finished, so saturate instead of overflowing here.
+        let rhs_id =
+            ConditionId::from_usize(decision.num_conditions.min(ConditionId::MAX.as_usize()));
+        decision.num_conditions += 1;
+
+        let (lhs, rhs) = match op {
+            LogicalOp::And => (
+                ConditionInfo {
+                    condition_id: lhs_id,
+                    true_next_id: Some(rhs_id),
+                    false_next_id: parent_condition.false_next_id,
+                },
+                ConditionInfo { condition_id: rhs_id, ..parent_condition },
+            ),
+            LogicalOp::Or => (
+                ConditionInfo {
+                    condition_id: lhs_id,
+                    true_next_id: parent_condition.true_next_id,
+                    false_next_id: Some(rhs_id),
+                },
+                ConditionInfo { condition_id: rhs_id, ..parent_condition },
+            ),
+        };
+        // The LHS is lowered first, so it goes on top.
+        decision_ctx.decision_stack.push(rhs);
+        decision_ctx.decision_stack.push(lhs);
+    }
+
+    /// Records an evaluated condition as part of the current decision, and
+    /// returns the decision and its conditions if this was the last one.
+    ///
+    /// A condition that is not part of any `&&` or `||` chain is recorded as a
+    /// plain branch in `branch_spans` instead.
+    fn try_finish_decision(
+        &mut self,
+        branch_span: BranchSpan,
+        branch_spans: &mut Vec<BranchSpan>,
+    ) -> Option<(MCDCDecisionSpan, Vec<MCDCBranchSpan>)> {
+        let Some(decision_ctx) = self.decision_ctx_stack.last_mut() else {
+            bug!("unexpected empty decision stack")
+        };
+        let Some(condition_info) = decision_ctx.decision_stack.pop() else {
+            branch_spans.push(branch_span);
+            return None;
+        };
+        let Some(decision) = decision_ctx.processing_decision.as_mut() else {
+            bug!("decision should have been created before any of its conditions were visited")
+        };
+
+        let BranchSpan { span, true_marker, false_marker } = branch_span;
+        if condition_info.true_next_id.is_none() {
+            decision.end_markers.push(true_marker);
+        }
+        if condition_info.false_next_id.is_none() {
+            decision.end_markers.push(false_marker);
+        }
+        decision_ctx.conditions.push(MCDCBranchSpan {
+            span,
+            condition_info,
+            true_marker,
+            false_marker,
+        });
+
+        if decision_ctx.decision_stack.is_empty() {
+            let conditions = std::mem::take(&mut decision_ctx.conditions);
+            decision_ctx.processing_decision.take().map(|decision| (decision, conditions))
+        } else {
+            None
+        }
+    }
+}
+
+/// Collects MC/DC decisions during MIR building. Present in
+/// [`CoverageInfoBuilder`](super::CoverageInfoBuilder) if MC/DC coverage is enabled.
+pub(crate) struct MCDCInfoBuilder {
+    mcdc_spans: Vec<(MCDCDecisionSpan, Vec<MCDCBranchSpan>)>,
+    state: MCDCState,
+    /// `&&`/`||` expressions that are the RHS of another `&&`/`||` expression
+    /// lowered as a value, and therefore continue its decision instead of
+    /// starting a new one.
+    continued_ops: FxHashSet<ExprId>,
+}
+
+impl MCDCInfoBuilder {
+    pub(crate) fn new() -> Self {
+        Self { mcdc_spans: vec![], state: MCDCState::new(), continued_ops: FxHashSet::default() }
+    }
+
+    pub(crate) fn visit_evaluated_condition(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        branch_span: BranchSpan,
+        branch_spans: &mut Vec<BranchSpan>,
+    ) {
+        let Some((decision, conditions)) =
+            self.state.try_finish_decision(branch_span, branch_spans)
+        else {
+            return;
+        };
+
+        let num_conditions = conditions.len();
+        assert_eq!(
+            num_conditions, decision.num_conditions,
+            "final number of conditions is not correct"
+        );
+        match num_conditions {
+            0 => unreachable!("decision with no condition is not expected"),
+            1..=MAX_CONDITIONS_IN_DECISION => self.mcdc_spans.push((decision, conditions)),
+            _ => {
+                // Report the conditions as plain branches instead.
+                branch_spans.extend(conditions.into_iter().map(
+                    |MCDCBranchSpan { span, condition_info: _, true_marker, false_marker }| {
+                        BranchSpan { span, true_marker, false_marker }
+                    },
+                ));
+
+                tcx.dcx().emit_warn(MCDCExceedsConditionLimit {
+                    span: decision.span,
+                    num_conditions,
+                    max_conditions: MAX_CONDITIONS_IN_DECISION,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn mcdc_spans(&self) -> &[(MCDCDecisionSpan, Vec<MCDCBranchSpan>)] {
+        &self.mcdc_spans
+    }
+
+    pub(crate) fn into_mcdc_spans(self) -> Vec<(MCDCDecisionSpan, Vec<MCDCBranchSpan>)> {
+        self.mcdc_spans
+    }
+}
+
+impl<'tcx> Builder<'_, 'tcx> {
+    /// If MC/DC coverage is enabled, assigns positions in the condition tree of
+    /// the current decision to the operands of a `&&` or `||` expression that
+    /// is being lowered as a branch condition.
+    pub(crate) fn visit_coverage_branch_operation(&mut self, logical_op: LogicalOp, span: Span) {
+        if let Some(coverage_info) = self.coverage_info.as_mut()
+            && let Some(mcdc_info) = coverage_info.mcdc_info.as_mut()
+        {
+            mcdc_info.state.record_conditions(logical_op, span);
+        }
+    }
+
+    /// Like [`Builder::visit_coverage_branch_operation`], but for a `&&` or `||`
+    /// expression that is being lowered as a value, e.g. in `let x = a && b;`.
+    ///
+    /// Such an expression starts a new, nested decision, unless it is the RHS of
+    /// another `&&` or `||` expression lowered as a value. Returns true if a
+    /// decision was started, in which case the caller must call
+    /// [`Builder::mcdc_decrement_depth_if_enabled`] once the whole expression
+    /// has been lowered.
+    pub(crate) fn visit_coverage_value_operation(
+        &mut self,
+        expr_id: ExprId,
+        logical_op: LogicalOp,
+        rhs: ExprId,
+        span: Span,
+    ) -> bool {
+        let Some(coverage_info) = self.coverage_info.as_mut() else { return false };
+        let Some(mcdc_info) = coverage_info.mcdc_info.as_mut() else { return false };
+
+        let is_decision_root = !mcdc_info.continued_ops.remove(&expr_id);
+        if is_decision_root {
+            mcdc_info.state.decision_ctx_stack.push(MCDCDecisionCtx::default());
+        }
+        let rhs = strip_wrappers(self.thir, rhs);
+        if let ExprKind::LogicalOp { .. } = self.thir[rhs].kind {
+            mcdc_info.continued_ops.insert(rhs);
+        }
+        mcdc_info.state.record_conditions(logical_op, span);
+        is_decision_root
+    }
+
+    /// If MC/DC coverage is enabled, starts a new level of decision nesting,
+    /// so that the conditions of an `if` or match guard are not mixed up with
+    /// those of any decision it is nested within.
+    pub(crate) fn mcdc_increment_depth_if_enabled(&mut self) {
+        if let Some(coverage_info) = self.coverage_info.as_mut()
+            && let Some(mcdc_info) = coverage_info.mcdc_info.as_mut()
+        {
+            mcdc_info.state.decision_ctx_stack.push(MCDCDecisionCtx::default());
+        }
+    }
+
+    pub(crate) fn mcdc_decrement_depth_if_enabled(&mut self) {
+        if let Some(coverage_info) = self.coverage_info.as_mut()
+            && let Some(mcdc_info) = coverage_info.mcdc_info.as_mut()
+            && mcdc_info.state.decision_ctx_stack.pop().is_none()
+        {
+            bug!("unexpected empty decision stack");
+        }
+    }
+}
+
+/// Removes any `Scope` and `Use` wrappers, which are lowered by lowering the
+/// expression they wrap.
+fn strip_wrappers(thir: &Thir<'_>, mut expr_id: ExprId) -> ExprId {
+    while let ExprKind::Use { source: inner } | ExprKind::Scope { value: inner, .. } =
+        thir[expr_id].kind
+    {
+        expr_id = inner;
+    }
+    expr_id
+}
@@ -1,7 +1,9 @@
 use itertools::Itertools;
 use rustc_data_structures::fx::{FxIndexMap, FxIndexSet, IndexEntry};
 use rustc_middle::mir;
-use rustc_middle::mir::coverage::{BasicCoverageBlock, BranchSpan};
+use rustc_middle::mir::coverage::{
+    BasicCoverageBlock, BranchSpan, MCDCBranchSpan, MCDCDecisionSpan,
+};
 use rustc_span::{ExpnId, ExpnKind, Span};
 
 use crate::coverage::from_mir;
@@ -61,6 +63,8 @@ pub(crate) struct ExpnNode {
 
     /// Branch spans (recorded during MIR building) belonging to this expansion.
     pub(crate) branch_spans: Vec<BranchSpan>,
+    /// MC/DC decisions (recorded during MIR building) belonging to this expansion.
+    pub(crate) mcdc_spans: Vec<(MCDCDecisionSpan, Vec<MCDCBranchSpan>)>,
 
     /// Hole spans belonging to this expansion, to be carved out from the
     /// code spans during span refinement.
@@ -90,6 +94,7 @@ fn new(expn_id: ExpnId) -> Self {
             minmax_bcbs: None,
 
             branch_spans: vec![],
+            mcdc_spans: vec![],
 
             hole_spans: vec![],
         }
@@ -171,14 +176,19 @@ pub(crate) fn build_expn_tree(
         node.hole_spans.push(hole_span);
     }
 
-    // Associate each branch span (recorded during MIR building) with its
-    // corresponding expansion tree node.
+    // Associate each branch span and MC/DC decision (recorded during MIR
+    // building) with its corresponding expansion tree node.
     if let Some(coverage_info_hi) = mir_body.coverage_info_hi.as_deref() {
         for branch_span in &coverage_info_hi.branch_spans {
             if let Some(node) = nodes.get_mut(&branch_span.span.ctxt().outer_expn()) {
                 node.branch_spans.push(BranchSpan::clone(branch_span));
             }
         }
+        for mcdc_span @ (decision, _) in &coverage_info_hi.mcdc_spans {
+            if let Some(node) = nodes.get_mut(&decision.span.ctxt().outer_expn()) {
+                node.mcdc_spans.push(mcdc_span.clone());
+            }
+        }
     }
 
     Ok(ExpnTree { nodes })
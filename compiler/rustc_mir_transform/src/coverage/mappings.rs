@@ -1,15 +1,24 @@
+use std::collections::VecDeque;
+
+use rustc_data_structures::fx::FxIndexMap;
 use rustc_index::IndexVec;
 use rustc_middle::mir::coverage::{
-    BlockMarkerId, BranchSpan, CoverageInfoHi, CoverageKind, Mapping, MappingKind,
+    BasicCoverageBlock, BlockMarkerId, BranchSpan, ConditionId, ConditionInfo, CoverageInfoHi,
+    CoverageKind, DecisionInfo, MCDCBranchSpan, MCDCDecisionSpan, Mapping, MappingKind,
 };
 use rustc_middle::mir::{self, BasicBlock, StatementKind};
 use rustc_middle::ty::TyCtxt;
-use rustc_span::ExpnKind;
+use rustc_span::{ExpnKind, Span};
 
 use crate::coverage::expansion::{self, ExpnTree};
 use crate::coverage::graph::CoverageGraph;
 use crate::coverage::hir_info::ExtractedHirInfo;
 use crate::coverage::spans::extract_refined_covspans;
+use crate::errors::MCDCExceedsTestVectorLimit;
+
+/// LLVM represents bitmap indices as `int32_t`, so the test vector bitmaps of
+/// all decisions in a function can have at most `i32::MAX` bits in total.
+const MCDC_MAX_BITMAP_SIZE: usize = i32::MAX as usize;
 
 /// Indicates why mapping extraction failed, for debug-logging purposes.
 #[derive(Debug)]
@@ -21,6 +30,33 @@ pub(crate) enum MappingsError {
 #[derive(Default)]
 pub(crate) struct ExtractedMappings {
     pub(crate) mappings: Vec<Mapping>,
+    /// MC/DC decisions, which need extra statements to be injected into MIR.
+    pub(crate) mcdc_decisions: Vec<MCDCDecision>,
+    pub(crate) mcdc_bitmap_bits: usize,
+    pub(crate) mcdc_num_condition_bitmaps: usize,
+}
+
+/// An MC/DC decision whose markers have been resolved to coverage graph nodes.
+#[derive(Debug)]
+pub(crate) struct MCDCDecision {
+    /// Nodes in which the decision is known to be fully evaluated.
+    pub(crate) end_bcbs: Vec<BasicCoverageBlock>,
+    pub(crate) bitmap_idx: usize,
+    pub(crate) decision_depth: u16,
+    pub(crate) conditions: Vec<MCDCCondition>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MCDCCondition {
+    span: Span,
+    pub(crate) true_bcb: BasicCoverageBlock,
+    pub(crate) false_bcb: BasicCoverageBlock,
+    condition_info: ConditionInfo,
+    /// Amounts to add to the condition bitmap when the condition is true or
+    /// false, so that each test vector ends up with a distinct index.
+    /// See [`calc_test_vectors_index`].
+    pub(crate) true_index: usize,
+    pub(crate) false_index: usize,
 }
 
 /// Extracts coverage-relevant spans from MIR, and uses them to create
@@ -40,11 +76,31 @@ pub(crate) fn extract_mappings_from_mir<'tcx>(
 
     extract_branch_mappings(mir_body, hir_info, graph, &expn_tree, &mut mappings);
 
+    let mut mcdc_decisions = vec![];
+    let mut mcdc_bitmap_bits = 0;
+    extract_mcdc_mappings(
+        tcx,
+        mir_body,
+        hir_info,
+        graph,
+        &expn_tree,
+        &mut mappings,
+        &mut mcdc_decisions,
+        &mut mcdc_bitmap_bits,
+    );
+
     if mappings.is_empty() {
         tracing::debug!("no mappings were extracted");
         return Err(MappingsError::NoMappings);
     }
-    Ok(ExtractedMappings { mappings })
+
+    let mcdc_num_condition_bitmaps = mcdc_decisions
+        .iter()
+        .map(|decision| usize::from(decision.decision_depth) + 1)
+        .max()
+        .unwrap_or(0);
+
+    Ok(ExtractedMappings { mappings, mcdc_decisions, mcdc_bitmap_bits, mcdc_num_condition_bitmaps })
 }
 
 fn resolve_block_markers(
@@ -96,3 +152,176 @@ fn extract_branch_mappings(
         },
     ));
 }
+
+fn extract_mcdc_mappings(
+    tcx: TyCtxt<'_>,
+    mir_body: &mir::Body<'_>,
+    hir_info: &ExtractedHirInfo,
+    graph: &CoverageGraph,
+    expn_tree: &ExpnTree,
+    mappings: &mut Vec<Mapping>,
+    mcdc_decisions: &mut Vec<MCDCDecision>,
+    mcdc_bitmap_bits: &mut usize,
+) {
+    let Some(coverage_info_hi) = mir_body.coverage_info_hi.as_deref() else { return };
+    let block_markers = resolve_block_markers(coverage_info_hi, mir_body);
+
+    // Like branch spans, ignore any decision that was introduced by expansion.
+    let Some(node) = expn_tree.get(hir_info.body_span.ctxt().outer_expn()) else { return };
+    if node.expn_kind != ExpnKind::Root {
+        return;
+    }
+
+    let bcb_from_marker = |marker: BlockMarkerId| graph.bcb_from_bb(block_markers[marker]?);
+
+    for (decision_span, branch_spans) in &node.mcdc_spans {
+        let &MCDCDecisionSpan { span, ref end_markers, decision_depth, num_conditions } =
+            decision_span;
+
+        // If any of the markers of a decision were removed by MIR building or
+        // are unreachable, give up on instrumenting the decision, but keep
+        // its conditions as ordinary branches.
+        let conditions: Option<Vec<_>> = branch_spans
+            .iter()
+            .map(|&MCDCBranchSpan { span, condition_info, true_marker, false_marker }| {
+                Some(MCDCCondition {
+                    span,
+                    true_bcb: bcb_from_marker(true_marker)?,
+                    false_bcb: bcb_from_marker(false_marker)?,
+                    condition_info,
+                    true_index: usize::MAX,
+                    false_index: usize::MAX,
+                })
+            })
+            .collect();
+        let end_bcbs: Option<Vec<_>> =
+            end_markers.iter().map(|&marker| bcb_from_marker(marker)).collect();
+        let (Some(mut conditions), Some(mut end_bcbs)) = (conditions, end_bcbs) else {
+            degrade_to_branches(branch_spans, &bcb_from_marker, mappings);
+            continue;
+        };
+        assert_eq!(conditions.len(), num_conditions);
+        // Recording the same test vector twice would record a bogus one the
+        // second time, as the condition bitmap is reset after recording.
+        end_bcbs.sort_unstable();
+        end_bcbs.dedup();
+
+        let num_test_vectors = calc_test_vectors_index(&mut conditions);
+        let Some(next_bitmap_bits) = mcdc_bitmap_bits
+            .checked_add(num_test_vectors)
+            .filter(|&bits| bits <= MCDC_MAX_BITMAP_SIZE)
+        else {
+            tcx.dcx().emit_warn(MCDCExceedsTestVectorLimit {
+                span,
+                max_num_test_vectors: MCDC_MAX_BITMAP_SIZE,
+            });
+            degrade_to_branches(branch_spans, &bcb_from_marker, mappings);
+            continue;
+        };
+        let bitmap_idx = std::mem::replace(mcdc_bitmap_bits, next_bitmap_bits);
+
+        mappings.push(Mapping {
+            span,
+            kind: MappingKind::MCDCDecision(DecisionInfo {
+                // The test vector updates are given the index of the first bit
+                // of the decision, but `llvm-cov` expects the decision mapping
+                // to point one past its last bit.
+                bitmap_idx: next_bitmap_bits as u32,
+                num_conditions: num_conditions as u16,
+            }),
+        });
+        mappings.extend(conditions.iter().map(
+            |&MCDCCondition { span, true_bcb, false_bcb, condition_info, .. }| Mapping {
+                span,
+                kind: MappingKind::MCDCBranch { true_bcb, false_bcb, mcdc_params: condition_info },
+            },
+        ));
+        mcdc_decisions.push(MCDCDecision { end_bcbs, bitmap_idx, decision_depth, conditions });
+    }
+}
+
+/// Adds ordinary branch mappings for the conditions of a decision that can't
+/// be instrumented for MC/DC.
+fn degrade_to_branches(
+    branch_spans: &[MCDCBranchSpan],
+    bcb_from_marker: &impl Fn(BlockMarkerId) -> Option<BasicCoverageBlock>,
+    mappings: &mut Vec<Mapping>,
+) {
+    mappings.extend(branch_spans.iter().filter_map(
+        |&MCDCBranchSpan { span, true_marker, false_marker, .. }| try {
+            let true_bcb = bcb_from_marker(true_marker)?;
+            let false_bcb = bcb_from_marker(false_marker)?;
+            Mapping { span, kind: MappingKind::Branch { true_bcb, false_bcb } }
+        },
+    ));
+}
+
+/// Assigns to each condition the amounts that are added to the condition
+/// bitmap when it is evaluated, and returns the number of test vectors of the
+/// decision, which is the number of bits it needs in the test vector bitmap.
+///
+/// This mirrors `TVIdxBuilder` in LLVM's `CoverageMapping.cpp`, which
+/// `llvm-cov` uses to decode the bitmap, so the two must agree exactly:
+/// - The conditions are visited in topological order of the condition tree,
+///   counting the number of paths that reach each condition. A condition that
+///   is reached by `n` paths before a condition that is reached by `m` paths
+///   has `m` added to the bitmap on that edge, so the paths into each
+///   condition have distinct sums.
+/// - Edges that end the decision are sorted by descending number of paths,
+///   and each is assigned the running total of the preceding ones, so that
+///   every complete path has a distinct index.
+fn calc_test_vectors_index(conditions: &mut [MCDCCondition]) -> usize {
+    let mut indegree_stats = IndexVec::<ConditionId, usize>::from_elem_n(0, conditions.len());
+    let mut num_paths_stats = IndexVec::<ConditionId, usize>::from_elem_n(0, conditions.len());
+    let mut next_conditions = conditions
+        .iter_mut()
+        .map(|condition| {
+            let ConditionInfo { condition_id, true_next_id, false_next_id } =
+                condition.condition_info;
+            for next_id in [true_next_id, false_next_id].into_iter().flatten() {
+                indegree_stats[next_id] += 1;
+            }
+            (condition_id, condition)
+        })
+        .collect::<FxIndexMap<_, _>>();
+
+    let mut queue = VecDeque::from_iter(next_conditions.swap_remove(&ConditionId::START));
+    num_paths_stats[ConditionId::START] = 1;
+    let mut decision_end_nodes = vec![];
+    while let Some(condition) = queue.pop_front() {
+        let ConditionInfo { condition_id, true_next_id, false_next_id } = condition.condition_info;
+        let this_paths_count = num_paths_stats[condition_id];
+        // Visit the false edge first, like LLVM does.
+        for (next, index) in
+            [(false_next_id, &mut condition.false_index), (true_next_id, &mut condition.true_index)]
+        {
+            let Some(next_id) = next else {
+                decision_end_nodes.push((this_paths_count, index));
+                continue;
+            };
+            let next_paths_count = &mut num_paths_stats[next_id];
+            *index = *next_paths_count;
+            *next_paths_count = next_paths_count.saturating_add(this_paths_count);
+            let next_indegree = &mut indegree_stats[next_id];
+            *next_indegree -= 1;
+            if *next_indegree == 0 {
+                queue.push_back(
+                    next_conditions
+                        .swap_remove(&next_id)
+                        .expect("condition with no remaining predecessors should be unvisited"),
+                );
+            }
+        }
+    }
+    assert!(next_conditions.is_empty(), "the condition tree has unreachable conditions");
+
+    // LLVM expects the end edges in descending order of their number of paths,
+    // which keeps the bitmap small for chains like `a && b && c && ...`.
+    decision_end_nodes.sort_by_key(|&(num_paths, _)| std::cmp::Reverse(num_paths));
+    let mut num_test_vectors = 0usize;
+    for (num_paths, index) in decision_end_nodes {
+        *index = num_test_vectors;
+        num_test_vectors = num_test_vectors.saturating_add(num_paths);
+    }
+    num_test_vectors
+}
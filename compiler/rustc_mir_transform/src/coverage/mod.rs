@@ -5,7 +5,7 @@
 
 use crate::coverage::counters::BcbCountersData;
 use crate::coverage::graph::CoverageGraph;
-use crate::coverage::mappings::ExtractedMappings;
+use crate::coverage::mappings::{ExtractedMappings, MCDCCondition, MCDCDecision};
 
 mod counters;
 mod expansion;
@@ -72,14 +72,18 @@ fn instrument_function_for_coverage<'tcx>(tcx: TyCtxt<'tcx>, mir_body: &mut mir:
 
     ////////////////////////////////////////////////////
     // Extract coverage spans and other mapping info from MIR.
-    let ExtractedMappings { mappings } =
-        match mappings::extract_mappings_from_mir(tcx, mir_body, &hir_info, &graph) {
-            Ok(m) => m,
-            Err(error) => {
-                tracing::debug!(?error, "mapping extraction failed; skipping this function");
-                return;
-            }
-        };
+    let ExtractedMappings {
+        mappings,
+        mcdc_decisions,
+        mcdc_bitmap_bits,
+        mcdc_num_condition_bitmaps,
+    } = match mappings::extract_mappings_from_mir(tcx, mir_body, &hir_info, &graph) {
+        Ok(m) => m,
+        Err(error) => {
+            tracing::debug!(?error, "mapping extraction failed; skipping this function");
+            return;
+        }
+    };
 
     // Use the coverage graph to prepare intermediate data that will eventually
     // be used to assign physical counters and counter expressions to points in
@@ -89,6 +93,7 @@ fn instrument_function_for_coverage<'tcx>(tcx: TyCtxt<'tcx>, mir_body: &mut mir:
 
     // Inject coverage statements into MIR.
     inject_coverage_statements(mir_body, &graph);
+    inject_mcdc_statements(mir_body, &graph, &mcdc_decisions);
 
     mir_body.function_coverage_info = Some(Box::new(FunctionCoverageInfo {
         function_source_hash: hir_info.function_source_hash,
@@ -97,6 +102,9 @@ fn instrument_function_for_coverage<'tcx>(tcx: TyCtxt<'tcx>, mir_body: &mut mir:
         priority_list,
 
         mappings,
+
+        mcdc_bitmap_bits,
+        mcdc_num_condition_bitmaps,
     }));
 }
 
@@ -108,6 +116,40 @@ fn inject_coverage_statements<'tcx>(mir_body: &mut mir::Body<'tcx>, graph: &Cove
     }
 }
 
+/// Inject the statements that record the test vectors of MC/DC decisions.
+fn inject_mcdc_statements<'tcx>(
+    mir_body: &mut mir::Body<'tcx>,
+    graph: &CoverageGraph,
+    mcdc_decisions: &[MCDCDecision],
+) {
+    for &MCDCDecision { ref end_bcbs, bitmap_idx, decision_depth, ref conditions } in mcdc_decisions
+    {
+        // `inject_statement` inserts at the start of the block, so inject the
+        // test vector updates first, so that they end up after any condition
+        // bitmap update in the same block.
+        for &end_bcb in end_bcbs {
+            inject_statement(
+                mir_body,
+                CoverageKind::TestVectorBitmapUpdate {
+                    bitmap_idx: bitmap_idx as u32,
+                    decision_depth,
+                },
+                graph[end_bcb].leader_bb(),
+            );
+        }
+
+        for &MCDCCondition { true_bcb, false_bcb, true_index, false_index, .. } in conditions {
+            for (index, bcb) in [(false_index, false_bcb), (true_index, true_bcb)] {
+                inject_statement(
+                    mir_body,
+                    CoverageKind::CondBitmapUpdate { index: index as u32, decision_depth },
+                    graph[bcb].leader_bb(),
+                );
+            }
+        }
+    }
+}
+
 fn inject_statement(mir_body: &mut mir::Body<'_>, counter_kind: CoverageKind, bb: BasicBlock) {
     debug!("  injecting statement {counter_kind:?} for {bb:?}");
     let data = &mut mir_body[bb];
@@ -387,3 +387,13 @@ pub(crate) struct ForceInlineJustification {
     pub sym: Symbol,
     pub callee: String,
 }
+
+#[derive(Diagnostic)]
+#[diag(
+    "number of total test vectors in one function will exceed limit ({$max_num_test_vectors}) if this decision is instrumented, so MC/DC analysis ignores it"
+)]
+pub(crate) struct MCDCExceedsTestVectorLimit {
+    #[primary_span]
+    pub(crate) span: Span,
+    pub(crate) max_num_test_vectors: usize,
+}
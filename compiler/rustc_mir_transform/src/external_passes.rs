@@ -0,0 +1,40 @@
+//! Runs the MIR passes that drivers registered through the `run_external_mir_passes` hook, e.g.
+//! the transformation passes of `rustc_public`.
+
+use rustc_middle::mir::Body;
+use rustc_middle::ty::TyCtxt;
+
+use crate::pass_manager as pm;
+use crate::required_consts::RequiredConstsVisitor;
+
+/// Hands the optimized body to the passes registered by the driver.
+///
+/// The passes work on a representation that knows nothing about the invariants of runtime MIR,
+/// so the body is validated right away if they changed it, instead of failing later in codegen.
+pub(super) struct ExternalPasses;
+
+impl<'tcx> crate::MirPass<'tcx> for ExternalPasses {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if !tcx.run_external_mir_passes(body) {
+            return;
+        }
+
+        // The passes may have introduced constants that still need to be evaluated before
+        // codegen. Keep the ones that were already required, even if they are gone from the
+        // body now, so that their errors are still reported.
+        let previous = body.required_consts.take().unwrap_or_default();
+        RequiredConstsVisitor::compute_required_consts(body);
+        let required_consts = body.required_consts.as_mut().unwrap();
+        for constant in previous {
+            if !required_consts.contains(&constant) {
+                required_consts.push(constant);
+            }
+        }
+
+        pm::validate_body(tcx, body, format!("after {}", self.name()));
+    }
+
+    fn is_required(&self) -> bool {
+        true
+    }
+}
@@ -375,6 +375,10 @@ fn check_callee_mir_body(
             return Err("Not inlining multi-block body as we're past a depth limit");
         }
 
+        if has_mcdc_instrumentation(callee_body) {
+            return Err("incompatible with MC/DC coverage");
+        }
+
         let mut threshold = if self.caller_is_inline_forwarder || self.past_depth_limit() {
             tcx.sess.opts.unstable_opts.inline_mir_forwarder_threshold.unwrap_or(30)
         } else if tcx.cross_crate_inlinable(callsite.callee.def_id()) {
@@ -848,6 +852,33 @@ fn check_codegen_attributes<'tcx, I: Inliner<'tcx>>(
     Ok(())
 }
 
+/// Returns true if `body` records MC/DC test vectors. Codegen keeps the condition
+/// bitmaps of a function in its own stack frame, so such bodies are not inlined unless
+/// they have to be, see `remove_mcdc_instrumentation`.
+fn has_mcdc_instrumentation(body: &Body<'_>) -> bool {
+    body.function_coverage_info.as_ref().is_some_and(|info| info.mcdc_bitmap_bits > 0)
+}
+
+/// Removes the statements that update the MC/DC condition bitmaps of `body`, which can't be
+/// reached from the frame of the caller it is inlined into. The inlined copy of a body that is
+/// forced to be inlined still counts blocks and branches, but records no test vectors.
+fn remove_mcdc_instrumentation(body: &mut Body<'_>) {
+    if !has_mcdc_instrumentation(body) {
+        return;
+    }
+    for block in body.basic_blocks_mut() {
+        for statement in &mut block.statements {
+            if let StatementKind::Coverage(
+                coverage::CoverageKind::CondBitmapUpdate { .. }
+                | coverage::CoverageKind::TestVectorBitmapUpdate { .. },
+            ) = statement.kind
+            {
+                statement.make_nop(true);
+            }
+        }
+    }
+}
+
 fn inline_call<'tcx, I: Inliner<'tcx>>(
     inliner: &I,
     caller_body: &mut Body<'tcx>,
@@ -855,6 +886,7 @@ fn inline_call<'tcx, I: Inliner<'tcx>>(
     mut callee_body: Body<'tcx>,
 ) {
     let tcx = inliner.tcx();
+    remove_mcdc_instrumentation(&mut callee_body);
     let terminator = caller_body[callsite.block].terminator.take().unwrap();
     let TerminatorKind::Call { func, args, destination, unwind, target, .. } = terminator.kind
     else {
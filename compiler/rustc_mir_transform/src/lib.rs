@@ -151,6 +151,7 @@ mod dead_store_elimination : DeadStoreElimination {
     mod erase_deref_temps : EraseDerefTemps;
     mod elaborate_box_derefs : ElaborateBoxDerefs;
     mod elaborate_drops : ElaborateDrops;
+    mod external_passes : ExternalPasses;
     mod function_item_references : FunctionItemReferences;
     mod gvn : GVN;
     // Made public so that `mir_drops_elaborated_and_const_checked` can be overridden
@@ -235,6 +236,7 @@ pub fn provide(providers: &mut Providers) {
         trivial_const: trivial_const::trivial_const_provider,
         ..providers.queries
     };
+    providers.hooks.run_external_mir_passes = |_, _| false;
 }
 
 fn remap_mir_for_const_eval_select<'tcx>(
@@ -770,6 +772,9 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &simplify::SimplifyLocals::Final,
             &multiple_return_terminators::MultipleReturnTerminators,
             &large_enums::EnumSizeOpt { discrepancy: 128 },
+            // Passes registered by drivers see the fully optimized body. The critical edge
+            // splitting below is needed by codegen, so it must come after them.
+            &external_passes::ExternalPasses,
             // Some cleanup necessary at least for LLVM and potentially other codegen backends.
             &add_call_guards::CriticalCallEdges,
             // Cleanup for human readability, off by default.
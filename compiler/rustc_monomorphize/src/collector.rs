@@ -206,6 +206,8 @@
 //! regardless of whether it is actually needed or not.
 
 use std::cell::OnceCell;
+use std::collections::VecDeque;
+use std::iter;
 use std::ops::ControlFlow;
 
 use rustc_data_structures::fx::FxIndexMap;
@@ -266,11 +268,23 @@ pub(crate) struct UsageMap<'tcx> {
     // Maps each mono item with users to the mono items that use it.
     // Be careful: subsets `used_map`, so unused items are vacant.
     user_map: UnordMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+
+    // The roots the collection started from, in collection order.
+    roots: Vec<MonoItem<'tcx>>,
+
+    // Maps every mono item to the spans at which it uses the items in `used_map`, in the same
+    // order. Only recorded for `-Zdump-mono-usage-graph` and `-Zexplain-mono-item`.
+    use_spans: Option<UnordMap<MonoItem<'tcx>, Vec<Span>>>,
 }
 
 impl<'tcx> UsageMap<'tcx> {
-    fn new() -> UsageMap<'tcx> {
-        UsageMap { used_map: Default::default(), user_map: Default::default() }
+    fn new(roots: Vec<MonoItem<'tcx>>, record_use_spans: bool) -> UsageMap<'tcx> {
+        UsageMap {
+            used_map: Default::default(),
+            user_map: Default::default(),
+            roots,
+            use_spans: record_use_spans.then(Default::default),
+        }
     }
 
     fn record_used<'a>(&mut self, user_item: MonoItem<'tcx>, used_items: &'a MonoItems<'tcx>)
@@ -282,6 +296,47 @@ fn record_used<'a>(&mut self, user_item: MonoItem<'tcx>, used_items: &'a MonoIte
         }
 
         assert!(self.used_map.insert(user_item, used_items.items().collect()).is_none());
+
+        if let Some(use_spans) = &mut self.use_spans {
+            use_spans.insert(user_item, used_items.items.values().copied().collect());
+        }
+    }
+
+    pub(crate) fn roots(&self) -> &[MonoItem<'tcx>] {
+        &self.roots
+    }
+
+    /// Returns the items used by `item`, together with the span of the use, if use spans were
+    /// recorded during collection.
+    pub(crate) fn get_used_items_with_spans(
+        &self,
+        item: MonoItem<'tcx>,
+    ) -> Option<impl Iterator<Item = (MonoItem<'tcx>, Span)>> {
+        let used_items = self.used_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[]);
+        let spans = self.use_spans.as_ref()?.get(&item).map(|spans| spans.as_slice());
+        Some(iter::zip(used_items.iter().copied(), spans.unwrap_or(&[]).iter().copied()))
+    }
+
+    /// Returns, for every mono item reachable from the roots, the item that uses it on a shortest
+    /// chain of uses from a root, and the span of that use. Roots are not part of the map.
+    ///
+    /// Returns `None` if use spans were not recorded during collection.
+    pub(crate) fn shortest_use_chains(
+        &self,
+    ) -> Option<UnordMap<MonoItem<'tcx>, (MonoItem<'tcx>, Span)>> {
+        let mut visited: UnordSet<MonoItem<'tcx>> = self.roots.iter().copied().collect();
+        let mut parents = UnordMap::default();
+        // A breadth-first walk in root order, so that the chains are deterministic.
+        let mut queue: VecDeque<_> = self.roots.iter().copied().collect();
+        while let Some(user_item) = queue.pop_front() {
+            for (used_item, span) in self.get_used_items_with_spans(user_item)? {
+                if visited.insert(used_item) {
+                    parents.insert(used_item, (user_item, span));
+                    queue.push_back(used_item);
+                }
+            }
+        }
+        Some(parents)
     }
 
     pub(crate) fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
@@ -1047,6 +1102,8 @@ fn visit_instance_use<'tcx>(
 /// Returns `true` if we should codegen an instance in the local crate, or returns `false` if we
 /// can just link to the upstream crate and therefore don't need a mono item.
 fn should_codegen_locally<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> bool {
+    // Upstream crates export the polymorphized instance, if any.
+    let instance = instance.polymorphize(tcx);
     let Some(def_id) = instance.def.def_id_if_not_guaranteed_local_codegen() else {
         return true;
     };
@@ -1194,6 +1251,9 @@ fn create_fn_mono_item<'tcx>(
     instance: Instance<'tcx>,
     source: Span,
 ) -> Spanned<MonoItem<'tcx>> {
+    // Instantiations that only differ in arguments their body does not depend on share one mono
+    // item, see `polymorphize`.
+    let instance = instance.polymorphize(tcx);
     let def_id = instance.def_id();
     if tcx.sess.opts.unstable_opts.profile_closures
         && def_id.is_local()
@@ -1809,10 +1869,12 @@ pub(crate) fn collect_crate_mono_items<'tcx>(
 
     debug!("building mono item graph, beginning at roots");
 
+    let opts = &tcx.sess.opts.unstable_opts;
+    let record_use_spans = opts.dump_mono_usage_graph.enabled() || opts.explain_mono_item.is_some();
     let state = SharedState {
         visited: Lock::new(UnordSet::default()),
         mentioned: Lock::new(UnordSet::default()),
-        usage_map: Lock::new(UsageMap::new()),
+        usage_map: Lock::new(UsageMap::new(roots.clone(), record_use_spans)),
     };
     let recursion_limit = tcx.recursion_limit();
 
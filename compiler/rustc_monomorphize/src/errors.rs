@@ -1,4 +1,4 @@
-use rustc_macros::Diagnostic;
+use rustc_macros::{Diagnostic, Subdiagnostic};
 use rustc_middle::ty::{Instance, Ty};
 use rustc_span::{Span, Symbol};
 
@@ -36,6 +36,45 @@ pub(crate) struct LargeAssignmentsLint {
     pub limit: u64,
 }
 
+#[derive(Diagnostic)]
+#[diag("this future is {$size} bytes, which is larger than the limit of {$limit} bytes")]
+#[note(
+    "the current maximum size is {$limit}, but it can be customized with the future_size_limit attribute: `#![future_size_limit = \"...\"]`"
+)]
+pub(crate) struct LargeFuturesLint<'tcx> {
+    pub size: u64,
+    pub limit: u64,
+    #[subdiagnostic]
+    pub upvars: Option<LargeFutureUpvars>,
+    #[subdiagnostic]
+    pub await_points: Vec<LargeFutureAwaitPoint>,
+    #[subdiagnostic]
+    pub held_locals: Vec<LargeFutureHeldLocal<'tcx>>,
+}
+
+#[derive(Subdiagnostic)]
+#[note("{$size} bytes of the future are taken by captured variables and arguments")]
+pub(crate) struct LargeFutureUpvars {
+    pub size: u64,
+}
+
+#[derive(Subdiagnostic)]
+#[label("this await point holds {$size} bytes")]
+pub(crate) struct LargeFutureAwaitPoint {
+    #[primary_span]
+    pub span: Span,
+    pub size: u64,
+}
+
+#[derive(Subdiagnostic)]
+#[label("`{$ty}` ({$size} bytes) is held across an await point")]
+pub(crate) struct LargeFutureHeldLocal<'tcx> {
+    #[primary_span]
+    pub span: Span,
+    pub ty: Ty<'tcx>,
+    pub size: u64,
+}
+
 #[derive(Diagnostic)]
 #[diag("symbol `{$symbol}` is already defined")]
 pub(crate) struct SymbolAlreadyDefined {
@@ -50,6 +89,19 @@ pub(crate) struct CouldntDumpMonoStats {
     pub error: String,
 }
 
+#[derive(Diagnostic)]
+#[diag("unexpected error occurred while dumping the monomorphization usage graph: {$error}")]
+pub(crate) struct CouldntDumpMonoUsageGraph {
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag("no monomorphized item named `{$name}` was collected")]
+#[note("`-Z explain-mono-item` expects the full path of an item, e.g. `alloc::vec::Vec::push`")]
+pub(crate) struct NoMonoItemToExplain {
+    pub name: String,
+}
+
 #[derive(Diagnostic)]
 #[diag("the above error was encountered while instantiating `{$kind} {$instance}`")]
 pub(crate) struct EncounteredErrorWhileInstantiating<'tcx> {
@@ -17,6 +17,7 @@
 mod graph_checks;
 mod mono_checks;
 mod partitioning;
+mod polymorphize;
 mod util;
 
 fn custom_coerce_unsize_info<'tcx>(
@@ -48,5 +49,6 @@ fn custom_coerce_unsize_info<'tcx>(
 
 pub fn provide(providers: &mut Providers) {
     partitioning::provide(providers);
+    polymorphize::provide(providers);
     mono_checks::provide(&mut providers.queries);
 }
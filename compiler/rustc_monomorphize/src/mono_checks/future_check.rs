@@ -0,0 +1,123 @@
+use rustc_abi::Size;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::{CoroutineDesugaring, CoroutineKind, CoroutineSource};
+use rustc_middle::ty::layout::{LayoutCx, TyAndLayout};
+use rustc_middle::ty::{self, CoroutineArgs, Instance, InstanceKind, TyCtxt};
+use rustc_session::lint::builtin::LARGE_FUTURES;
+use tracing::debug;
+
+use crate::errors::{
+    LargeFutureAwaitPoint, LargeFutureHeldLocal, LargeFutureUpvars, LargeFuturesLint,
+};
+
+/// The number of await points, and of locals held across each of them, that are pointed at
+/// when reporting a large future. The largest ones are reported first.
+const MAX_REPORTED: usize = 3;
+
+/// Lints if the future of the `async` function or block whose body is `instance` is larger
+/// than the `future_size_limit`.
+pub(crate) fn check_future_size<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
+    let limit = tcx.future_size_limit();
+    if limit.0 == 0 {
+        return;
+    }
+
+    let InstanceKind::Item(def_id) = instance.def else {
+        return;
+    };
+    if !tcx.coroutine_is_async(def_id) {
+        return;
+    }
+    let Some(local_def_id) = def_id.as_local() else {
+        // We can't get a `HirId` for coroutines from other crates that get monomorphized in
+        // the current crate, see `MoveCheckVisitor::lint_large_assignment`.
+        return;
+    };
+
+    let typing_env = ty::TypingEnv::fully_monomorphized();
+    let cx = LayoutCx::new(tcx, typing_env);
+    let coroutine_ty = instance.ty(tcx, typing_env);
+    let Ok(layout) = tcx.layout_of(typing_env.as_query_input(coroutine_ty)) else {
+        return;
+    };
+    if layout.size.bytes_usize() <= limit.0 {
+        return;
+    }
+    debug!(?instance, size = ?layout.size, "future is too large");
+
+    let Ok(coroutine) = tcx.coroutine_layout(def_id, instance.args) else {
+        return;
+    };
+
+    // Gather the locals stored in each suspension state, i.e. the locals held across each
+    // await point, along with how much of the future each state needs.
+    let mut await_points: Vec<_> = coroutine
+        .variant_fields
+        .iter_enumerated()
+        .skip(CoroutineArgs::RESERVED_VARIANTS)
+        .map(|(variant_idx, variant_fields)| {
+            let variant_layout = layout.for_variant(&cx, variant_idx);
+            let mut held_size = Size::ZERO;
+            let mut held_locals: Vec<(_, TyAndLayout<'tcx>)> = variant_fields
+                .iter()
+                .enumerate()
+                .map(|(field_idx, &local)| {
+                    let field_layout = variant_layout.field(&cx, field_idx);
+                    let offset = variant_layout.fields.offset(field_idx);
+                    held_size = held_size.max(offset + field_layout.size);
+                    (local, field_layout)
+                })
+                .collect();
+            held_locals.sort_by_key(|(_, field_layout)| std::cmp::Reverse(field_layout.size));
+            (coroutine.variant_source_info[variant_idx].span, held_size, held_locals)
+        })
+        .collect();
+    await_points.sort_by_key(|&(_, held_size, _)| std::cmp::Reverse(held_size));
+
+    let mut reported_await_points = vec![];
+    let mut reported_locals = vec![];
+    let mut seen_locals = FxHashSet::default();
+    for (span, held_size, held_locals) in await_points.into_iter().take(MAX_REPORTED) {
+        reported_await_points.push(LargeFutureAwaitPoint { span, size: held_size.bytes() });
+        for (local, field_layout) in held_locals.into_iter().take(MAX_REPORTED) {
+            // A local held across several await points is only pointed at once.
+            if field_layout.size == Size::ZERO || !seen_locals.insert(local) {
+                continue;
+            }
+            reported_locals.push(LargeFutureHeldLocal {
+                span: coroutine.field_tys[local].source_info.span,
+                ty: field_layout.ty,
+                size: field_layout.size.bytes(),
+            });
+        }
+    }
+
+    // If the future is mostly made of its captures, e.g. the arguments of an `async fn`, there
+    // are no locals to point at, so mention them separately.
+    let upvars_size = (0..instance.args.as_coroutine().upvar_tys().len())
+        .map(|field_idx| layout.field(&cx, field_idx).size)
+        .fold(Size::ZERO, |total, size| total + size);
+
+    // The span of the coroutine of an `async fn` is its whole body, point at the signature
+    // instead.
+    let span = match tcx.coroutine_kind(def_id) {
+        Some(CoroutineKind::Desugared(CoroutineDesugaring::Async, CoroutineSource::Fn)) => {
+            tcx.def_span(tcx.parent(def_id))
+        }
+        _ => tcx.def_span(def_id),
+    };
+
+    tcx.emit_node_span_lint(
+        LARGE_FUTURES,
+        tcx.local_def_id_to_hir_id(local_def_id),
+        span,
+        LargeFuturesLint {
+            size: layout.size.bytes(),
+            limit: limit.0 as u64,
+            upvars: (upvars_size > Size::ZERO)
+                .then(|| LargeFutureUpvars { size: upvars_size.bytes() }),
+            await_points: reported_await_points,
+            held_locals: reported_locals,
+        },
+    );
+}
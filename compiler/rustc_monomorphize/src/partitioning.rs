@@ -123,7 +123,9 @@
 use tracing::debug;
 
 use crate::collector::{self, MonoItemCollectionStrategy, UsageMap};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined};
+use crate::errors::{
+    CouldntDumpMonoStats, CouldntDumpMonoUsageGraph, NoMonoItemToExplain, SymbolAlreadyDefined,
+};
 use crate::graph_checks::target_specific_checks;
 
 struct PartitioningCx<'a, 'tcx> {
@@ -1183,6 +1185,18 @@ fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> MonoItemPartitio
         tcx.dcx().emit_fatal(CouldntDumpMonoStats { error: err.to_string() });
     }
 
+    // Output the mono item usage graph
+    if let SwitchWithOptPath::Enabled(ref path) = tcx.sess.opts.unstable_opts.dump_mono_usage_graph
+        && let Err(err) =
+            dump_mono_usage_graph(tcx, &items, &usage_map, path, tcx.crate_name(LOCAL_CRATE))
+    {
+        tcx.dcx().emit_fatal(CouldntDumpMonoUsageGraph { error: err.to_string() });
+    }
+
+    if let Some(ref name) = tcx.sess.opts.unstable_opts.explain_mono_item {
+        explain_mono_item(tcx, &items, &usage_map, name);
+    }
+
     if tcx.sess.opts.unstable_opts.print_mono_items {
         let mut item_to_cgus: UnordMap<_, Vec<_>> = Default::default();
 
@@ -1312,6 +1326,126 @@ struct MonoItem {
     Ok(())
 }
 
+/// Outputs the mono item usage graph, i.e. which mono items each mono item uses and where, to a
+/// file in the given output directory.
+fn dump_mono_usage_graph<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &[MonoItem<'tcx>],
+    usage_map: &UsageMap<'tcx>,
+    output_directory: &Option<PathBuf>,
+    crate_name: Symbol,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output_directory = if let Some(directory) = output_directory {
+        fs::create_dir_all(directory)?;
+        directory
+    } else {
+        Path::new(".")
+    };
+
+    let format = tcx.sess.opts.unstable_opts.dump_mono_stats_format;
+    let ext = format.extension();
+    let filename = format!("{crate_name}.mono_usage.{ext}");
+    let output_path = output_directory.join(&filename);
+    let mut file = File::create_buffered(&output_path)?;
+
+    #[derive(serde::Serialize)]
+    struct UsageEdge {
+        user: String,
+        used: String,
+        span: String,
+    }
+
+    #[derive(serde::Serialize)]
+    struct UsageGraph {
+        roots: Vec<String>,
+        edges: Vec<UsageEdge>,
+    }
+
+    let source_map = tcx.sess.source_map();
+    let item_name = |item: &MonoItem<'tcx>| with_no_trimmed_paths!(item.to_string());
+    let roots = usage_map.roots().iter().map(item_name).collect();
+    // `items` is sorted, so this outputs the edges in a deterministic order.
+    let edges = items
+        .iter()
+        .flat_map(|&user_item| {
+            let used_items = usage_map
+                .get_used_items_with_spans(user_item)
+                .expect("use spans are recorded for -Z dump-mono-usage-graph");
+            used_items.map(move |(used_item, span)| UsageEdge {
+                user: item_name(&user_item),
+                used: item_name(&used_item),
+                span: source_map.span_to_diagnostic_string(span),
+            })
+        })
+        .collect();
+    let graph = UsageGraph { roots, edges };
+
+    match format {
+        DumpMonoStatsFormat::Json => serde_json::to_writer(file, &graph)?,
+        DumpMonoStatsFormat::Markdown => {
+            writeln!(file, "## Roots")?;
+            writeln!(file)?;
+            for root in graph.roots {
+                writeln!(file, "- `{root}`")?;
+            }
+            writeln!(file)?;
+            writeln!(file, "## Uses")?;
+            writeln!(file)?;
+            writeln!(file, "| User | Used | Use site |")?;
+            writeln!(file, "| --- | --- | --- |")?;
+            for UsageEdge { user, used, span } in graph.edges {
+                writeln!(file, "| `{user}` | `{used}` | {span} |")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the shortest chain of uses from a root to each instantiation of the item with the
+/// given path, to find out why a generic item got instantiated with some arguments.
+fn explain_mono_item<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &[MonoItem<'tcx>],
+    usage_map: &UsageMap<'tcx>,
+    name: &str,
+) {
+    let parents =
+        usage_map.shortest_use_chains().expect("use spans are recorded for -Z explain-mono-item");
+    let source_map = tcx.sess.source_map();
+
+    let mut found = false;
+    for &item in items {
+        if with_no_trimmed_paths!(tcx.def_path_str(item.def_id())) != name {
+            continue;
+        }
+        found = true;
+
+        let mut chain = vec![];
+        let mut current = item;
+        while let Some(&(user_item, span)) = parents.get(&current) {
+            chain.push((current, Some(span)));
+            current = user_item;
+        }
+        chain.push((current, None));
+
+        println!("instantiation chain of `{}`:", with_no_trimmed_paths!(item.to_string()));
+        for (item, span) in chain.into_iter().rev() {
+            let item = with_no_trimmed_paths!(item.to_string());
+            match span {
+                Some(span) => {
+                    println!("    `{item}` used at {}", source_map.span_to_diagnostic_string(span))
+                }
+                None => println!("    `{item}` (root)"),
+            }
+        }
+    }
+
+    if !found {
+        tcx.dcx().emit_warn(NoMonoItemToExplain { name: name.to_string() });
+    }
+}
+
 pub(crate) fn provide(providers: &mut Providers) {
     providers.queries.collect_and_partition_mono_items = collect_and_partition_mono_items;
 
@@ -0,0 +1,328 @@
+//! Polymorphization Analysis
+//! =========================
+//!
+//! This module implements an analysis of functions, methods and closures to determine which
+//! generic parameters their bodies do not depend on. Instantiations that only differ in those
+//! parameters generate the same code, so [`Instance::polymorphize`] replaces the arguments for
+//! them with the parameters themselves, and the collector and codegen then only emit one shared
+//! mono item for all of them.
+//!
+//! A parameter is unused if it does not appear in any type or constant of the body, with one
+//! exception: a parameter that only appears as the argument of a `PhantomData` is also unused,
+//! as `PhantomData<T>` is a ZST whatever `T` is and has no code of its own.
+//!
+//! FIXME: A parameter that the body only depends on through its layout, e.g. because it moves
+//! values of that type around or calls `size_of` on it, is considered used. Instantiations whose
+//! arguments have the same layout could share code too, but that needs the shared mono item to
+//! be generated from a layout rather than from a type, which codegen does not support yet.
+//!
+//! [`Instance::polymorphize`]: rustc_middle::ty::Instance::polymorphize
+
+use std::ops::ControlFlow;
+
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::visit::{TyContext, Visitor as MirVisitor};
+use rustc_middle::mir::{self, ConstOperand, Local, LocalDecl, Location};
+use rustc_middle::ty::{
+    self, GenericArgsRef, GenericParamDefKind, Ty, TyCtxt, TypeSuperVisitable, TypeVisitable,
+    TypeVisitableExt, TypeVisitor, UnusedGenericParams,
+};
+use rustc_middle::util::Providers;
+use tracing::{debug, instrument};
+
+pub(crate) fn provide(providers: &mut Providers) {
+    providers.queries.unused_generic_params = unused_generic_params;
+}
+
+/// Determine which generic parameters are used by the instance.
+///
+/// Returns a bitset where bits representing unused parameters are set (`all_used` indicates all
+/// parameters are used).
+#[instrument(level = "debug", skip(tcx))]
+fn unused_generic_params<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: ty::InstanceKind<'tcx>,
+) -> UnusedGenericParams {
+    if !tcx.sess.opts.unstable_opts.polymorphize {
+        // If polymorphization disabled, then all parameters are used.
+        return UnusedGenericParams::new_all_used();
+    }
+
+    // Only plain items have a body of their own to analyze; shims are generated from their
+    // arguments, so they always depend on all of them.
+    let ty::InstanceKind::Item(def_id) = instance else {
+        return UnusedGenericParams::new_all_used();
+    };
+    if !tcx.is_mir_available(def_id) {
+        return UnusedGenericParams::new_all_used();
+    }
+
+    let generics = tcx.generics_of(def_id);
+    debug!(?generics);
+
+    // Exit early when there are no parameters to be unused.
+    if generics.is_empty() {
+        return UnusedGenericParams::new_all_used();
+    }
+
+    // Create a bitset with N rightmost ones for each parameter.
+    let generics_count: u32 =
+        generics.count().try_into().expect("more generic parameters than can fit into a `u32`");
+    let mut unused_parameters = UnusedGenericParams::new_all_unused(generics_count);
+    debug!(?unused_parameters, "(start)");
+
+    mark_used_by_default_parameters(tcx, def_id, generics, &mut unused_parameters);
+    debug!(?unused_parameters, "(after default)");
+
+    // Visit MIR and accumulate used generic parameters.
+    let body = tcx.instance_mir(instance);
+    let mut vis = MarkUsedGenericParams { tcx, def_id, unused_parameters: &mut unused_parameters };
+    vis.visit_body(body);
+    debug!(?unused_parameters, "(after visitor)");
+
+    mark_used_by_predicates(tcx, def_id, &mut unused_parameters);
+    debug!(?unused_parameters, "(end)");
+
+    unused_parameters
+}
+
+/// Some parameters are considered used-by-default, such as non-type parameters and the
+/// synthetic parameters of closures and coroutines, which are always used by their bodies.
+#[instrument(level = "debug", skip(tcx, def_id, generics, unused_parameters))]
+fn mark_used_by_default_parameters<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    generics: &'tcx ty::Generics,
+    unused_parameters: &mut UnusedGenericParams,
+) {
+    match tcx.def_kind(def_id) {
+        DefKind::Closure | DefKind::SyntheticCoroutineBody => {
+            for param in &generics.own_params {
+                debug!(?param, "(closure/coroutine)");
+                unused_parameters.mark_used(param.index);
+            }
+        }
+        _ => {
+            for param in &generics.own_params {
+                if !matches!(param.kind, GenericParamDefKind::Type { .. }) {
+                    debug!(?param, "(other)");
+                    unused_parameters.mark_used(param.index);
+                }
+            }
+        }
+    }
+
+    if let Some(parent) = generics.parent {
+        mark_used_by_default_parameters(tcx, parent, tcx.generics_of(parent), unused_parameters);
+    }
+}
+
+/// Search the predicates on used generic parameters for any unused generic parameters, and mark
+/// those as used.
+#[instrument(level = "debug", skip(tcx, def_id))]
+fn mark_used_by_predicates<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    unused_parameters: &mut UnusedGenericParams,
+) {
+    let def_id = tcx.typeck_root_def_id(def_id);
+    let predicates = tcx.explicit_predicates_of(def_id);
+
+    let mut current_unused_parameters = UnusedGenericParams::new_all_unused(0);
+    // Run to a fixed point to support `where T: Trait<U>, U: Trait<V>`.
+    while current_unused_parameters != *unused_parameters {
+        debug!(?current_unused_parameters, ?unused_parameters);
+        current_unused_parameters = *unused_parameters;
+
+        for (predicate, _) in predicates.predicates {
+            // Consider all generic params in a predicate as used if any other parameter in the
+            // predicate is used.
+            let any_param_used = {
+                let mut vis = HasUsedGenericParams { unused_parameters };
+                predicate.visit_with(&mut vis).is_break()
+            };
+
+            if any_param_used {
+                let mut vis = MarkUsedGenericParams { tcx, def_id, unused_parameters };
+                predicate.visit_with(&mut vis);
+            }
+        }
+    }
+
+    if let Some(parent) = predicates.parent {
+        mark_used_by_predicates(tcx, parent, unused_parameters);
+    }
+}
+
+/// Visitor used to aggregate generic parameter uses.
+struct MarkUsedGenericParams<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    unused_parameters: &'a mut UnusedGenericParams,
+}
+
+impl<'a, 'tcx> MarkUsedGenericParams<'a, 'tcx> {
+    /// Invoke `unused_generic_params` on a body contained within the current item (e.g.
+    /// a closure, coroutine or constant).
+    #[instrument(level = "debug", skip(self, def_id, args))]
+    fn visit_child_body(&mut self, def_id: DefId, args: GenericArgsRef<'tcx>) {
+        let instance = ty::InstanceKind::Item(def_id);
+        let unused = self.tcx.unused_generic_params(instance);
+        debug!(?self.unused_parameters, ?unused);
+        for (i, arg) in args.iter().enumerate() {
+            let i = i.try_into().unwrap();
+            if unused.is_used(i) {
+                arg.visit_with(self);
+            }
+        }
+        debug!(?self.unused_parameters);
+    }
+}
+
+impl<'a, 'tcx> MirVisitor<'tcx> for MarkUsedGenericParams<'a, 'tcx> {
+    #[instrument(level = "debug", skip(self, local))]
+    fn visit_local_decl(&mut self, local: Local, local_decl: &LocalDecl<'tcx>) {
+        if local == Local::from_usize(1) {
+            let def_kind = self.tcx.def_kind(self.def_id);
+            if matches!(def_kind, DefKind::Closure) {
+                // Skip visiting the closure/coroutine that is currently being processed. This only
+                // happens because the first argument to the closure is a reference to itself and
+                // that will call `visit_args`, resulting in each generic parameter captured being
+                // considered used by default.
+                debug!("skipping closure args");
+                return;
+            }
+        }
+
+        self.super_local_decl(local, local_decl);
+    }
+
+    fn visit_const_operand(&mut self, ct: &ConstOperand<'tcx>, location: Location) {
+        match ct.const_ {
+            // Avoid considering `T` unused when constants are of the form:
+            //   `<Self as Foo<T>>::foo::promoted[p]`
+            mir::Const::Unevaluated(mir::UnevaluatedConst { def, promoted: Some(p), .. }, _)
+                if self.def_id == def && !self.tcx.generics_of(def).has_self =>
+            {
+                // If there is a promoted, don't look at the args - since it will always contain
+                // the generic parameters, instead, traverse the promoted MIR.
+                let promoted = self.tcx.promoted_mir(def);
+                self.visit_body(&promoted[p]);
+            }
+            _ => self.super_const_operand(ct, location),
+        }
+    }
+
+    fn visit_ty(&mut self, ty: Ty<'tcx>, ty_context: TyContext) {
+        // User type annotations are only used for type checking, they do not affect the
+        // generated code.
+        if let TyContext::UserTy(_) = ty_context {
+            return;
+        }
+        // A `PhantomData` that is the whole type of a local, a constant or a field has no code of
+        // its own, so it does not need its argument. It still does when nested within another
+        // type, whose code could depend on the argument, e.g. `type_name::<PhantomData<T>>`.
+        if let ty::Adt(def, _) = ty.kind()
+            && def.is_phantom_data()
+        {
+            return;
+        }
+        ty.visit_with(self);
+    }
+}
+
+impl<'a, 'tcx> TypeVisitor<TyCtxt<'tcx>> for MarkUsedGenericParams<'a, 'tcx> {
+    #[instrument(level = "debug", skip(self))]
+    fn visit_const(&mut self, c: ty::Const<'tcx>) {
+        if !c.has_non_region_param() {
+            return;
+        }
+
+        match c.kind() {
+            ty::ConstKind::Param(param) => {
+                debug!(?param);
+                self.unused_parameters.mark_used(param.index);
+            }
+            ty::ConstKind::Unevaluated(ty::UnevaluatedConst { def, args })
+                if matches!(self.tcx.def_kind(def), DefKind::AnonConst) =>
+            {
+                self.visit_child_body(def, args);
+            }
+            _ => c.super_visit_with(self),
+        }
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    fn visit_ty(&mut self, ty: Ty<'tcx>) {
+        if !ty.has_non_region_param() {
+            return;
+        }
+
+        match *ty.kind() {
+            ty::Closure(def_id, args)
+            | ty::CoroutineClosure(def_id, args)
+            | ty::Coroutine(def_id, args) => {
+                debug!(?def_id);
+                // Avoid cycle errors with coroutines.
+                if def_id == self.def_id {
+                    return;
+                }
+
+                // Consider any generic parameters used by any closures/coroutines as used in the
+                // parent.
+                self.visit_child_body(def_id, args);
+            }
+            ty::Param(param) => {
+                debug!(?param);
+                self.unused_parameters.mark_used(param.index);
+            }
+            _ => ty.super_visit_with(self),
+        }
+    }
+}
+
+/// Visitor used to check if a generic parameter is used.
+struct HasUsedGenericParams<'a> {
+    unused_parameters: &'a UnusedGenericParams,
+}
+
+impl<'a, 'tcx> TypeVisitor<TyCtxt<'tcx>> for HasUsedGenericParams<'a> {
+    type Result = ControlFlow<()>;
+
+    #[instrument(level = "debug", skip(self))]
+    fn visit_const(&mut self, c: ty::Const<'tcx>) -> ControlFlow<()> {
+        if !c.has_non_region_param() {
+            return ControlFlow::Continue(());
+        }
+
+        match c.kind() {
+            ty::ConstKind::Param(param) => {
+                if self.unused_parameters.is_unused(param.index) {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            }
+            _ => c.super_visit_with(self),
+        }
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<()> {
+        if !ty.has_non_region_param() {
+            return ControlFlow::Continue(());
+        }
+
+        match ty.kind() {
+            ty::Param(param) => {
+                if self.unused_parameters.is_unused(param.index) {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            }
+            _ => ty.super_visit_with(self),
+        }
+    }
+}
@@ -248,6 +248,7 @@ fn check_attributes(
                     | AttributeKind::Feature(..)
                     | AttributeKind::FfiConst(..)
                     | AttributeKind::Fundamental
+                    | AttributeKind::FutureSizeLimit { .. }
                     | AttributeKind::Ignore { .. }
                     | AttributeKind::InstructionSet(..)
                     | AttributeKind::Lang(..)
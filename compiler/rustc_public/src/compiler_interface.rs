@@ -26,7 +26,7 @@
 use crate::unstable::{RustcInternal, Stable, new_item_kind};
 use crate::{
     AssocItems, Crate, CrateDef, CrateItem, CrateItems, CrateNum, DefId, Error, Filename,
-    ImplTraitDecls, ItemKind, Symbol, ThreadLocalIndex, TraitDecls, alloc, mir,
+    ImplTraitDecls, ItemKind, Symbol, ThreadLocalIndex, TraitDecls, alloc, hir, mir,
 };
 
 pub struct BridgeTys;
@@ -311,6 +311,76 @@ pub(crate) fn get_lines(&self, span: &Span) -> LineInfo {
         LineInfo::from(lines)
     }
 
+    /// Returns the source code of the given `Span`, if it is available.
+    pub(crate) fn span_to_snippet(&self, span: &Span) -> Option<String> {
+        let tables = self.tables.borrow();
+        let cx = &*self.cx.borrow();
+        let sp = tables.spans[*span];
+        cx.span_to_snippet(sp)
+    }
+
+    /// Returns the expansion that produced the given `Span`, if any.
+    pub(crate) fn span_expansion(&self, span: &Span) -> Option<hir::Expansion> {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        let sp = tables.spans[*span];
+        cx.span_expansion(sp).map(|expn_data| expn_data.stable(&mut *tables, cx))
+    }
+
+    /// Returns the free items declared in the root module of the local crate.
+    pub(crate) fn hir_root_items(&self) -> Vec<hir::ItemDef> {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        cx.hir_root_items().into_iter().map(|did| hir::ItemDef(tables.create_def_id(did))).collect()
+    }
+
+    /// Returns all free items of the local crate.
+    pub(crate) fn hir_all_items(&self) -> Vec<hir::ItemDef> {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        cx.hir_all_items().into_iter().map(|did| hir::ItemDef(tables.create_def_id(did))).collect()
+    }
+
+    /// Returns the HIR of a free item of the local crate, or `None` if `item` is not one.
+    pub(crate) fn hir_item(&self, item: hir::ItemDef) -> Option<hir::Item> {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        let did = tables[item.0];
+        cx.hir_item(did).map(|item| item.stable(&mut *tables, cx))
+    }
+
+    /// Returns all attributes of a definition.
+    pub(crate) fn hir_attrs(&self, def_id: DefId) -> Vec<hir::Attribute> {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        let did = tables[def_id];
+        cx.all_attrs(did).stable(&mut *tables, cx)
+    }
+
+    /// Returns the HIR body of a local definition, if it has one.
+    pub(crate) fn hir_body(&self, def_id: DefId) -> Option<hir::Body> {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        let did = tables[def_id];
+        cx.hir_body(did).map(|body| body.stable(&mut *tables, cx))
+    }
+
+    /// Emits a plugin lint at the given span, with the level in effect at `node`, or at the crate
+    /// root if there is no node.
+    pub(crate) fn emit_lint(
+        &self,
+        lint: &'static rustc_session::lint::Lint,
+        node: Option<hir::NodeId>,
+        span: Span,
+        message: String,
+    ) {
+        let mut tables = self.tables.borrow_mut();
+        let cx = &*self.cx.borrow();
+        let hir_id = node.map(|node| node.internal(&mut *tables, cx.tcx));
+        let sp = tables.spans[span];
+        cx.emit_lint(lint, hir_id, sp, message)
+    }
+
     /// Returns the `kind` of given `DefId`.
     pub(crate) fn item_kind(&self, item: CrateItem) -> ItemKind {
         let tables = self.tables.borrow();
@@ -877,6 +947,19 @@ pub(crate) fn run<'tcx, F, T>(interface: &CompilerInterface<'tcx>, f: F) -> Resu
     }
 }
 
+/// Like [`run`], but shadows the interface that is currently running, if any.
+///
+/// This is needed when the compiler calls into `rustc_public` from a query, which may be
+/// executed while a driver callback is using its own interface.
+#[cfg(feature = "rustc_internal")]
+pub(crate) fn run_nested<'tcx, F, T>(interface: &CompilerInterface<'tcx>, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let ptr: *const () = (&raw const interface) as _;
+    TLV.set(&Cell::new(ptr), f)
+}
+
 /// Execute the given function with access the [`CompilerInterface`].
 ///
 /// I.e., This function will load the current interface and calls a function with it.
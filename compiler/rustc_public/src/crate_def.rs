@@ -1,13 +1,14 @@
 //! Module that define a common trait for things that represent a crate definition,
 //! such as, a function, a trait, an enum, and any other definitions.
 
-use crate::ty::{GenericArgs, Span, Ty, index_impl};
+use crate::ty::{GenericArgs, Span, Ty, deserialize_index_impl, index_impl};
 use crate::{Crate, Symbol, ThreadLocalIndex, with};
 
 /// A unique identification number for each item accessible for the current compilation unit.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DefId(pub(crate) usize, ThreadLocalIndex);
 index_impl!(DefId);
+deserialize_index_impl!(DefId);
 
 impl DefId {
     /// Return fully qualified name of this definition
@@ -0,0 +1,594 @@
+//! A read-only view of the source structure of the local crate.
+//!
+//! This module exposes a simplified version of the compiler's HIR, i.e., the code of the local
+//! crate after macro expansion and name resolution, but before bodies are lowered to MIR. It
+//! preserves what tools need to reason about the code the way the user wrote it: attributes,
+//! visibilities as written, `use` trees, the expression tree of bodies, and the macro expansion
+//! each [`Span`] comes from.
+//!
+//! Parts of the HIR that have no representation here, such as types and generic arguments as
+//! written, are exposed through their [`Span`], whose source text can be retrieved with
+//! [`Span::snippet`].
+//!
+//! The types in this module are versioned by [`HIR_VERSION`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::compiler_interface::with;
+use crate::mir::Mutability;
+use crate::ty::{Span, Ty};
+use crate::{CrateDef, DefId, Symbol};
+
+/// The version of the types in this module.
+///
+/// It is incremented whenever they change in a way that is not backwards compatible, e.g., when
+/// a variant is added to one of the enums, so that tools can check which version they were
+/// written against.
+pub const HIR_VERSION: u32 = 1;
+
+/// Returns the items declared directly in the root module of the local crate.
+pub fn root_items() -> Vec<ItemDef> {
+    with(|cx| cx.hir_root_items())
+}
+
+/// Returns all the free items of the local crate, including the ones nested in modules and
+/// bodies, in no particular order.
+///
+/// Associated items and foreign items are not included, see [`ItemKind`] for how to reach them.
+pub fn all_items() -> Vec<ItemDef> {
+    with(|cx| cx.hir_all_items())
+}
+
+/// Returns all the attributes of a definition, including the ones that the compiler interprets
+/// itself and doc comments.
+pub fn attrs(def: DefId) -> Vec<Attribute> {
+    with(|cx| cx.hir_attrs(def))
+}
+
+/// Returns the body of a local definition, if it has one.
+///
+/// Functions, constants, statics, closures and inline constants have a body.
+pub fn body(def: DefId) -> Option<Body> {
+    with(|cx| cx.hir_body(def))
+}
+
+crate_def! {
+    /// A free item of the local crate.
+    #[derive(Serialize, Deserialize)]
+    pub ItemDef;
+}
+
+impl ItemDef {
+    /// Returns the HIR of this item, or `None` if it is not a free item of the local crate,
+    /// which can only happen if it was constructed from an arbitrary [`DefId`].
+    pub fn item(&self) -> Option<Item> {
+        with(|cx| cx.hir_item(*self))
+    }
+}
+
+/// A free item of the local crate.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub def: ItemDef,
+    /// The name of the item, if it has one. For a single import, this is the name it binds.
+    /// Glob imports, `impl` blocks, foreign modules and global assembly don't have a name.
+    pub name: Option<Symbol>,
+    pub kind: ItemKind,
+    pub vis: Visibility,
+    /// The span of the visibility as written, if any.
+    pub vis_span: Option<Span>,
+    pub attrs: Vec<Attribute>,
+    pub span: Span,
+}
+
+impl Item {
+    /// Returns the body of this item, if it has one.
+    pub fn body(&self) -> Option<Body> {
+        body(self.def.def_id())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ItemKind {
+    /// An `extern crate` item, with the original name of the crate if it was renamed.
+    ExternCrate {
+        orig_name: Option<Symbol>,
+    },
+    /// A `use` item.
+    Use(UseTree),
+    Static {
+        mutability: Mutability,
+    },
+    Const,
+    Fn {
+        has_body: bool,
+    },
+    /// A `macro_rules!` or `macro` definition.
+    Macro,
+    Mod {
+        items: Vec<ItemDef>,
+    },
+    /// An `extern` block, with the definitions of its items.
+    ForeignMod {
+        items: Vec<DefId>,
+    },
+    GlobalAsm,
+    TyAlias,
+    Enum,
+    Struct,
+    Union,
+    /// A trait, with the definitions of its associated items.
+    Trait {
+        items: Vec<DefId>,
+    },
+    TraitAlias,
+    /// An `impl` block, with the definitions of its associated items.
+    Impl {
+        of_trait: bool,
+        items: Vec<DefId>,
+    },
+}
+
+/// The visibility of an item, as written in the source code.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Visibility {
+    /// `pub`
+    Public,
+    /// `pub(crate)`
+    Crate,
+    /// `pub(super)`
+    Super,
+    /// `pub(self)`
+    SelfModule,
+    /// `pub(in path)`, with the path as written.
+    InPath(String),
+    /// No visibility was written.
+    Inherited,
+}
+
+/// An attribute, including the ones that the compiler interprets itself, and doc comments.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    /// The path of the attribute, e.g., `["rustfmt", "skip"]` for `#[rustfmt::skip]`.
+    ///
+    /// This is `None` for attributes that the compiler interprets itself, such as `#[inline]`.
+    pub path: Option<Vec<Symbol>>,
+    /// The attribute printed back as source code.
+    pub text: String,
+    /// The contents of the doc comment, if this is a doc comment.
+    pub doc: Option<Symbol>,
+    /// The span of the attribute, when the compiler keeps track of it.
+    pub span: Option<Span>,
+}
+
+/// A single `use` path.
+///
+/// A `use` item with nested imports, such as `use a::{b, c as d};`, is represented by one `use`
+/// item per import, i.e., `a::b` and `a::c as d`, plus a [`UseKind::ListStem`] item for `a::{}`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UseTree {
+    pub path: Vec<PathSegment>,
+    pub kind: UseKind,
+    /// The definitions the path resolves to, one per namespace.
+    pub res: Vec<Res>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum UseKind {
+    /// `use a::b;` or `use a::b as c;`, with the name the import is bound to.
+    Single(Symbol),
+    /// `use a::*;`
+    Glob,
+    /// The `a::{}` stem of `use a::{b, c};`.
+    ListStem,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PathSegment {
+    pub name: Symbol,
+    pub span: Span,
+}
+
+/// A path in an expression or a pattern, together with what it resolves to.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Path {
+    /// The span of the self type of a qualified path such as `<T as Trait>::f` or `T::f`.
+    pub qself: Option<Span>,
+    pub segments: Vec<PathSegment>,
+    pub res: Res,
+    pub span: Span,
+}
+
+/// The resolution of a path.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Res {
+    Def(DefId),
+    /// A local variable, introduced by the pattern binding with the same id.
+    Local(BindingId),
+    /// A primitive type, such as `u8`.
+    PrimTy(Symbol),
+    /// `Self` in a trait or an `impl` block.
+    SelfTy,
+    /// `Self` used as the constructor of the given struct.
+    SelfCtor(DefId),
+    /// Any other resolution, e.g., a tool module or a path that failed to resolve.
+    Other,
+}
+
+/// Identifies a local variable. It is unique within the item that owns the body, including the
+/// closures and inline constants defined in it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct BindingId(pub usize);
+
+/// Identifies a node of the HIR of the local crate, such as an expression or a statement.
+///
+/// It can be passed to [`LintContext::emit_at`](crate::lint::LintContext::emit_at), so that
+/// attributes like `#[allow(...)]` on the node are taken into account.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NodeId {
+    /// The item, or the associated or foreign item, that the node belongs to.
+    pub(crate) owner: DefId,
+    pub(crate) local_id: u32,
+}
+
+impl NodeId {
+    /// The node of an item, or of an associated or foreign item.
+    pub(crate) fn of_owner(owner: DefId) -> NodeId {
+        NodeId { owner, local_id: 0 }
+    }
+}
+
+/// The body of a function, constant, static, closure or inline constant.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Body {
+    pub params: Vec<Param>,
+    pub value: Expr,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Param {
+    pub id: NodeId,
+    pub pat: Pat,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Expr {
+    pub id: NodeId,
+    pub kind: ExprKind,
+    /// The type of the expression, before any adjustment such as auto-deref is applied.
+    ///
+    /// This is `None` for expressions that are not type checked as part of the body.
+    pub ty: Option<Ty>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ExprKind {
+    /// An inline constant, e.g., `const { 1 }`. Its body can be retrieved with [`body`].
+    ConstBlock(DefId),
+    Array(Vec<Expr>),
+    Call(Box<Expr>, Vec<Expr>),
+    /// A method call, with the method it resolves to, if any.
+    MethodCall {
+        receiver: Box<Expr>,
+        method: PathSegment,
+        args: Vec<Expr>,
+        callee: Option<DefId>,
+    },
+    /// `expr.use`
+    Use(Box<Expr>),
+    Tuple(Vec<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Unary(UnOp, Box<Expr>),
+    Lit(Lit),
+    /// `expr as ty`, with the span of the type as written.
+    Cast(Box<Expr>, Span),
+    /// A type ascription, with the span of the type as written.
+    Type(Box<Expr>, Span),
+    /// A compiler-introduced wrapper that drops the temporaries of the inner expression.
+    DropTemps(Box<Expr>),
+    /// `let pat = expr`, in the condition of an `if` or a `while`.
+    Let {
+        pat: Pat,
+        init: Box<Expr>,
+    },
+    If {
+        cond: Box<Expr>,
+        then: Box<Expr>,
+        els: Option<Box<Expr>>,
+    },
+    Loop {
+        body: Block,
+        label: Option<Symbol>,
+        source: LoopSource,
+    },
+    Match {
+        scrutinee: Box<Expr>,
+        arms: Vec<Arm>,
+        source: MatchSource,
+    },
+    /// A closure. Its body can be retrieved with [`body`].
+    Closure {
+        def: DefId,
+        is_move: bool,
+    },
+    Block(Block, Option<Symbol>),
+    Assign(Box<Expr>, Box<Expr>),
+    /// A compound assignment, e.g., `a += b`.
+    AssignOp(BinOp, Box<Expr>, Box<Expr>),
+    Field(Box<Expr>, Symbol),
+    Index(Box<Expr>, Box<Expr>),
+    Path(Path),
+    AddrOf {
+        kind: BorrowKind,
+        mutability: Mutability,
+        expr: Box<Expr>,
+    },
+    Break {
+        label: Option<Symbol>,
+        value: Option<Box<Expr>>,
+    },
+    Continue {
+        label: Option<Symbol>,
+    },
+    Return(Option<Box<Expr>>),
+    Become(Box<Expr>),
+    InlineAsm,
+    /// `offset_of!(container, fields)`, with the span of the container type as written.
+    OffsetOf {
+        container: Span,
+        fields: Vec<Symbol>,
+    },
+    Struct {
+        path: Path,
+        fields: Vec<ExprField>,
+        base: Option<Box<Expr>>,
+    },
+    /// `[value; count]`, with the span of the count as written.
+    Repeat(Box<Expr>, Span),
+    Yield(Box<Expr>),
+    UnsafeBinderCast(Box<Expr>),
+    /// An expression that failed to compile.
+    Err,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExprField {
+    pub name: Symbol,
+    pub expr: Expr,
+    pub is_shorthand: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    /// The trailing expression of the block, if any.
+    pub expr: Option<Box<Expr>>,
+    /// Whether this is an `unsafe` block written by the user.
+    pub is_unsafe: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Stmt {
+    pub id: NodeId,
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StmtKind {
+    Let(LetStmt),
+    Item(ItemDef),
+    /// An expression without a trailing semicolon, e.g., a block used as a statement.
+    Expr(Expr),
+    Semi(Expr),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LetStmt {
+    pub pat: Pat,
+    /// The span of the type annotation as written, if any.
+    pub ty: Option<Span>,
+    pub init: Option<Expr>,
+    /// The `else` block of a `let ... else`.
+    pub els: Option<Block>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Arm {
+    pub id: NodeId,
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub body: Expr,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Pat {
+    pub kind: PatKind,
+    /// The type of the value matched by the pattern.
+    pub ty: Option<Ty>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PatKind {
+    Wild,
+    /// A binding, e.g., `ref mut x @ subpat`.
+    Binding {
+        id: BindingId,
+        name: Symbol,
+        /// The mutability of the reference if the binding is `ref` or `ref mut`.
+        by_ref: Option<Mutability>,
+        mutability: Mutability,
+        subpat: Option<Box<Pat>>,
+    },
+    Struct {
+        path: Path,
+        fields: Vec<PatField>,
+        has_rest: bool,
+    },
+    /// A tuple struct pattern, with the position of `..` if any.
+    TupleStruct {
+        path: Path,
+        elems: Vec<Pat>,
+        rest: Option<usize>,
+    },
+    Or(Vec<Pat>),
+    Never,
+    /// A tuple pattern, with the position of `..` if any.
+    Tuple(Vec<Pat>, Option<usize>),
+    Box(Box<Pat>),
+    Deref(Box<Pat>),
+    Ref(Box<Pat>, Mutability),
+    Expr(PatExpr),
+    Guard(Box<Pat>, Box<Expr>),
+    Range {
+        start: Option<PatExpr>,
+        end: Option<PatExpr>,
+        inclusive: bool,
+    },
+    Slice {
+        before: Vec<Pat>,
+        middle: Option<Box<Pat>>,
+        after: Vec<Pat>,
+    },
+    /// A pattern that failed to compile.
+    Err,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PatField {
+    pub name: Symbol,
+    pub pat: Pat,
+    pub is_shorthand: bool,
+    pub span: Span,
+}
+
+/// A literal or a path used as a pattern, or as a bound of a range pattern.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PatExpr {
+    Lit { lit: Lit, negated: bool },
+    Path(Path),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Lit {
+    pub kind: LitKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LitKind {
+    Str(Symbol),
+    ByteStr(Vec<u8>),
+    CStr(Vec<u8>),
+    Byte(u8),
+    Char(char),
+    Int(u128),
+    /// A float literal, as written, without its suffix.
+    Float(Symbol),
+    Bool(bool),
+    Err,
+}
+
+/// A binary operator, as written. Unlike [`crate::mir::BinOp`], this includes the lazy boolean
+/// operators.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    And,
+    Or,
+    BitXor,
+    BitAnd,
+    BitOr,
+    Shl,
+    Shr,
+    Eq,
+    Lt,
+    Le,
+    Ne,
+    Ge,
+    Gt,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum UnOp {
+    Deref,
+    Not,
+    Neg,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BorrowKind {
+    /// `&expr` or `&mut expr`
+    Ref,
+    /// `&raw const expr` or `&raw mut expr`
+    Raw,
+    /// `&pin const expr` or `&pin mut expr`
+    Pin,
+}
+
+/// The source code construct a loop was lowered from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum LoopSource {
+    Loop,
+    While,
+    ForLoop,
+}
+
+/// The source code construct a `match` was lowered from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MatchSource {
+    Normal,
+    /// `expr.match { ... }`
+    Postfix,
+    ForLoopDesugar,
+    /// The `?` operator.
+    TryDesugar,
+    AwaitDesugar,
+    FormatArgs,
+}
+
+/// Describes the expansion that produced a [`Span`], see [`Span::expansion`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Expansion {
+    pub kind: ExpansionKind,
+    /// The span of the macro invocation, or of the construct that was desugared.
+    ///
+    /// It may itself come from an expansion, e.g., when a macro invokes another macro.
+    pub call_site: Span,
+    /// The span of the definition of the macro.
+    pub def_site: Span,
+    /// The definition of the macro, if the expansion comes from a macro.
+    pub macro_def: Option<DefId>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ExpansionKind {
+    /// The expansion of a macro, with the name of the macro.
+    Macro(MacroKind, Symbol),
+    /// A transformation done by the compiler on the syntax tree, e.g., the injection of the
+    /// standard library prelude.
+    AstPass(String),
+    /// A desugaring done by the compiler, e.g., of the `?` operator.
+    Desugaring(String),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MacroKind {
+    /// A function-like macro, e.g., `foo!()`.
+    Bang,
+    /// An attribute macro, e.g., `#[foo]`.
+    Attr,
+    /// A derive macro, e.g., `#[derive(Foo)]`.
+    Derive,
+}
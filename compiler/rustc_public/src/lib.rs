@@ -48,6 +48,8 @@
 pub mod compiler_interface;
 #[macro_use]
 pub mod error;
+pub mod hir;
+pub mod lint;
 pub mod mir;
 pub mod target;
 #[cfg(test)]
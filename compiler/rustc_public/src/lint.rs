@@ -0,0 +1,208 @@
+//! Lints that are loaded into the compiler at runtime.
+//!
+//! A lint plugin is a `dylib` crate that uses `rustc_public`, and declares its entry point with
+//! [`declare_lint_plugin!`]. It is loaded with `-Zlint-plugin=<path>`, which may be repeated.
+//!
+//! ```ignore (needs to be built as a `dylib`)
+//! #![feature(rustc_private)]
+//! extern crate rustc_driver;
+//! extern crate rustc_public;
+//!
+//! use rustc_public::hir::{Item, ItemKind};
+//! use rustc_public::lint::{Level, Lint, LintContext, LintId, LintPass, LintRegistry};
+//!
+//! struct NoGlobAsm(LintId);
+//!
+//! impl LintPass for NoGlobAsm {
+//!     fn check_item(&self, cx: &LintContext, item: &Item) {
+//!         if let ItemKind::GlobalAsm = item.kind {
+//!             cx.emit(self.0, item.span, "global assembly is not allowed in this project");
+//!         }
+//!     }
+//! }
+//!
+//! fn register(registry: &mut LintRegistry) {
+//!     let lint = registry.register_lint(Lint {
+//!         name: "no_global_asm",
+//!         default_level: Level::Deny,
+//!         description: "detects uses of `global_asm!`",
+//!     });
+//!     registry.register_pass(NoGlobAsm(lint));
+//! }
+//!
+//! rustc_public::declare_lint_plugin!(register);
+//! ```
+//!
+//! Plugin lints are registered with the compiler like any other lint. Their level can be changed
+//! on the command line and with attributes such as `#[allow(no_global_asm)]`, and they are listed
+//! by `-W help`.
+//!
+//! # Compatibility
+//!
+//! The compiler and a plugin only communicate through C functions and data serialized in a
+//! versioned format. Before calling into a plugin, the compiler checks that it was written against
+//! the same [`PLUGIN_INTERFACE_VERSION`] and [`HIR_VERSION`], and reports an error otherwise. A
+//! plugin built with one toolchain therefore keeps working with later toolchains until one of these
+//! versions changes.
+//!
+//! This only holds for the API of this module and for the data in the [`hir`](crate::hir) types.
+//! Other functions of `rustc_public` that query the compiler, such as [`Span::snippet`],
+//! [`Ty::kind`](crate::ty::Ty::kind) or [`Item::body`], only work in a plugin that is built with
+//! the same toolchain that loads it. [`LintContext::body`] works in any case.
+//!
+//! Lint passes run after type checking, in a `rustc_public` context of their own. Items obtained
+//! from other contexts are not valid inside a pass.
+//!
+//! [`HIR_VERSION`]: crate::hir::HIR_VERSION
+
+use crate::hir::{Body, Item, NodeId};
+use crate::ty::Span;
+use crate::{DefId, IndexedVal};
+
+#[doc(hidden)]
+pub mod ffi;
+
+/// The version of the interface between the compiler and lint plugins.
+///
+/// It is incremented whenever the types in `lint::ffi` or the symbols generated by
+/// [`declare_lint_plugin!`] change in a way that is not backwards compatible.
+pub const PLUGIN_INTERFACE_VERSION: u32 = 1;
+
+/// The default level of a lint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// The declaration of a lint provided by a plugin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Lint {
+    /// The name of the lint, as used in attributes and on the command line.
+    ///
+    /// It may only contain ASCII letters, digits and underscores, and must not clash with the
+    /// name of another lint.
+    pub name: &'static str,
+    /// The level of the lint when it is not changed on the command line or with an attribute.
+    pub default_level: Level,
+    /// A short description of what the lint detects, shown by `-W help`.
+    pub description: &'static str,
+}
+
+/// A lint registered with [`LintRegistry::register_lint`], to be passed to [`LintContext::emit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LintId {
+    pub(crate) index: u32,
+    name: &'static str,
+}
+
+impl LintId {
+    /// The name of the lint.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// A set of checks that emit the lints of a plugin.
+///
+/// All methods have an empty default implementation, so a pass only needs to implement the
+/// checks it is interested in.
+pub trait LintPass: Send + Sync {
+    /// Checks the local crate as a whole. This runs before any item is checked.
+    fn check_crate(&self, _cx: &LintContext) {}
+
+    /// Checks a free item of the local crate. This runs for every item returned by
+    /// [`hir::all_items`](crate::hir::all_items).
+    fn check_item(&self, _cx: &LintContext, _item: &Item) {}
+}
+
+/// Collects the lints and passes of a plugin when it is loaded.
+pub struct LintRegistry {
+    pub(crate) lints: Vec<Lint>,
+    pub(crate) passes: Vec<Box<dyn LintPass>>,
+}
+
+impl LintRegistry {
+    pub(crate) fn new() -> LintRegistry {
+        LintRegistry { lints: Vec::new(), passes: Vec::new() }
+    }
+
+    /// Declares a lint, so that it can be emitted by the passes of this plugin.
+    pub fn register_lint(&mut self, lint: Lint) -> LintId {
+        let index = u32::try_from(self.lints.len()).expect("too many lints");
+        self.lints.push(lint);
+        LintId { index, name: lint.name }
+    }
+
+    /// Adds a pass that runs on the local crate.
+    ///
+    /// Passes run in the order in which they were registered.
+    pub fn register_pass(&mut self, pass: impl LintPass + 'static) {
+        self.passes.push(Box::new(pass));
+    }
+}
+
+/// The context in which a [`LintPass`] check runs.
+#[derive(Debug)]
+pub struct LintContext {
+    pub(crate) host: *const ffi::Host,
+}
+
+impl LintContext {
+    /// Emits `lint` at `span` with the given message.
+    ///
+    /// The level of the lint is the one in effect at the item being checked, or at the crate
+    /// root in [`LintPass::check_crate`].
+    pub fn emit(&self, lint: LintId, span: Span, message: impl Into<String>) {
+        self.emit_raw(lint, None, span, &message.into())
+    }
+
+    /// Emits `lint` at `span` with the given message, with the level in effect at `node`.
+    ///
+    /// This takes attributes like `#[allow(...)]` on statements and expressions into account,
+    /// which [`LintContext::emit`] doesn't.
+    pub fn emit_at(&self, lint: LintId, node: NodeId, span: Span, message: impl Into<String>) {
+        let node = ffi::RawNodeId { owner: node.owner.to_index(), local_id: node.local_id };
+        self.emit_raw(lint, Some(&node), span, &message.into())
+    }
+
+    fn emit_raw(&self, lint: LintId, node: Option<&ffi::RawNodeId>, span: Span, message: &str) {
+        let node = node.map_or(std::ptr::null(), |node| node as *const _);
+        // SAFETY: the host is valid for the duration of the check that `self` was passed to.
+        unsafe {
+            let host = &*self.host;
+            (host.emit)(host.data, lint.index, node, span.to_index(), ffi::RawStr::new(message))
+        }
+    }
+
+    /// Returns the body of a local definition, if it has one, like [`hir::body`].
+    ///
+    /// Unlike [`hir::body`] and [`Item::body`], this works in plugins that are built with a
+    /// different toolchain than the one that loads them.
+    ///
+    /// [`hir::body`]: crate::hir::body
+    pub fn body(&self, def: DefId) -> Option<Body> {
+        // SAFETY: the host is valid for the duration of the check that `self` was passed to.
+        ffi::body(unsafe { &*self.host }, def.to_index())
+    }
+}
+
+/// Declares the entry point of a lint plugin.
+///
+/// The argument is the path of a function with the signature `fn(&mut LintRegistry)`, which is
+/// called once when the plugin is loaded.
+#[macro_export]
+macro_rules! declare_lint_plugin {
+    ($register:path) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn __rustc_lint_plugin_interface_version() -> u32 {
+            $crate::lint::PLUGIN_INTERFACE_VERSION
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn __rustc_lint_plugin_register() -> $crate::lint::ffi::Plugin {
+            $crate::lint::ffi::Plugin::new($register)
+        }
+    };
+}
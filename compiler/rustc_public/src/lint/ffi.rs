@@ -0,0 +1,187 @@
+//! The C ABI through which the compiler and lint plugins communicate.
+//!
+//! [`declare_lint_plugin!`](crate::declare_lint_plugin) exports two functions from a plugin:
+//! `__rustc_lint_plugin_interface_version`, which returns [`PLUGIN_INTERFACE_VERSION`], and
+//! `__rustc_lint_plugin_register`, which returns a [`Plugin`]. Everything else is passed through
+//! the `#[repr(C)]` types of this module, and the HIR of the local crate is passed serialized as
+//! JSON. As a result, the plugin and the compiler don't need to agree on the layout of any Rust
+//! type, only on [`PLUGIN_INTERFACE_VERSION`] and [`HIR_VERSION`].
+//!
+//! [`PLUGIN_INTERFACE_VERSION`]: super::PLUGIN_INTERFACE_VERSION
+//! [`HIR_VERSION`]: crate::hir::HIR_VERSION
+
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::{slice, str};
+
+use crate::hir::{Body, HIR_VERSION, Item};
+use crate::lint::{Level, LintContext, LintRegistry};
+
+/// A borrowed UTF-8 string.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RawStr {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+impl RawStr {
+    pub fn new(s: &str) -> RawStr {
+        RawStr { ptr: s.as_ptr(), len: s.len() }
+    }
+
+    /// # Safety
+    ///
+    /// `self` must point to a valid UTF-8 string that lives at least as long as `'a`.
+    pub unsafe fn as_str<'a>(self) -> &'a str {
+        // SAFETY: guaranteed by the caller.
+        unsafe { str::from_utf8_unchecked(slice::from_raw_parts(self.ptr, self.len)) }
+    }
+}
+
+pub const LEVEL_ALLOW: u32 = 0;
+pub const LEVEL_WARN: u32 = 1;
+pub const LEVEL_DENY: u32 = 2;
+
+/// A lint declared by a plugin, see [`Lint`](super::Lint).
+#[repr(C)]
+pub struct RawLint {
+    pub name: RawStr,
+    pub description: RawStr,
+    /// One of [`LEVEL_ALLOW`], [`LEVEL_WARN`] and [`LEVEL_DENY`].
+    pub default_level: u32,
+}
+
+/// A [`NodeId`](crate::hir::NodeId), with the owner given as the index of its `DefId`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RawNodeId {
+    pub owner: usize,
+    pub local_id: u32,
+}
+
+/// The callbacks into the compiler, which are valid for the duration of a call into the plugin.
+#[repr(C)]
+pub struct Host {
+    pub data: *const c_void,
+    /// Emits the lint with the given index in [`Plugin::lints`] at the span with the given index.
+    /// The level is the one in effect at `node`, or at the item or crate being checked if `node`
+    /// is null.
+    pub emit: unsafe extern "C" fn(
+        data: *const c_void,
+        lint: u32,
+        node: *const RawNodeId,
+        span: usize,
+        message: RawStr,
+    ),
+    /// Calls `write` with the body of the definition with the given index, serialized as JSON,
+    /// unless it doesn't have a body.
+    pub body: unsafe extern "C" fn(
+        data: *const c_void,
+        def: usize,
+        out: *mut c_void,
+        write: unsafe extern "C" fn(out: *mut c_void, body: RawStr),
+    ),
+}
+
+/// The entry points of a plugin, returned by `__rustc_lint_plugin_register`.
+///
+/// The plugin is never unloaded, so everything it points to lives for the rest of the process.
+#[repr(C)]
+pub struct Plugin {
+    /// The [`HIR_VERSION`] that the plugin was written against.
+    pub hir_version: u32,
+    pub data: *const c_void,
+    pub lints: *const RawLint,
+    pub num_lints: usize,
+    /// Runs [`LintPass::check_crate`](super::LintPass::check_crate) for all passes. Returns
+    /// false if a pass panicked.
+    pub check_crate: unsafe extern "C" fn(data: *const c_void, host: *const Host) -> bool,
+    /// Runs [`LintPass::check_item`](super::LintPass::check_item) for all passes, with an
+    /// [`Item`] serialized as JSON. Returns false if a pass panicked.
+    pub check_item:
+        unsafe extern "C" fn(data: *const c_void, host: *const Host, item: RawStr) -> bool,
+}
+
+// SAFETY: `data` points to a `PluginData`, whose passes are `Send` and `Sync`.
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+struct PluginData {
+    registry: LintRegistry,
+    // Keeps the `RawLint`s that `Plugin::lints` points to alive.
+    _lints: Vec<RawLint>,
+}
+
+impl Plugin {
+    /// Runs the register function of a plugin, and returns its entry points.
+    pub fn new(register: fn(&mut LintRegistry)) -> Plugin {
+        let mut registry = LintRegistry::new();
+        register(&mut registry);
+        let lints: Vec<_> = registry
+            .lints
+            .iter()
+            .map(|lint| RawLint {
+                name: RawStr::new(lint.name),
+                description: RawStr::new(lint.description),
+                default_level: match lint.default_level {
+                    Level::Allow => LEVEL_ALLOW,
+                    Level::Warn => LEVEL_WARN,
+                    Level::Deny => LEVEL_DENY,
+                },
+            })
+            .collect();
+        let (lints_ptr, num_lints) = (lints.as_ptr(), lints.len());
+        let data = Box::leak(Box::new(PluginData { registry, _lints: lints }));
+        Plugin {
+            hir_version: HIR_VERSION,
+            data: (data as *const PluginData).cast(),
+            lints: lints_ptr,
+            num_lints,
+            check_crate,
+            check_item,
+        }
+    }
+}
+
+unsafe extern "C" fn check_crate(data: *const c_void, host: *const Host) -> bool {
+    // SAFETY: `data` is the one created by `Plugin::new`.
+    let data = unsafe { &*data.cast::<PluginData>() };
+    let cx = LintContext { host };
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        for pass in &data.registry.passes {
+            pass.check_crate(&cx);
+        }
+    }))
+    .is_ok()
+}
+
+unsafe extern "C" fn check_item(data: *const c_void, host: *const Host, item: RawStr) -> bool {
+    // SAFETY: `data` is the one created by `Plugin::new`.
+    let data = unsafe { &*data.cast::<PluginData>() };
+    let cx = LintContext { host };
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        // SAFETY: the compiler passes a valid string.
+        let item: Item = serde_json::from_str(unsafe { item.as_str() })
+            .expect("the compiler should serialize items with the same HIR version");
+        for pass in &data.registry.passes {
+            pass.check_item(&cx, &item);
+        }
+    }))
+    .is_ok()
+}
+
+/// Retrieves the body of a definition through `host`.
+pub(super) fn body(host: &Host, def: usize) -> Option<Body> {
+    unsafe extern "C" fn write(out: *mut c_void, body: RawStr) {
+        // SAFETY: `out` is the `Option<String>` below, and the compiler passes a valid string.
+        unsafe { *out.cast::<Option<String>>() = Some(body.as_str().to_owned()) };
+    }
+    let mut json: Option<String> = None;
+    // SAFETY: `host` is valid during the call into the plugin that `body` is called from.
+    unsafe { (host.body)(host.data, def, (&raw mut json).cast(), write) };
+    json.map(|json| {
+        serde_json::from_str(&json)
+            .expect("the compiler should serialize bodies with the same HIR version")
+    })
+}
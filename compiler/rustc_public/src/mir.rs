@@ -2,6 +2,7 @@
 mod body;
 pub mod mono;
 pub mod pretty;
+pub mod transform;
 pub mod visit;
 
 pub use body::*;
@@ -1,6 +1,6 @@
 use std::io;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::compiler_interface::with;
 use crate::mir::pretty::function_body;
@@ -11,7 +11,7 @@
 use crate::{Error, Opaque, Span, Symbol};
 
 /// The rustc_public's IR representation of a single function.
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct Body {
     pub blocks: Vec<BasicBlock>,
 
@@ -119,6 +119,14 @@ pub fn dump<W: io::Write>(&self, w: &mut W, fn_name: &str) -> io::Result<()> {
     pub fn spread_arg(&self) -> Option<Local> {
         self.spread_arg
     }
+
+    /// Declares a new local in this body and returns it.
+    ///
+    /// The new local is an inner local, i.e. it is neither the return local nor an argument.
+    pub fn new_local(&mut self, ty: Ty, span: Span, mutability: Mutability) -> Local {
+        self.locals.push(LocalDecl { ty, span, mutability });
+        self.locals.len() - 1
+    }
 }
 
 type LocalDecls = Vec<LocalDecl>;
@@ -962,7 +970,7 @@ pub enum FakeBorrowKind {
     Shallow,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Mutability {
     Not,
     Mut,
@@ -0,0 +1,54 @@
+//! Transformation passes that drivers can run on the MIR of the local crate.
+//!
+//! Passes registered with [`register_mir_pass`] receive the body of every function of the local
+//! crate after the compiler has optimized it, and before it is handed over to code generation.
+//! They may change the body in place. The compiler validates the changed body afterwards, and
+//! aborts with an internal error if the result is not well-formed.
+//!
+//! Passes only run if the driver installed the `rustc_public` query providers, which the
+//! `run!` and `run_with_tcx!` macros do. They must be registered before the compiler starts.
+//!
+//! # Limitations
+//!
+//! - Passes run inside the compiler's own query system, in a fresh `rustc_public` context. Items
+//!   and types obtained from the context of the driver callback are not valid inside a pass, and
+//!   the items seen by a pass are not valid outside of it.
+//! - The optimized MIR of a function is cached by incremental compilation. A pass that changes
+//!   its behavior between compilation sessions must be used with incremental compilation
+//!   disabled.
+//! - Bodies that use constructs without a `rustc_public` representation, such as tail calls or
+//!   unsafe binders, are not handed to the passes. Neither are compiler generated shims.
+//! - The number of arguments of a body cannot be changed. Passes also cannot introduce inline
+//!   assembly, coverage statements, `FakeRead`, `AscribeUserType` or `Rvalue::Len`.
+//! - Information that `rustc_public` does not expose is recovered on a best-effort basis for new
+//!   statements and terminators. For example, their source scope is looked up by span, and they
+//!   don't carry the call source used by compiler diagnostics.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::CrateItem;
+use crate::mir::Body;
+
+/// A transformation pass on the MIR of the local crate.
+pub trait MirPass: Send + Sync {
+    /// The name of this pass, used when reporting problems with the bodies it produced.
+    fn name(&self) -> &str;
+
+    /// Transforms the optimized body of `item` in place.
+    fn run_pass(&self, item: CrateItem, body: &mut Body);
+}
+
+static PASSES: RwLock<Vec<Arc<dyn MirPass>>> = RwLock::new(Vec::new());
+
+/// Registers a pass that runs on the optimized MIR of every function of the local crate.
+///
+/// Passes run in the order in which they were registered.
+pub fn register_mir_pass(pass: impl MirPass + 'static) {
+    PASSES.write().unwrap_or_else(PoisonError::into_inner).push(Arc::new(pass));
+}
+
+/// Returns the passes registered so far.
+#[cfg_attr(not(feature = "rustc_internal"), allow(dead_code))]
+pub(crate) fn registered_mir_passes() -> Vec<Arc<dyn MirPass>> {
+    PASSES.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
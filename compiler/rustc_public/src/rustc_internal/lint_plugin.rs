@@ -0,0 +1,272 @@
+//! Loads the lint plugins given with `-Zlint-plugin`, see [`crate::lint`], and runs their passes
+//! as part of the compiler's late lint pass.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::path::Path;
+use std::slice;
+use std::sync::Arc;
+
+use rustc_data_structures::sync::IntoDynSyncSend;
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_metadata::{DylibError, load_symbol_from_dylib};
+use rustc_public_bridge::Tables;
+use rustc_public_bridge::context::CompilerCtxt;
+use rustc_session::Session;
+use rustc_session::lint::{Lint, LintPass, LintVec};
+
+use crate::compiler_interface::{CompilerInterface, run_nested, with};
+use crate::hir::{self, HIR_VERSION, NodeId};
+use crate::lint::PLUGIN_INTERFACE_VERSION;
+use crate::lint::ffi::{self, RawNodeId, RawStr};
+use crate::ty::Span;
+use crate::{DefId, IndexedVal};
+
+type InterfaceVersionFn = extern "C" fn() -> u32;
+type RegisterFn = extern "C" fn() -> ffi::Plugin;
+
+/// A plugin that was loaded successfully.
+struct LoadedPlugin {
+    path: String,
+    plugin: ffi::Plugin,
+    /// The lints of the plugin, in the order of `plugin.lints`.
+    lints: Vec<&'static Lint>,
+}
+
+/// Loads the plugins given with `-Zlint-plugin`, and registers their lints with `lint_store`.
+///
+/// This is meant to be called from [`Config::register_lints`]. Problems with a plugin are
+/// reported as errors, and its lints are not registered.
+///
+/// [`Config::register_lints`]: https://doc.rust-lang.org/nightly/nightly-rustc/rustc_interface/interface/struct.Config.html#structfield.register_lints
+pub fn register_lint_plugins(sess: &Session, lint_store: &mut LintStore) {
+    let mut plugins = Vec::new();
+    for path in &sess.opts.unstable_opts.lint_plugin {
+        let Some(plugin) = load_plugin(sess, lint_store, Path::new(path)) else { continue };
+        lint_store.register_lints(&plugin.lints);
+        plugins.push(plugin);
+    }
+    if plugins.is_empty() {
+        return;
+    }
+
+    let lints: LintVec = plugins.iter().flat_map(|plugin| plugin.lints.iter().copied()).collect();
+    let plugins = IntoDynSyncSend(Arc::<[_]>::from(plugins));
+    lint_store.register_late_pass(move |_| {
+        Box::new(PluginLints { lints: lints.clone(), plugins: Arc::clone(&*plugins) })
+    });
+}
+
+fn load_plugin(sess: &Session, lint_store: &LintStore, path: &Path) -> Option<LoadedPlugin> {
+    let dcx = sess.dcx();
+    if !path.exists() {
+        dcx.err(format!("couldn't find lint plugin `{}`", path.display()));
+        return None;
+    }
+    let load = |symbol: &str| {
+        // SAFETY: the types of the symbols match the ones generated by `declare_lint_plugin!`,
+        // and the interface version is checked before calling anything else.
+        match unsafe { load_symbol_from_dylib(path, symbol) } {
+            Ok(sym) => Some(sym),
+            Err(DylibError::DlOpen(path, err)) => {
+                dcx.err(format!("couldn't load lint plugin {path}{err}"));
+                None
+            }
+            Err(DylibError::DlSym(path, err)) => {
+                dcx.err(format!(
+                    "`{symbol}` symbol lookup in lint plugin `{path}` failed{err}; \
+                     is the plugin declared with `declare_lint_plugin!`?"
+                ));
+                None
+            }
+        }
+    };
+
+    let interface_version =
+        load("__rustc_lint_plugin_interface_version").map(|f: InterfaceVersionFn| f())?;
+    if interface_version != PLUGIN_INTERFACE_VERSION {
+        dcx.err(format!(
+            "lint plugin `{}` uses version {interface_version} of the plugin interface, \
+             but this compiler supports version {PLUGIN_INTERFACE_VERSION}",
+            path.display()
+        ));
+        return None;
+    }
+    let register: RegisterFn = load("__rustc_lint_plugin_register")?;
+    let plugin = register();
+    if plugin.hir_version != HIR_VERSION {
+        dcx.err(format!(
+            "lint plugin `{}` uses version {} of the HIR, but this compiler supports version \
+             {HIR_VERSION}",
+            path.display(),
+            plugin.hir_version
+        ));
+        return None;
+    }
+
+    // SAFETY: the plugin passes a valid array, which lives as long as the plugin is loaded.
+    let raw_lints = unsafe { slice::from_raw_parts(plugin.lints, plugin.num_lints) };
+    let mut lints: Vec<&'static Lint> = Vec::with_capacity(raw_lints.len());
+    let mut valid = true;
+    for raw in raw_lints {
+        // SAFETY: the plugin passes valid strings.
+        let (name, desc) = unsafe { (raw.name.as_str(), raw.description.as_str()) };
+        let default_level = match raw.default_level {
+            ffi::LEVEL_ALLOW => rustc_session::lint::Level::Allow,
+            ffi::LEVEL_WARN => rustc_session::lint::Level::Warn,
+            ffi::LEVEL_DENY => rustc_session::lint::Level::Deny,
+            level => {
+                dcx.err(format!(
+                    "lint plugin `{}` declares the lint `{name}` with the unknown level {level}",
+                    path.display()
+                ));
+                valid = false;
+                continue;
+            }
+        };
+        // The compiler keeps lints for the rest of the process.
+        let lint = Box::leak(Box::new(Lint {
+            name: Box::leak(Box::<str>::from(name)),
+            default_level,
+            desc: Box::leak(Box::<str>::from(desc)),
+            is_externally_loaded: true,
+            ..Lint::default_fields_for_macro()
+        }));
+
+        let name = lint.name_lower();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            dcx.err(format!(
+                "lint plugin `{}` declares a lint with the invalid name `{}`",
+                path.display(),
+                lint.name
+            ));
+            valid = false;
+        } else if lint_store.find_lints(&name).is_some()
+            || lints.iter().any(|other| other.name_lower() == name)
+        {
+            dcx.err(format!(
+                "lint plugin `{}` declares the lint `{name}`, which already exists",
+                path.display()
+            ));
+            valid = false;
+        }
+        lints.push(lint);
+    }
+    valid.then(|| LoadedPlugin { path: path.display().to_string(), plugin, lints })
+}
+
+/// The late lint pass that runs the passes of all plugins.
+struct PluginLints {
+    lints: LintVec,
+    plugins: Arc<[LoadedPlugin]>,
+}
+
+impl LintPass for PluginLints {
+    fn name(&self) -> &'static str {
+        "PluginLints"
+    }
+
+    fn get_lints(&self) -> LintVec {
+        self.lints.clone()
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for PluginLints {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        // The driver may be using `rustc_public` while the lints run, so the passes get a
+        // context of their own.
+        let interface = CompilerInterface {
+            tables: RefCell::new(Tables::default()),
+            cx: RefCell::new(CompilerCtxt::new(cx.tcx)),
+        };
+        let _: Result<(), crate::Error> = run_nested(&interface, || {
+            // A plugin that panicked is not called again.
+            let mut panicked = vec![false; self.plugins.len()];
+            for (plugin, panicked) in self.plugins.iter().zip(&mut panicked) {
+                let data = HostData { lints: &plugin.lints, owner: None };
+                // SAFETY: `data` is the one the plugin was created with, and the host is valid
+                // for the duration of the call.
+                if !unsafe { (plugin.plugin.check_crate)(plugin.plugin.data, &data.host()) } {
+                    report_panic(cx, plugin, "the crate");
+                    *panicked = true;
+                }
+            }
+            for def in hir::all_items() {
+                let item = def.item().expect("`all_items` only returns local free items");
+                let item = serde_json::to_string(&item).unwrap();
+                for (plugin, panicked) in self.plugins.iter().zip(&mut panicked) {
+                    if *panicked {
+                        continue;
+                    }
+                    let data = HostData { lints: &plugin.lints, owner: Some(def.0) };
+                    // SAFETY: as above.
+                    if !unsafe {
+                        (plugin.plugin.check_item)(
+                            plugin.plugin.data,
+                            &data.host(),
+                            RawStr::new(&item),
+                        )
+                    } {
+                        report_panic(cx, plugin, &format!("`{}`", def.0.name()));
+                        *panicked = true;
+                    }
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+fn report_panic(cx: &LateContext<'_>, plugin: &LoadedPlugin, what: &str) {
+    cx.tcx.dcx().err(format!("lint plugin `{}` panicked while checking {what}", plugin.path));
+}
+
+/// The state behind the [`ffi::Host`] passed to a plugin.
+struct HostData<'a> {
+    lints: &'a [&'static Lint],
+    /// The item being checked, if any.
+    owner: Option<DefId>,
+}
+
+impl HostData<'_> {
+    fn host(&self) -> ffi::Host {
+        ffi::Host { data: (self as *const Self).cast(), emit: host_emit, body: host_body }
+    }
+}
+
+unsafe extern "C" fn host_emit(
+    data: *const c_void,
+    lint: u32,
+    node: *const RawNodeId,
+    span: usize,
+    message: RawStr,
+) {
+    // SAFETY: `data` is the `HostData` that the `ffi::Host` was created from.
+    let data = unsafe { &*data.cast::<HostData<'_>>() };
+    // `LintId`s can only be created by registering a lint, but a plugin may still pass one to
+    // the host of another plugin.
+    let Some(&lint) = data.lints.get(lint as usize) else { return };
+    let node = if node.is_null() {
+        data.owner.map(NodeId::of_owner)
+    } else {
+        // SAFETY: the plugin passes a valid pointer.
+        let RawNodeId { owner, local_id } = unsafe { *node };
+        Some(NodeId { owner: DefId::to_val(owner), local_id })
+    };
+    // SAFETY: the plugin passes a valid string.
+    let message = unsafe { message.as_str() }.to_owned();
+    with(|cx| cx.emit_lint(lint, node, Span::to_val(span), message))
+}
+
+unsafe extern "C" fn host_body(
+    _data: *const c_void,
+    def: usize,
+    out: *mut c_void,
+    write: unsafe extern "C" fn(*mut c_void, RawStr),
+) {
+    if let Some(body) = hir::body(DefId::to_val(def)) {
+        let body = serde_json::to_string(&body).unwrap();
+        // SAFETY: `write` and `out` are passed by the plugin for this purpose.
+        unsafe { write(out, RawStr::new(&body)) }
+    }
+}
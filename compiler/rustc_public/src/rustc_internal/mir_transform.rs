@@ -0,0 +1,497 @@
+//! Runs the passes registered through [`crate::mir::transform`] on the optimized MIR of the local
+//! crate, and writes the bodies they produce back into the compiler's MIR.
+
+use std::cell::RefCell;
+
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{self, OUTERMOST_SOURCE_SCOPE};
+use rustc_middle::ty::{InstanceKind, TyCtxt};
+use rustc_middle::util::Providers;
+use rustc_public_bridge::Tables;
+use rustc_public_bridge::context::CompilerCtxt;
+use rustc_span::{Spanned, Symbol};
+use tracing::debug;
+
+use super::with_bridge;
+use crate::compiler_interface::{BridgeTys, CompilerInterface, run_nested};
+use crate::mir::transform::registered_mir_passes;
+use crate::mir::visit::{Location, PlaceContext};
+use crate::mir::{
+    Body, Local, MirVisitor, Rvalue, Statement, StatementKind, Terminator, TerminatorKind,
+    UnwindAction, VarDebugInfo, VarDebugInfoContents,
+};
+use crate::unstable::{RustcInternal, Stable};
+use crate::{Error, error};
+
+/// Installs the providers that run the passes registered with
+/// [`register_mir_pass`](crate::mir::transform::register_mir_pass).
+///
+/// The [`run!`](crate::run) and [`run_with_tcx!`](crate::run_with_tcx) macros already do this.
+/// Custom drivers should call this from [`Config::override_queries`].
+///
+/// [`Config::override_queries`]: https://doc.rust-lang.org/nightly/nightly-rustc/rustc_interface/interface/struct.Config.html#structfield.override_queries
+pub fn provide(providers: &mut Providers) {
+    providers.hooks.run_external_mir_passes = run_external_mir_passes;
+}
+
+fn run_external_mir_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut mir::Body<'tcx>) -> bool {
+    let passes = registered_mir_passes();
+    if passes.is_empty() {
+        return false;
+    }
+    // Shims and promoted constants have no item that the passes could refer to.
+    let InstanceKind::Item(def_id) = body.source.instance else { return false };
+    if body.source.promoted.is_some() || !def_id.is_local() {
+        return false;
+    }
+    if let Some(construct) = unsupported_construct(body) {
+        debug!(?def_id, construct, "skipping external MIR passes");
+        return false;
+    }
+
+    // The driver may be using `rustc_public` while the compiler computes this body, so the
+    // passes get a context of their own.
+    let interface = CompilerInterface {
+        tables: RefCell::new(Tables::default()),
+        cx: RefCell::new(CompilerCtxt::new(tcx)),
+    };
+    let result: Result<bool, Error> = run_nested(&interface, || {
+        let (item, original) =
+            with_bridge(|tables, cx| (tables.crate_item(def_id), body.stable(tables, cx)));
+        let mut transformed = original.clone();
+        for pass in &passes {
+            pass.run_pass(item, &mut transformed);
+        }
+        if transformed == original {
+            return Ok(false);
+        }
+        check_body(&original, &transformed)?;
+        with_bridge(|tables, _| lower_body(tables, tcx, body, &original, &transformed))?;
+        Ok(true)
+    });
+
+    match result {
+        Ok(changed) => changed,
+        Err(err) => {
+            let names = passes.iter().map(|pass| pass.name()).collect::<Vec<_>>().join("`, `");
+            tcx.dcx().span_err(
+                tcx.def_span(def_id),
+                format!("MIR passes `{names}` produced a body that cannot be used: {err}"),
+            );
+            false
+        }
+    }
+}
+
+/// Returns the first construct in `body` that has no `rustc_public` representation.
+fn unsupported_construct(body: &mir::Body<'_>) -> Option<&'static str> {
+    struct Finder(Option<&'static str>);
+
+    impl<'tcx> Visitor<'tcx> for Finder {
+        fn visit_terminator(
+            &mut self,
+            terminator: &mir::Terminator<'tcx>,
+            location: mir::Location,
+        ) {
+            if let mir::TerminatorKind::TailCall { .. } = terminator.kind {
+                self.0.get_or_insert("tail call");
+            }
+            self.super_terminator(terminator, location);
+        }
+
+        fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: mir::Location) {
+            if let mir::Rvalue::WrapUnsafeBinder(..) = rvalue {
+                self.0.get_or_insert("unsafe binder");
+            }
+            self.super_rvalue(rvalue, location);
+        }
+
+        fn visit_projection_elem(
+            &mut self,
+            place_ref: mir::PlaceRef<'tcx>,
+            elem: mir::PlaceElem<'tcx>,
+            context: mir::visit::PlaceContext,
+            location: mir::Location,
+        ) {
+            if let mir::ProjectionElem::UnwrapUnsafeBinder(_) = elem {
+                self.0.get_or_insert("unsafe binder");
+            }
+            self.super_projection_elem(place_ref, elem, context, location);
+        }
+    }
+
+    let mut finder = Finder(None);
+    finder.visit_body(body);
+    finder.0
+}
+
+/// Checks the parts of `transformed` that the MIR validator relies on to be consistent.
+fn check_body(original: &Body, transformed: &Body) -> Result<(), Error> {
+    if transformed.arg_locals().len() != original.arg_locals().len()
+        || transformed.spread_arg() != original.spread_arg()
+    {
+        return Err(error!("the arguments of the body were changed"));
+    }
+    if transformed.locals().len() < original.locals().len() {
+        return Err(error!("locals were removed from the body"));
+    }
+    if transformed.blocks.is_empty() {
+        return Err(error!("the body has no basic blocks"));
+    }
+
+    struct IndexChecker {
+        locals: usize,
+        blocks: usize,
+        error: Option<Error>,
+    }
+
+    impl MirVisitor for IndexChecker {
+        fn visit_local(&mut self, local: &Local, _: PlaceContext, _: Location) {
+            if *local >= self.locals && self.error.is_none() {
+                self.error = Some(error!("local `_{local}` is not declared"));
+            }
+        }
+
+        fn visit_terminator(&mut self, term: &Terminator, location: Location) {
+            if let Some(target) = term.successors().into_iter().find(|bb| *bb >= self.blocks)
+                && self.error.is_none()
+            {
+                self.error = Some(error!("basic block `bb{target}` does not exist"));
+            }
+            self.super_terminator(term, location);
+        }
+    }
+
+    let mut checker = IndexChecker {
+        locals: transformed.locals().len(),
+        blocks: transformed.blocks.len(),
+        error: None,
+    };
+    checker.visit_body(transformed);
+    checker.error.map_or(Ok(()), Err)
+}
+
+/// Writes the changes from `original` to `transformed` into `body`.
+///
+/// The stable representation does not carry everything internal MIR needs, e.g. source scopes or
+/// call sources. Statements, terminators and locals that the passes left untouched are therefore
+/// kept as they are, and only what changed is lowered from `transformed`.
+fn lower_body<'tcx>(
+    tables: &mut Tables<'_, BridgeTys>,
+    tcx: TyCtxt<'tcx>,
+    body: &mut mir::Body<'tcx>,
+    original: &Body,
+    transformed: &Body,
+) -> Result<(), Error> {
+    let scopes = SourceScopes::new(body);
+
+    let mut blocks = Vec::with_capacity(transformed.blocks.len());
+    for (idx, block) in transformed.blocks.iter().enumerate() {
+        let bb = mir::BasicBlock::from_usize(idx);
+        let old = original.blocks.get(idx).map(|old| (old, &body.basic_blocks[bb]));
+        if let Some((old, internal)) = old
+            && old == block
+        {
+            blocks.push(internal.clone());
+            continue;
+        }
+
+        // Keep the statements that are still there, in order.
+        let mut statements = Vec::with_capacity(block.statements.len());
+        let mut next_old = 0;
+        for statement in &block.statements {
+            let reused = old.and_then(|(old, internal)| {
+                let pos = old.statements[next_old..].iter().position(|s| s == statement)?;
+                next_old += pos + 1;
+                Some(internal.statements[next_old - 1].clone())
+            });
+            match reused {
+                Some(statement) => statements.push(statement),
+                None => statements.push(lower_statement(tables, tcx, &scopes, statement)?),
+            }
+        }
+        let terminator = match old {
+            Some((old, internal)) if old.terminator == block.terminator => {
+                internal.terminator().clone()
+            }
+            _ => lower_terminator(tables, tcx, &scopes, &block.terminator)?,
+        };
+        blocks.push(mir::BasicBlockData::new_stmts(statements, Some(terminator), false));
+    }
+
+    // Blocks reachable from an unwind edge are cleanup blocks. Blocks that were cleanup blocks
+    // before stay so, even if they became unreachable.
+    let mut visited = vec![false; blocks.len()];
+    let mut worklist: Vec<_> = transformed
+        .blocks
+        .iter()
+        .filter_map(|block| match block.terminator.kind.unwind() {
+            Some(UnwindAction::Cleanup(bb)) => Some(*bb),
+            _ => None,
+        })
+        .collect();
+    while let Some(bb) = worklist.pop() {
+        if !visited[bb] {
+            visited[bb] = true;
+            blocks[bb].is_cleanup = true;
+            worklist.extend(transformed.blocks[bb].terminator.successors());
+        }
+    }
+    *body.basic_blocks_mut() = blocks.into_iter().collect();
+
+    for (local, decl) in transformed.locals().iter().enumerate() {
+        if original.locals().get(local) == Some(decl) {
+            continue;
+        }
+        let ty = decl.ty.internal(tables, tcx);
+        let span = decl.span.internal(tables, tcx);
+        let mutability = decl.mutability.internal(tables, tcx);
+        if let Some(internal) = body.local_decls.get_mut(mir::Local::from_usize(local)) {
+            internal.ty = ty;
+            internal.source_info.span = span;
+            internal.mutability = mutability;
+        } else {
+            let mut internal = mir::LocalDecl::with_source_info(ty, scopes.source_info(span));
+            internal.mutability = mutability;
+            body.local_decls.push(internal);
+        }
+    }
+
+    if transformed.var_debug_info != original.var_debug_info {
+        let mut var_debug_info = Vec::with_capacity(transformed.var_debug_info.len());
+        for (idx, info) in transformed.var_debug_info.iter().enumerate() {
+            if original.var_debug_info.get(idx) == Some(info) {
+                var_debug_info.push(body.var_debug_info[idx].clone());
+            } else {
+                var_debug_info.push(lower_var_debug_info(tables, tcx, body, info)?);
+            }
+        }
+        body.var_debug_info = var_debug_info;
+    }
+
+    Ok(())
+}
+
+/// Finds source scopes for new statements, by looking for a statement of the original body
+/// with the same span.
+struct SourceScopes {
+    source_infos: Vec<mir::SourceInfo>,
+}
+
+impl SourceScopes {
+    fn new(body: &mir::Body<'_>) -> Self {
+        let source_infos = body
+            .basic_blocks
+            .iter()
+            .flat_map(|block| {
+                block
+                    .statements
+                    .iter()
+                    .map(|statement| statement.source_info)
+                    .chain(block.terminator.as_ref().map(|terminator| terminator.source_info))
+            })
+            .collect();
+        SourceScopes { source_infos }
+    }
+
+    fn source_info(&self, span: rustc_span::Span) -> mir::SourceInfo {
+        self.source_infos
+            .iter()
+            .find(|info| info.span == span)
+            .copied()
+            .unwrap_or(mir::SourceInfo { span, scope: OUTERMOST_SOURCE_SCOPE })
+    }
+}
+
+fn lower_statement<'tcx>(
+    tables: &mut Tables<'_, BridgeTys>,
+    tcx: TyCtxt<'tcx>,
+    scopes: &SourceScopes,
+    statement: &Statement,
+) -> Result<mir::Statement<'tcx>, Error> {
+    let kind = match &statement.kind {
+        StatementKind::Assign(place, rvalue) => mir::StatementKind::Assign(Box::new((
+            place.internal(tables, tcx),
+            lower_rvalue(tables, tcx, rvalue)?,
+        ))),
+        StatementKind::SetDiscriminant { place, variant_index } => {
+            mir::StatementKind::SetDiscriminant {
+                place: Box::new(place.internal(tables, tcx)),
+                variant_index: variant_index.internal(tables, tcx),
+            }
+        }
+        StatementKind::StorageLive(local) => {
+            mir::StatementKind::StorageLive(mir::Local::from_usize(*local))
+        }
+        StatementKind::StorageDead(local) => {
+            mir::StatementKind::StorageDead(mir::Local::from_usize(*local))
+        }
+        StatementKind::Retag(kind, place) => mir::StatementKind::Retag(
+            kind.internal(tables, tcx),
+            Box::new(place.internal(tables, tcx)),
+        ),
+        StatementKind::PlaceMention(place) => {
+            mir::StatementKind::PlaceMention(Box::new(place.internal(tables, tcx)))
+        }
+        StatementKind::Intrinsic(intrinsic) => {
+            mir::StatementKind::Intrinsic(Box::new(intrinsic.internal(tables, tcx)))
+        }
+        StatementKind::ConstEvalCounter => mir::StatementKind::ConstEvalCounter,
+        StatementKind::Nop => mir::StatementKind::Nop,
+        StatementKind::FakeRead(..)
+        | StatementKind::AscribeUserType { .. }
+        | StatementKind::Coverage(_) => {
+            return Err(error!(
+                "statement `{:?}` cannot be added to optimized MIR",
+                statement.kind
+            ));
+        }
+    };
+    Ok(mir::Statement::new(scopes.source_info(statement.span.internal(tables, tcx)), kind))
+}
+
+fn lower_rvalue<'tcx>(
+    tables: &mut Tables<'_, BridgeTys>,
+    tcx: TyCtxt<'tcx>,
+    rvalue: &Rvalue,
+) -> Result<mir::Rvalue<'tcx>, Error> {
+    let rvalue = match rvalue {
+        Rvalue::AddressOf(kind, place) => {
+            mir::Rvalue::RawPtr(kind.internal(tables, tcx), place.internal(tables, tcx))
+        }
+        Rvalue::Aggregate(kind, operands) => mir::Rvalue::Aggregate(
+            Box::new(kind.internal(tables, tcx)),
+            operands.iter().map(|operand| operand.internal(tables, tcx)).collect(),
+        ),
+        Rvalue::BinaryOp(op, lhs, rhs) => mir::Rvalue::BinaryOp(
+            op.internal(tables, tcx),
+            Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+        ),
+        Rvalue::Cast(kind, operand, ty) => mir::Rvalue::Cast(
+            kind.internal(tables, tcx),
+            operand.internal(tables, tcx),
+            ty.internal(tables, tcx),
+        ),
+        Rvalue::CheckedBinaryOp(op, lhs, rhs) => {
+            let Some(op) = op.internal(tables, tcx).wrapping_to_overflowing() else {
+                return Err(error!("`{op:?}` cannot overflow"));
+            };
+            mir::Rvalue::BinaryOp(
+                op,
+                Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+            )
+        }
+        Rvalue::CopyForDeref(place) => mir::Rvalue::CopyForDeref(place.internal(tables, tcx)),
+        Rvalue::Discriminant(place) => mir::Rvalue::Discriminant(place.internal(tables, tcx)),
+        Rvalue::Len(_) => {
+            return Err(error!("`Rvalue::Len` is not supported, use `PtrMetadata` instead"));
+        }
+        Rvalue::Ref(region, kind, place) => mir::Rvalue::Ref(
+            region.internal(tables, tcx),
+            kind.internal(tables, tcx),
+            place.internal(tables, tcx),
+        ),
+        Rvalue::Repeat(operand, count) => {
+            mir::Rvalue::Repeat(operand.internal(tables, tcx), count.internal(tables, tcx))
+        }
+        Rvalue::ThreadLocalRef(item) => mir::Rvalue::ThreadLocalRef(item.internal(tables, tcx)),
+        Rvalue::UnaryOp(op, operand) => {
+            mir::Rvalue::UnaryOp(op.internal(tables, tcx), operand.internal(tables, tcx))
+        }
+        Rvalue::Use(operand) => mir::Rvalue::Use(operand.internal(tables, tcx)),
+    };
+    Ok(rvalue)
+}
+
+fn lower_terminator<'tcx>(
+    tables: &mut Tables<'_, BridgeTys>,
+    tcx: TyCtxt<'tcx>,
+    scopes: &SourceScopes,
+    terminator: &Terminator,
+) -> Result<mir::Terminator<'tcx>, Error> {
+    let span = terminator.span.internal(tables, tcx);
+    let bb = mir::BasicBlock::from_usize;
+    let kind = match &terminator.kind {
+        TerminatorKind::Goto { target } => mir::TerminatorKind::Goto { target: bb(*target) },
+        TerminatorKind::SwitchInt { discr, targets } => mir::TerminatorKind::SwitchInt {
+            discr: discr.internal(tables, tcx),
+            targets: mir::SwitchTargets::new(
+                targets.branches().map(|(value, target)| (value, bb(target))),
+                bb(targets.otherwise()),
+            ),
+        },
+        TerminatorKind::Resume => mir::TerminatorKind::UnwindResume,
+        TerminatorKind::Abort => {
+            mir::TerminatorKind::UnwindTerminate(mir::UnwindTerminateReason::Abi)
+        }
+        TerminatorKind::Return => mir::TerminatorKind::Return,
+        TerminatorKind::Unreachable => mir::TerminatorKind::Unreachable,
+        TerminatorKind::Drop { place, target, unwind } => mir::TerminatorKind::Drop {
+            place: place.internal(tables, tcx),
+            target: bb(*target),
+            unwind: unwind.internal(tables, tcx),
+            replace: false,
+            drop: None,
+            async_fut: None,
+        },
+        TerminatorKind::Call { func, args, destination, target, unwind } => {
+            mir::TerminatorKind::Call {
+                func: func.internal(tables, tcx),
+                args: args
+                    .iter()
+                    .map(|arg| Spanned { node: arg.internal(tables, tcx), span })
+                    .collect(),
+                destination: destination.internal(tables, tcx),
+                target: target.map(bb),
+                unwind: unwind.internal(tables, tcx),
+                call_source: mir::CallSource::Misc,
+                fn_span: span,
+            }
+        }
+        TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
+            mir::TerminatorKind::Assert {
+                cond: cond.internal(tables, tcx),
+                expected: *expected,
+                msg: Box::new(msg.internal(tables, tcx)),
+                target: bb(*target),
+                unwind: unwind.internal(tables, tcx),
+            }
+        }
+        TerminatorKind::InlineAsm { .. } => {
+            return Err(error!("inline assembly cannot be added to optimized MIR"));
+        }
+    };
+    Ok(mir::Terminator { source_info: scopes.source_info(span), kind })
+}
+
+fn lower_var_debug_info<'tcx>(
+    tables: &mut Tables<'_, BridgeTys>,
+    tcx: TyCtxt<'tcx>,
+    body: &mir::Body<'tcx>,
+    info: &VarDebugInfo,
+) -> Result<mir::VarDebugInfo<'tcx>, Error> {
+    let scope = mir::SourceScope::from_u32(info.source_info.scope);
+    if body.source_scopes.get(scope).is_none() {
+        return Err(error!("source scope `{}` does not exist", info.source_info.scope));
+    }
+    let value = match &info.value {
+        VarDebugInfoContents::Place(place) => {
+            mir::VarDebugInfoContents::Place(place.internal(tables, tcx))
+        }
+        VarDebugInfoContents::Const(constant) => {
+            mir::VarDebugInfoContents::Const(constant.internal(tables, tcx))
+        }
+    };
+    Ok(mir::VarDebugInfo {
+        name: Symbol::intern(&info.name),
+        source_info: mir::SourceInfo { span: info.source_info.span.internal(tables, tcx), scope },
+        composite: info.composite.as_ref().map(|composite| {
+            Box::new(mir::VarDebugInfoFragment {
+                ty: composite.ty.internal(tables, tcx),
+                projection: composite.projection.internal(tables, tcx),
+            })
+        }),
+        value,
+        argument_index: info.argument_index,
+    })
+}
@@ -14,6 +14,8 @@
 use crate::compiler_interface::{BridgeTys, CompilerInterface, with};
 use crate::unstable::{RustcInternal, Stable};
 
+pub mod lint_plugin;
+pub mod mir_transform;
 pub mod pretty;
 
 /// Convert an internal Rust compiler item into its stable counterpart, if one exists.
@@ -226,6 +228,14 @@ impl<B, C, F> Callbacks for RustcPublic<B, C, F>
             C: Send,
             F: FnOnce($($crate::optional!($with_tcx TyCtxt))?) -> ControlFlow<B, C> + Send,
         {
+            /// Installs the providers that run the MIR passes registered with
+            /// `rustc_public::mir::transform::register_mir_pass`.
+            fn config(&mut self, config: &mut interface::Config) {
+                config.override_queries = Some(|_, providers| {
+                    rustc_internal::mir_transform::provide(providers);
+                });
+            }
+
             /// Called after analysis. Return value instructs the compiler whether to
             /// continue the compilation afterwards (defaults to `Compilation::Continue`)
             fn after_analysis<'tcx>(
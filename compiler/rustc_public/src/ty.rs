@@ -8,6 +8,7 @@
 use super::{DefId, Error, Symbol, with};
 use crate::abi::{FnAbi, Layout};
 use crate::crate_def::{CrateDef, CrateDefType};
+use crate::hir::Expansion;
 use crate::mir::alloc::{AllocId, read_target_int, read_target_uint};
 use crate::mir::mono::{Instance, StaticDef};
 use crate::target::MachineInfo;
@@ -285,6 +286,20 @@ pub fn get_lines(&self) -> LineInfo {
     pub fn diagnostic(&self) -> String {
         with(|c| c.span_to_string(*self))
     }
+
+    /// Return the source code of this `Span`, if it is available.
+    pub fn snippet(&self) -> Option<String> {
+        with(|c| c.span_to_snippet(self))
+    }
+
+    /// Return the macro expansion or compiler desugaring this `Span` was produced by, or `None`
+    /// if it comes directly from the source code.
+    ///
+    /// The expansion that the macro invocation itself comes from, if any, can be retrieved from
+    /// the call site of the returned expansion.
+    pub fn expansion(&self) -> Option<Expansion> {
+        with(|c| c.span_expansion(self))
+    }
 }
 
 #[derive(Clone, Copy, Debug, Serialize)]
@@ -1589,6 +1604,20 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         }
     };
 }
+macro_rules! deserialize_index_impl {
+    ($name:ident) => {
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let n: usize = ::serde::Deserialize::deserialize(deserializer)?;
+                Ok(<$name as $crate::IndexedVal>::to_val(n))
+            }
+        }
+    };
+}
+pub(crate) use deserialize_index_impl;
 pub(crate) use index_impl;
 pub(crate) use serialize_index_impl;
 
@@ -1597,6 +1626,10 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 index_impl!(Ty);
 index_impl!(Span);
 
+// Lint plugins receive the HIR of the items they check in serialized form, see `lint::ffi`.
+deserialize_index_impl!(Ty);
+deserialize_index_impl!(Span);
+
 /// The source-order index of a variant in a type.
 ///
 /// For example, in the following types,
@@ -0,0 +1,671 @@
+//! Conversion of internal Rust compiler `hir` items to stable ones.
+
+use rustc_hir as hir;
+use rustc_hir::HirId;
+use rustc_hir::def::Res;
+use rustc_public_bridge::Tables;
+use rustc_public_bridge::context::CompilerCtxt;
+use rustc_span::hygiene::{ExpnData, ExpnKind, MacroKind};
+
+use crate::compiler_interface::BridgeTys;
+use crate::hir::{BindingId, ItemDef, NodeId};
+use crate::unstable::Stable;
+
+impl<'tcx> Stable<'tcx> for hir::Item<'tcx> {
+    type T = crate::hir::Item;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use crate::hir::ItemKind;
+        let def_id = self.owner_id.to_def_id();
+        let kind = match self.kind {
+            hir::ItemKind::ExternCrate(orig_name, _) => {
+                ItemKind::ExternCrate { orig_name: orig_name.map(|name| name.to_string()) }
+            }
+            hir::ItemKind::Use(path, kind) => ItemKind::Use(crate::hir::UseTree {
+                path: path.segments.stable(tables, cx),
+                kind: match kind {
+                    hir::UseKind::Single(ident) => crate::hir::UseKind::Single(ident.to_string()),
+                    hir::UseKind::Glob => crate::hir::UseKind::Glob,
+                    hir::UseKind::ListStem => crate::hir::UseKind::ListStem,
+                },
+                res: path.res.present_items().map(|res| res.stable(tables, cx)).collect(),
+                span: path.span.stable(tables, cx),
+            }),
+            hir::ItemKind::Static(mutability, ..) => {
+                ItemKind::Static { mutability: mutability.stable(tables, cx) }
+            }
+            hir::ItemKind::Const(..) => ItemKind::Const,
+            hir::ItemKind::Fn { has_body, .. } => ItemKind::Fn { has_body },
+            hir::ItemKind::Macro(..) => ItemKind::Macro,
+            hir::ItemKind::Mod(_, module) => ItemKind::Mod {
+                items: module
+                    .item_ids
+                    .iter()
+                    .map(|id| ItemDef(tables.create_def_id(id.owner_id.to_def_id())))
+                    .collect(),
+            },
+            hir::ItemKind::ForeignMod { items, .. } => ItemKind::ForeignMod {
+                items: items
+                    .iter()
+                    .map(|id| tables.create_def_id(id.owner_id.to_def_id()))
+                    .collect(),
+            },
+            hir::ItemKind::GlobalAsm { .. } => ItemKind::GlobalAsm,
+            hir::ItemKind::TyAlias(..) => ItemKind::TyAlias,
+            hir::ItemKind::Enum(..) => ItemKind::Enum,
+            hir::ItemKind::Struct(..) => ItemKind::Struct,
+            hir::ItemKind::Union(..) => ItemKind::Union,
+            hir::ItemKind::Trait(.., items) => ItemKind::Trait {
+                items: items
+                    .iter()
+                    .map(|id| tables.create_def_id(id.owner_id.to_def_id()))
+                    .collect(),
+            },
+            hir::ItemKind::TraitAlias(..) => ItemKind::TraitAlias,
+            hir::ItemKind::Impl(hir::Impl { of_trait, items, .. }) => ItemKind::Impl {
+                of_trait: of_trait.is_some(),
+                items: items
+                    .iter()
+                    .map(|id| tables.create_def_id(id.owner_id.to_def_id()))
+                    .collect(),
+            },
+        };
+        crate::hir::Item {
+            def: ItemDef(tables.create_def_id(def_id)),
+            name: self.kind.ident().map(|ident| ident.to_string()),
+            kind,
+            vis: visibility_from_source(&cx.visibility_as_written(def_id, self.vis_span)),
+            vis_span: (!self.vis_span.is_empty()).then(|| self.vis_span.stable(tables, cx)),
+            attrs: cx.all_attrs(def_id).stable(tables, cx),
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+/// Parses a visibility as written, e.g., `pub(in crate::a)`.
+fn visibility_from_source(text: &str) -> crate::hir::Visibility {
+    use crate::hir::Visibility;
+    let Some(restriction) = text.strip_prefix("pub") else {
+        return Visibility::Inherited;
+    };
+    let Some(restriction) =
+        restriction.trim().strip_prefix('(').and_then(|restriction| restriction.strip_suffix(')'))
+    else {
+        return Visibility::Public;
+    };
+    match restriction.trim() {
+        "crate" => Visibility::Crate,
+        "super" => Visibility::Super,
+        "self" => Visibility::SelfModule,
+        path => Visibility::InPath(path.strip_prefix("in").unwrap_or(path).trim().to_string()),
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Attribute {
+    type T = crate::hir::Attribute;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use rustc_hir::attrs::AttributeKind;
+        let (path, doc, span) = match self {
+            hir::Attribute::Unparsed(item) => (
+                Some(item.path.segments.iter().map(|segment| segment.to_string()).collect()),
+                None,
+                Some(item.span),
+            ),
+            hir::Attribute::Parsed(AttributeKind::DocComment { comment, span, .. }) => {
+                (None, Some(comment.to_string()), Some(*span))
+            }
+            hir::Attribute::Parsed(_) => (None, None, None),
+        };
+        crate::hir::Attribute {
+            path,
+            text: cx.attribute_to_string(self),
+            doc,
+            span: span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::PathSegment<'tcx> {
+    type T = crate::hir::PathSegment;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        crate::hir::PathSegment {
+            name: self.ident.to_string(),
+            span: self.ident.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Res {
+    type T = crate::hir::Res;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        _: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use crate::hir::Res as StableRes;
+        match *self {
+            Res::Def(_, def_id) => StableRes::Def(tables.create_def_id(def_id)),
+            Res::Local(hir_id) => StableRes::Local(binding_id(hir_id)),
+            Res::PrimTy(ty) => StableRes::PrimTy(ty.name_str().to_string()),
+            Res::SelfTyParam { .. } | Res::SelfTyAlias { .. } => StableRes::SelfTy,
+            Res::SelfCtor(def_id) => StableRes::SelfCtor(tables.create_def_id(def_id)),
+            Res::ToolMod | Res::NonMacroAttr(_) | Res::OpenMod(_) | Res::Err => StableRes::Other,
+        }
+    }
+}
+
+fn binding_id(hir_id: HirId) -> BindingId {
+    BindingId(hir_id.local_id.as_usize())
+}
+
+fn node_id(hir_id: HirId, tables: &mut Tables<'_, BridgeTys>) -> NodeId {
+    NodeId {
+        owner: tables.create_def_id(hir_id.owner.to_def_id()),
+        local_id: hir_id.local_id.as_u32(),
+    }
+}
+
+/// Converts a path in an expression or a pattern, resolving type-relative paths with the
+/// results of type checking the node `hir_id`.
+fn qpath_stable<'cx>(
+    qpath: &hir::QPath<'_>,
+    hir_id: HirId,
+    tables: &mut Tables<'cx, BridgeTys>,
+    cx: &CompilerCtxt<'cx, BridgeTys>,
+) -> crate::hir::Path {
+    match qpath {
+        hir::QPath::Resolved(qself, path) => crate::hir::Path {
+            qself: qself.map(|ty| ty.span.stable(tables, cx)),
+            segments: path.segments.stable(tables, cx),
+            res: path.res.stable(tables, cx),
+            span: path.span.stable(tables, cx),
+        },
+        hir::QPath::TypeRelative(qself, segment) => crate::hir::Path {
+            qself: Some(qself.span.stable(tables, cx)),
+            segments: vec![segment.stable(tables, cx)],
+            res: cx.hir_qpath_res(qpath, hir_id).stable(tables, cx),
+            span: qself.span.to(segment.ident.span).stable(tables, cx),
+        },
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Body<'tcx> {
+    type T = crate::hir::Body;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        crate::hir::Body {
+            params: self
+                .params
+                .iter()
+                .map(|param| crate::hir::Param {
+                    id: node_id(param.hir_id, tables),
+                    pat: param.pat.stable(tables, cx),
+                    span: param.span.stable(tables, cx),
+                })
+                .collect(),
+            value: self.value.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Expr<'tcx> {
+    type T = crate::hir::Expr;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use crate::hir::ExprKind;
+        let label = |label: Option<rustc_ast::Label>| label.map(|label| label.ident.to_string());
+        let kind = match self.kind {
+            hir::ExprKind::ConstBlock(block) => {
+                ExprKind::ConstBlock(tables.create_def_id(block.def_id.to_def_id()))
+            }
+            hir::ExprKind::Array(exprs) => ExprKind::Array(exprs.stable(tables, cx)),
+            hir::ExprKind::Call(func, args) => {
+                ExprKind::Call(Box::new(func.stable(tables, cx)), args.stable(tables, cx))
+            }
+            hir::ExprKind::MethodCall(segment, receiver, args, _) => ExprKind::MethodCall {
+                receiver: Box::new(receiver.stable(tables, cx)),
+                method: segment.stable(tables, cx),
+                args: args.stable(tables, cx),
+                callee: cx
+                    .hir_type_dependent_def(self.hir_id)
+                    .map(|def_id| tables.create_def_id(def_id)),
+            },
+            hir::ExprKind::Use(expr, _) => ExprKind::Use(Box::new(expr.stable(tables, cx))),
+            hir::ExprKind::Tup(exprs) => ExprKind::Tuple(exprs.stable(tables, cx)),
+            hir::ExprKind::Binary(op, lhs, rhs) => ExprKind::Binary(
+                op.node.stable(tables, cx),
+                Box::new(lhs.stable(tables, cx)),
+                Box::new(rhs.stable(tables, cx)),
+            ),
+            hir::ExprKind::Unary(op, expr) => {
+                ExprKind::Unary(op.stable(tables, cx), Box::new(expr.stable(tables, cx)))
+            }
+            hir::ExprKind::Lit(lit) => ExprKind::Lit(lit.stable(tables, cx)),
+            hir::ExprKind::Cast(expr, ty) => {
+                ExprKind::Cast(Box::new(expr.stable(tables, cx)), ty.span.stable(tables, cx))
+            }
+            hir::ExprKind::Type(expr, ty) => {
+                ExprKind::Type(Box::new(expr.stable(tables, cx)), ty.span.stable(tables, cx))
+            }
+            hir::ExprKind::DropTemps(expr) => {
+                ExprKind::DropTemps(Box::new(expr.stable(tables, cx)))
+            }
+            hir::ExprKind::Let(let_expr) => ExprKind::Let {
+                pat: let_expr.pat.stable(tables, cx),
+                init: Box::new(let_expr.init.stable(tables, cx)),
+            },
+            hir::ExprKind::If(cond, then, els) => ExprKind::If {
+                cond: Box::new(cond.stable(tables, cx)),
+                then: Box::new(then.stable(tables, cx)),
+                els: els.map(|els| Box::new(els.stable(tables, cx))),
+            },
+            hir::ExprKind::Loop(block, loop_label, source, _) => ExprKind::Loop {
+                body: block.stable(tables, cx),
+                label: label(loop_label),
+                source: match source {
+                    hir::LoopSource::Loop => crate::hir::LoopSource::Loop,
+                    hir::LoopSource::While => crate::hir::LoopSource::While,
+                    hir::LoopSource::ForLoop => crate::hir::LoopSource::ForLoop,
+                },
+            },
+            hir::ExprKind::Match(scrutinee, arms, source) => ExprKind::Match {
+                scrutinee: Box::new(scrutinee.stable(tables, cx)),
+                arms: arms.stable(tables, cx),
+                source: match source {
+                    hir::MatchSource::Normal => crate::hir::MatchSource::Normal,
+                    hir::MatchSource::Postfix => crate::hir::MatchSource::Postfix,
+                    hir::MatchSource::ForLoopDesugar => crate::hir::MatchSource::ForLoopDesugar,
+                    hir::MatchSource::TryDesugar(_) => crate::hir::MatchSource::TryDesugar,
+                    hir::MatchSource::AwaitDesugar => crate::hir::MatchSource::AwaitDesugar,
+                    hir::MatchSource::FormatArgs => crate::hir::MatchSource::FormatArgs,
+                },
+            },
+            hir::ExprKind::Closure(closure) => ExprKind::Closure {
+                def: tables.create_def_id(closure.def_id.to_def_id()),
+                is_move: matches!(closure.capture_clause, hir::CaptureBy::Value { .. }),
+            },
+            hir::ExprKind::Block(block, block_label) => {
+                ExprKind::Block(block.stable(tables, cx), label(block_label))
+            }
+            hir::ExprKind::Assign(lhs, rhs, _) => {
+                ExprKind::Assign(Box::new(lhs.stable(tables, cx)), Box::new(rhs.stable(tables, cx)))
+            }
+            hir::ExprKind::AssignOp(op, lhs, rhs) => ExprKind::AssignOp(
+                op.node.stable(tables, cx),
+                Box::new(lhs.stable(tables, cx)),
+                Box::new(rhs.stable(tables, cx)),
+            ),
+            hir::ExprKind::Field(expr, ident) => {
+                ExprKind::Field(Box::new(expr.stable(tables, cx)), ident.to_string())
+            }
+            hir::ExprKind::Index(expr, index, _) => ExprKind::Index(
+                Box::new(expr.stable(tables, cx)),
+                Box::new(index.stable(tables, cx)),
+            ),
+            hir::ExprKind::Path(ref qpath) => {
+                ExprKind::Path(qpath_stable(qpath, self.hir_id, tables, cx))
+            }
+            hir::ExprKind::AddrOf(kind, mutability, expr) => ExprKind::AddrOf {
+                kind: match kind {
+                    hir::BorrowKind::Ref => crate::hir::BorrowKind::Ref,
+                    hir::BorrowKind::Raw => crate::hir::BorrowKind::Raw,
+                    hir::BorrowKind::Pin => crate::hir::BorrowKind::Pin,
+                },
+                mutability: mutability.stable(tables, cx),
+                expr: Box::new(expr.stable(tables, cx)),
+            },
+            hir::ExprKind::Break(destination, value) => ExprKind::Break {
+                label: label(destination.label),
+                value: value.map(|value| Box::new(value.stable(tables, cx))),
+            },
+            hir::ExprKind::Continue(destination) => {
+                ExprKind::Continue { label: label(destination.label) }
+            }
+            hir::ExprKind::Ret(value) => {
+                ExprKind::Return(value.map(|value| Box::new(value.stable(tables, cx))))
+            }
+            hir::ExprKind::Become(expr) => ExprKind::Become(Box::new(expr.stable(tables, cx))),
+            hir::ExprKind::InlineAsm(_) => ExprKind::InlineAsm,
+            hir::ExprKind::OffsetOf(container, fields) => ExprKind::OffsetOf {
+                container: container.span.stable(tables, cx),
+                fields: fields.iter().map(|field| field.to_string()).collect(),
+            },
+            hir::ExprKind::Struct(qpath, fields, tail) => ExprKind::Struct {
+                path: qpath_stable(qpath, self.hir_id, tables, cx),
+                fields: fields.stable(tables, cx),
+                base: match tail {
+                    hir::StructTailExpr::Base(base) => Some(Box::new(base.stable(tables, cx))),
+                    hir::StructTailExpr::None
+                    | hir::StructTailExpr::DefaultFields(_)
+                    | hir::StructTailExpr::NoneWithError(_) => None,
+                },
+            },
+            hir::ExprKind::Repeat(value, count) => {
+                ExprKind::Repeat(Box::new(value.stable(tables, cx)), count.span.stable(tables, cx))
+            }
+            hir::ExprKind::Yield(value, _) => ExprKind::Yield(Box::new(value.stable(tables, cx))),
+            hir::ExprKind::UnsafeBinderCast(_, expr, _) => {
+                ExprKind::UnsafeBinderCast(Box::new(expr.stable(tables, cx)))
+            }
+            hir::ExprKind::Err(_) => ExprKind::Err,
+        };
+        crate::hir::Expr {
+            id: node_id(self.hir_id, tables),
+            kind,
+            ty: cx.hir_node_ty(self.hir_id).map(|ty| ty.stable(tables, cx)),
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::ExprField<'tcx> {
+    type T = crate::hir::ExprField;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        crate::hir::ExprField {
+            name: self.ident.to_string(),
+            expr: self.expr.stable(tables, cx),
+            is_shorthand: self.is_shorthand,
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Block<'tcx> {
+    type T = crate::hir::Block;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        crate::hir::Block {
+            stmts: self.stmts.stable(tables, cx),
+            expr: self.expr.map(|expr| Box::new(expr.stable(tables, cx))),
+            is_unsafe: matches!(
+                self.rules,
+                hir::BlockCheckMode::UnsafeBlock(hir::UnsafeSource::UserProvided)
+            ),
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Stmt<'tcx> {
+    type T = crate::hir::Stmt;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use crate::hir::StmtKind;
+        let kind = match self.kind {
+            hir::StmtKind::Let(local) => StmtKind::Let(crate::hir::LetStmt {
+                pat: local.pat.stable(tables, cx),
+                ty: local.ty.map(|ty| ty.span.stable(tables, cx)),
+                init: local.init.stable(tables, cx),
+                els: local.els.stable(tables, cx),
+            }),
+            hir::StmtKind::Item(id) => {
+                StmtKind::Item(ItemDef(tables.create_def_id(id.owner_id.to_def_id())))
+            }
+            hir::StmtKind::Expr(expr) => StmtKind::Expr(expr.stable(tables, cx)),
+            hir::StmtKind::Semi(expr) => StmtKind::Semi(expr.stable(tables, cx)),
+        };
+        crate::hir::Stmt {
+            id: node_id(self.hir_id, tables),
+            kind,
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Arm<'tcx> {
+    type T = crate::hir::Arm;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        crate::hir::Arm {
+            id: node_id(self.hir_id, tables),
+            pat: self.pat.stable(tables, cx),
+            guard: self.guard.stable(tables, cx),
+            body: self.body.stable(tables, cx),
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Pat<'tcx> {
+    type T = crate::hir::Pat;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use crate::hir::PatKind;
+        let kind = match self.kind {
+            hir::PatKind::Missing | hir::PatKind::Err(_) => PatKind::Err,
+            hir::PatKind::Wild => PatKind::Wild,
+            hir::PatKind::Binding(hir::BindingMode(by_ref, mutability), hir_id, ident, subpat) => {
+                PatKind::Binding {
+                    id: binding_id(hir_id),
+                    name: ident.to_string(),
+                    by_ref: match by_ref {
+                        hir::ByRef::Yes(_, mutability) => Some(mutability.stable(tables, cx)),
+                        hir::ByRef::No => None,
+                    },
+                    mutability: mutability.stable(tables, cx),
+                    subpat: subpat.map(|subpat| Box::new(subpat.stable(tables, cx))),
+                }
+            }
+            hir::PatKind::Struct(ref qpath, fields, rest) => PatKind::Struct {
+                path: qpath_stable(qpath, self.hir_id, tables, cx),
+                fields: fields.stable(tables, cx),
+                has_rest: rest.is_some(),
+            },
+            hir::PatKind::TupleStruct(ref qpath, elems, rest) => PatKind::TupleStruct {
+                path: qpath_stable(qpath, self.hir_id, tables, cx),
+                elems: elems.stable(tables, cx),
+                rest: rest.as_opt_usize(),
+            },
+            hir::PatKind::Or(pats) => PatKind::Or(pats.stable(tables, cx)),
+            hir::PatKind::Never => PatKind::Never,
+            hir::PatKind::Tuple(elems, rest) => {
+                PatKind::Tuple(elems.stable(tables, cx), rest.as_opt_usize())
+            }
+            hir::PatKind::Box(pat) => PatKind::Box(Box::new(pat.stable(tables, cx))),
+            hir::PatKind::Deref(pat) => PatKind::Deref(Box::new(pat.stable(tables, cx))),
+            hir::PatKind::Ref(pat, _, mutability) => {
+                PatKind::Ref(Box::new(pat.stable(tables, cx)), mutability.stable(tables, cx))
+            }
+            hir::PatKind::Expr(expr) => PatKind::Expr(expr.stable(tables, cx)),
+            hir::PatKind::Guard(pat, guard) => {
+                PatKind::Guard(Box::new(pat.stable(tables, cx)), Box::new(guard.stable(tables, cx)))
+            }
+            hir::PatKind::Range(start, end, range_end) => PatKind::Range {
+                start: start.stable(tables, cx),
+                end: end.stable(tables, cx),
+                inclusive: matches!(range_end, hir::RangeEnd::Included),
+            },
+            hir::PatKind::Slice(before, middle, after) => PatKind::Slice {
+                before: before.stable(tables, cx),
+                middle: middle.map(|middle| Box::new(middle.stable(tables, cx))),
+                after: after.stable(tables, cx),
+            },
+        };
+        crate::hir::Pat {
+            kind,
+            ty: cx.hir_node_ty(self.hir_id).map(|ty| ty.stable(tables, cx)),
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::PatField<'tcx> {
+    type T = crate::hir::PatField;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        crate::hir::PatField {
+            name: self.ident.to_string(),
+            pat: self.pat.stable(tables, cx),
+            is_shorthand: self.is_shorthand,
+            span: self.span.stable(tables, cx),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::PatExpr<'tcx> {
+    type T = crate::hir::PatExpr;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        match self.kind {
+            hir::PatExprKind::Lit { lit, negated } => {
+                crate::hir::PatExpr::Lit { lit: lit.stable(tables, cx), negated }
+            }
+            hir::PatExprKind::Path(ref qpath) => {
+                crate::hir::PatExpr::Path(qpath_stable(qpath, self.hir_id, tables, cx))
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::Lit {
+    type T = crate::hir::Lit;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use rustc_ast::LitKind as InternalLitKind;
+
+        use crate::hir::LitKind;
+        let kind = match self.node {
+            InternalLitKind::Str(symbol, _) => LitKind::Str(symbol.to_string()),
+            InternalLitKind::ByteStr(ref bytes, _) => {
+                LitKind::ByteStr(bytes.as_byte_str().to_vec())
+            }
+            InternalLitKind::CStr(ref bytes, _) => LitKind::CStr(bytes.as_byte_str().to_vec()),
+            InternalLitKind::Byte(byte) => LitKind::Byte(byte),
+            InternalLitKind::Char(c) => LitKind::Char(c),
+            InternalLitKind::Int(value, _) => LitKind::Int(value.0),
+            InternalLitKind::Float(symbol, _) => LitKind::Float(symbol.to_string()),
+            InternalLitKind::Bool(value) => LitKind::Bool(value),
+            InternalLitKind::Err(_) => LitKind::Err,
+        };
+        crate::hir::Lit { kind, span: self.span.stable(tables, cx) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::BinOpKind {
+    type T = crate::hir::BinOp;
+    fn stable(&self, _: &mut Tables<'_, BridgeTys>, _: &CompilerCtxt<'_, BridgeTys>) -> Self::T {
+        use crate::hir::BinOp;
+        match self {
+            hir::BinOpKind::Add => BinOp::Add,
+            hir::BinOpKind::Sub => BinOp::Sub,
+            hir::BinOpKind::Mul => BinOp::Mul,
+            hir::BinOpKind::Div => BinOp::Div,
+            hir::BinOpKind::Rem => BinOp::Rem,
+            hir::BinOpKind::And => BinOp::And,
+            hir::BinOpKind::Or => BinOp::Or,
+            hir::BinOpKind::BitXor => BinOp::BitXor,
+            hir::BinOpKind::BitAnd => BinOp::BitAnd,
+            hir::BinOpKind::BitOr => BinOp::BitOr,
+            hir::BinOpKind::Shl => BinOp::Shl,
+            hir::BinOpKind::Shr => BinOp::Shr,
+            hir::BinOpKind::Eq => BinOp::Eq,
+            hir::BinOpKind::Lt => BinOp::Lt,
+            hir::BinOpKind::Le => BinOp::Le,
+            hir::BinOpKind::Ne => BinOp::Ne,
+            hir::BinOpKind::Ge => BinOp::Ge,
+            hir::BinOpKind::Gt => BinOp::Gt,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::AssignOpKind {
+    type T = crate::hir::BinOp;
+    fn stable(&self, _: &mut Tables<'_, BridgeTys>, _: &CompilerCtxt<'_, BridgeTys>) -> Self::T {
+        use crate::hir::BinOp;
+        match self {
+            hir::AssignOpKind::AddAssign => BinOp::Add,
+            hir::AssignOpKind::SubAssign => BinOp::Sub,
+            hir::AssignOpKind::MulAssign => BinOp::Mul,
+            hir::AssignOpKind::DivAssign => BinOp::Div,
+            hir::AssignOpKind::RemAssign => BinOp::Rem,
+            hir::AssignOpKind::BitXorAssign => BinOp::BitXor,
+            hir::AssignOpKind::BitAndAssign => BinOp::BitAnd,
+            hir::AssignOpKind::BitOrAssign => BinOp::BitOr,
+            hir::AssignOpKind::ShlAssign => BinOp::Shl,
+            hir::AssignOpKind::ShrAssign => BinOp::Shr,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for hir::UnOp {
+    type T = crate::hir::UnOp;
+    fn stable(&self, _: &mut Tables<'_, BridgeTys>, _: &CompilerCtxt<'_, BridgeTys>) -> Self::T {
+        match self {
+            hir::UnOp::Deref => crate::hir::UnOp::Deref,
+            hir::UnOp::Not => crate::hir::UnOp::Not,
+            hir::UnOp::Neg => crate::hir::UnOp::Neg,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ExpnData {
+    type T = crate::hir::Expansion;
+    fn stable<'cx>(
+        &self,
+        tables: &mut Tables<'cx, BridgeTys>,
+        cx: &CompilerCtxt<'cx, BridgeTys>,
+    ) -> Self::T {
+        use crate::hir::ExpansionKind;
+        let kind = match self.kind {
+            ExpnKind::Root => unreachable!("the root expansion has no stable representation"),
+            ExpnKind::Macro(kind, name) => {
+                let kind = match kind {
+                    MacroKind::Bang => crate::hir::MacroKind::Bang,
+                    MacroKind::Attr => crate::hir::MacroKind::Attr,
+                    MacroKind::Derive => crate::hir::MacroKind::Derive,
+                };
+                ExpansionKind::Macro(kind, name.to_string())
+            }
+            ExpnKind::AstPass(pass) => ExpansionKind::AstPass(pass.descr().to_string()),
+            ExpnKind::Desugaring(kind) => ExpansionKind::Desugaring(kind.descr().to_string()),
+        };
+        crate::hir::Expansion {
+            kind,
+            call_site: self.call_site.stable(tables, cx),
+            def_site: self.def_site.stable(tables, cx),
+            macro_def: self.macro_def_id.map(|def_id| tables.create_def_id(def_id)),
+        }
+    }
+}
@@ -8,6 +8,7 @@
 use crate::compiler_interface::BridgeTys;
 
 mod abi;
+mod hir;
 mod mir;
 mod ty;
 
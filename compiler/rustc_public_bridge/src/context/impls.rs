@@ -5,13 +5,14 @@
 use std::iter;
 
 use rustc_abi::{Endian, Layout, ReprOptions};
-use rustc_hir::def::DefKind;
-use rustc_hir::{Attribute, LangItem};
+use rustc_errors::DiagDecorator;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::{self as hir, Attribute, CRATE_HIR_ID, HirId, LangItem};
 use rustc_middle::mir::interpret::{AllocId, ConstAllocation, ErrorHandled, GlobalAlloc, Scalar};
 use rustc_middle::mir::{BinOp, Body, Const as MirConst, ConstValue, UnOp};
 use rustc_middle::ty::layout::{FnAbiOf, LayoutOf};
 use rustc_middle::ty::print::{
-    with_forced_trimmed_paths, with_no_trimmed_paths, with_resolve_crate_name,
+    with_crate_prefix, with_forced_trimmed_paths, with_no_trimmed_paths, with_resolve_crate_name,
 };
 use rustc_middle::ty::util::Discr;
 use rustc_middle::ty::{
@@ -22,7 +23,9 @@
 };
 use rustc_middle::{mir, ty};
 use rustc_session::cstore::ForeignModule;
+use rustc_session::lint::Lint;
 use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_span::hygiene::{ExpnData, ExpnKind};
 use rustc_span::{Span, Symbol};
 use rustc_target::callconv::FnAbi;
 
@@ -317,6 +320,132 @@ pub fn all_tool_attrs(&self, did: DefId) -> Vec<(String, Span)> {
             .collect()
     }
 
+    /// Get all attributes of a definition, including the ones parsed by the compiler.
+    pub fn all_attrs(&self, did: DefId) -> &'tcx [Attribute] {
+        if let Some(did) = did.as_local() {
+            self.tcx.hir_attrs(self.tcx.local_def_id_to_hir_id(did))
+        } else {
+            self.tcx.attrs_for_def(did)
+        }
+    }
+
+    /// Returns the attribute printed back as source code.
+    pub fn attribute_to_string(&self, attribute: &Attribute) -> String {
+        rustc_hir_pretty::attribute_to_string(&self.tcx, attribute)
+    }
+
+    /// Get the free items declared in the root module of the local crate.
+    pub fn hir_root_items(&self) -> Vec<DefId> {
+        self.tcx.hir_root_module().item_ids.iter().map(|id| id.owner_id.to_def_id()).collect()
+    }
+
+    /// Get all free items of the local crate.
+    pub fn hir_all_items(&self) -> Vec<DefId> {
+        self.tcx.hir_free_items().map(|id| id.owner_id.to_def_id()).collect()
+    }
+
+    /// Get the HIR of a free item, if it is local.
+    pub fn hir_item(&self, def_id: DefId) -> Option<&'tcx hir::Item<'tcx>> {
+        match self.tcx.hir_node_by_def_id(def_id.as_local()?) {
+            hir::Node::Item(item) => Some(item),
+            _ => None,
+        }
+    }
+
+    /// Get the HIR body of a definition, if it is local and has one.
+    pub fn hir_body(&self, def_id: DefId) -> Option<&'tcx hir::Body<'tcx>> {
+        self.tcx.hir_maybe_body_owned_by(def_id.as_local()?)
+    }
+
+    /// Get the type of an expression or a pattern, before adjustments.
+    pub fn hir_node_ty(&self, hir_id: HirId) -> Option<Ty<'tcx>> {
+        let owner = hir_id.owner.def_id;
+        if !self.tcx.has_typeck_results(owner) {
+            return None;
+        }
+        self.tcx.typeck(owner).node_type_opt(hir_id)
+    }
+
+    /// Get the definition a method call or a type-relative path resolves to.
+    pub fn hir_type_dependent_def(&self, hir_id: HirId) -> Option<DefId> {
+        let owner = hir_id.owner.def_id;
+        if !self.tcx.has_typeck_results(owner) {
+            return None;
+        }
+        self.tcx.typeck(owner).type_dependent_def_id(hir_id)
+    }
+
+    /// Get the resolution of a path in an expression or a pattern.
+    pub fn hir_qpath_res(&self, qpath: &hir::QPath<'_>, hir_id: HirId) -> Res {
+        match qpath {
+            hir::QPath::Resolved(_, path) => path.res,
+            hir::QPath::TypeRelative(..) => self
+                .hir_type_dependent_def(hir_id)
+                .map_or(Res::Err, |def_id| Res::Def(self.tcx.def_kind(def_id), def_id)),
+        }
+    }
+
+    /// Get the visibility of a local item as written in the source code.
+    ///
+    /// If the visibility was not written by the user, e.g., because the item was produced by a
+    /// procedural macro, it is reconstructed from the visibility the item resolved to.
+    pub fn visibility_as_written(&self, def_id: DefId, vis_span: Span) -> String {
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(vis_span)
+            && (snippet.is_empty() || snippet.starts_with("pub"))
+        {
+            return snippet;
+        }
+        match self.tcx.visibility(def_id) {
+            ty::Visibility::Public => "pub".to_string(),
+            ty::Visibility::Restricted(module) if module.is_crate_root() => {
+                "pub(crate)".to_string()
+            }
+            ty::Visibility::Restricted(module)
+                if module
+                    == self.tcx.parent_module_from_def_id(def_id.expect_local()).to_def_id() =>
+            {
+                String::new()
+            }
+            ty::Visibility::Restricted(module) => {
+                with_crate_prefix!(with_no_trimmed_paths!(format!(
+                    "pub(in {})",
+                    self.tcx.def_path_str(module)
+                )))
+            }
+        }
+    }
+
+    /// Returns the source code of the given `Span`, if it is available.
+    pub fn span_to_snippet(&self, span: Span) -> Option<String> {
+        self.tcx.sess.source_map().span_to_snippet(span).ok()
+    }
+
+    /// Returns the expansion that produced the given `Span`, or `None` if it comes directly from
+    /// the source code.
+    pub fn span_expansion(&self, span: Span) -> Option<ExpnData> {
+        let expn_data = span.ctxt().outer_expn_data();
+        if matches!(expn_data.kind, ExpnKind::Root) { None } else { Some(expn_data) }
+    }
+
+    /// Emits `lint` at `span`, with the level in effect at the HIR node `hir_id`, or at the crate
+    /// root if there is no node.
+    pub fn emit_lint(
+        &self,
+        lint: &'static Lint,
+        hir_id: Option<HirId>,
+        span: Span,
+        message: String,
+    ) {
+        self.tcx.emit_node_span_lint(
+            lint,
+            hir_id.unwrap_or(CRATE_HIR_ID),
+            span,
+            DiagDecorator(|diag| {
+                diag.primary_message(message);
+            }),
+        );
+    }
+
     /// Returns printable, human readable form of `Span`.
     pub fn span_to_string(&self, span: Span) -> String {
         self.tcx.sess.source_map().span_to_diagnostic_string(span)
@@ -174,6 +174,11 @@ fn invocation_parent(&self, id: LocalExpnId) -> LocalDefId {
         self.invocation_parents[&id].parent_def
     }
 
+    fn invocation_parent_path(&self, id: LocalExpnId) -> Vec<Symbol> {
+        let parent = self.invocation_parent(id);
+        self.tcx.def_path(parent.to_def_id()).data.iter().map(|data| data.as_sym(false)).collect()
+    }
+
     fn mark_scope_with_compile_error(&mut self, id: NodeId) {
         if let Some(id) = self.opt_local_def_id(id)
             && self.tcx.def_kind(id).is_module_like()
@@ -10,7 +10,7 @@
 use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
 use std::sync::LazyLock;
-use std::{cmp, fs, iter};
+use std::{cmp, fs, io, iter};
 
 use externs::{ExternOpt, split_extern_opt};
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
@@ -182,11 +182,42 @@ pub enum CoverageLevel {
     /// let x = a && b;
     /// //           ^ last operand
     /// ```
-    ///
-    /// This level is mainly intended to be a stepping-stone towards full MC/DC
-    /// instrumentation, so it might be removed in the future when MC/DC is
-    /// sufficiently complete, or if it is making MC/DC changes difficult.
     Condition,
+    /// Instrument for MC/DC (modified condition/decision coverage). Mostly a
+    /// superset of condition coverage, but also records which combinations of
+    /// conditions were evaluated for each decision made of `&&`/`||` chains,
+    /// so that `llvm-cov` can report whether each condition independently
+    /// affected the outcome of its decision.
+    Mcdc,
+}
+
+/// Kinds of instrumentation enabled by `-Zsanitizer-coverage`, which are inserted by LLVM's
+/// SanitizerCoverage pass to guide fuzzers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct SanitizerCoverageOptions {
+    /// `trace-pc-guard`: call `__sanitizer_cov_trace_pc_guard` on every edge, with a guard
+    /// variable unique to that edge.
+    pub trace_pc_guard: bool,
+    /// `trace-cmp`: call `__sanitizer_cov_trace_cmp*` before comparisons and switches, with
+    /// their operands.
+    pub trace_cmp: bool,
+    /// `inline-8bit-counters`: increment an inline 8-bit counter on every edge.
+    pub inline_8bit_counters: bool,
+    /// `pc-table`: emit a table of the instrumented PCs, alongside the guards or counters.
+    pub pc_table: bool,
+}
+
+impl SanitizerCoverageOptions {
+    /// Returns true if any kind of instrumentation is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.trace_pc_guard || self.trace_cmp || self.inline_8bit_counters || self.pc_table
+    }
+
+    /// Returns true if edges get instrumented, i.e. if there are guards or counters that a
+    /// `pc-table` can refer to.
+    pub fn instruments_edges(&self) -> bool {
+        self.trace_pc_guard || self.inline_8bit_counters
+    }
 }
 
 // The different settings that the `-Z offload` flag can have.
@@ -2225,8 +2256,11 @@ pub fn parse_target_triple(early_dcx: &EarlyDiagCtxt, matches: &getopts::Matches
     match matches.opt_str("target") {
         Some(target) if target.ends_with(".json") => {
             let path = Path::new(&target);
-            TargetTuple::from_path(path).unwrap_or_else(|_| {
-                early_dcx.early_fatal(format!("target file {path:?} does not exist"))
+            TargetTuple::from_path(path).unwrap_or_else(|err| match err.kind() {
+                io::ErrorKind::InvalidData => {
+                    early_dcx.early_fatal(format!("error loading target specification: {err}"))
+                }
+                _ => early_dcx.early_fatal(format!("target file {path:?} does not exist")),
             })
         }
         Some(target) => TargetTuple::TargetTuple(target),
@@ -3076,8 +3110,9 @@ pub(crate) mod dep_tracking {
         CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FmtDebug, FunctionReturn,
         InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
         LtoCli, MirStripDebugInfo, NextSolverConfig, Offload, OptLevel, OutFileName, OutputType,
-        OutputTypes, PatchableFunctionEntry, Polonius, ResolveDocLinks, SourceFileHashAlgorithm,
-        SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion, WasiExecModel,
+        OutputTypes, PatchableFunctionEntry, Polonius, ResolveDocLinks, SanitizerCoverageOptions,
+        SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion,
+        WasiExecModel,
     };
     use crate::lint;
     use crate::utils::NativeLib;
@@ -3154,6 +3189,7 @@ fn hash(
         UnstableFeatures,
         NativeLib,
         SanitizerSet,
+        SanitizerCoverageOptions,
         CFGuard,
         CFProtection,
         TargetTuple,
@@ -177,6 +177,22 @@ pub(crate) struct CannotMixAndMatchSanitizers {
 #[diag("`-Z sanitizer=kcfi` requires `-C panic=abort`")]
 pub(crate) struct SanitizerKcfiRequiresPanicAbort;
 
+#[derive(Diagnostic)]
+#[diag(
+    "`-Zsanitizer-coverage=pc-table` requires `-Zsanitizer-coverage=trace-pc-guard` or `-Zsanitizer-coverage=inline-8bit-counters`"
+)]
+pub(crate) struct SanitizerCoveragePcTableRequiresEdges;
+
+#[derive(Diagnostic)]
+#[diag("`-Zsanitizer-coverage-ignorelist` requires `-Zsanitizer-coverage`")]
+pub(crate) struct SanitizerCoverageIgnorelistRequiresCoverage;
+
+#[derive(Diagnostic)]
+#[diag("SanitizerCoverage ignorelist `{$path}` does not exist or is not a file")]
+pub(crate) struct SanitizerCoverageIgnorelistNotFound<'a> {
+    pub(crate) path: &'a str,
+}
+
 #[derive(Diagnostic)]
 #[diag("`-Zsplit-lto-unit` requires `-Clto`, `-Clto=thin`, or `-Clinker-plugin-lto`")]
 pub(crate) struct SplitLtoUnitRequiresLto;
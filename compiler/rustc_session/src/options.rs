@@ -111,6 +111,20 @@ pub(super) fn sanitizer(l: &TargetModifier, r: Option<&TargetModifier>) -> bool
 
         lparsed & tmod_sanitizers == rparsed & tmod_sanitizers
     }
+    pub(super) fn sanitizer_coverage(l: &TargetModifier, r: Option<&TargetModifier>) -> bool {
+        // Crates without coverage instrumentation, like the standard library, can be linked
+        // with instrumented ones: their code is just not traced.
+        let Some(r) = r else { return true };
+
+        let mut lparsed: SanitizerCoverageOptions = Default::default();
+        parse::parse_sanitizer_coverage(&mut lparsed, Some(l.value_name.as_str()));
+        let mut rparsed: SanitizerCoverageOptions = Default::default();
+        parse::parse_sanitizer_coverage(&mut rparsed, Some(r.value_name.as_str()));
+
+        // Instrumented crates must agree on the kinds of instrumentation, as fuzzers expect
+        // every edge to be reported in the same way.
+        !lparsed.is_enabled() || !rparsed.is_enabled() || lparsed == rparsed
+    }
     pub(super) fn sanitizer_cfi_normalize_integers(
         sess: &Session,
         l: &TargetModifier,
@@ -146,6 +160,9 @@ pub fn consistent(&self, sess: &Session, other: Option<&TargetModifier>) -> bool
                         sess, self, other,
                     );
                 }
+                UnstableOptionsTargetModifiers::sanitizer_coverage => {
+                    return target_modifier_consistency_check::sanitizer_coverage(self, other);
+                }
                 _ => {}
             },
             _ => {}
@@ -818,6 +835,7 @@ mod desc {
     pub(crate) const parse_opt_panic_strategy: &str = parse_panic_strategy;
     pub(crate) const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
     pub(crate) const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `dataflow`, `hwaddress`, `kcfi`, `kernel-address`, `leak`, `memory`, `memtag`, `safestack`, `shadow-call-stack`, `thread`, or 'realtime'";
+    pub(crate) const parse_sanitizer_coverage: &str = "comma separated list of instrumentations: `trace-pc-guard`, `trace-cmp`, `inline-8bit-counters`, or `pc-table`";
     pub(crate) const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
     pub(crate) const parse_cfguard: &str =
         "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
@@ -831,7 +849,7 @@ mod desc {
     pub(crate) const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavorCli::one_of();
     pub(crate) const parse_dump_mono_stats: &str = "`markdown` (default) or `json`";
     pub(crate) const parse_instrument_coverage: &str = parse_bool;
-    pub(crate) const parse_coverage_options: &str = "`block` | `branch` | `condition`";
+    pub(crate) const parse_coverage_options: &str = "`block` | `branch` | `condition` | `mcdc`";
     pub(crate) const parse_instrument_xray: &str = "either a boolean (`yes`, `no`, `on`, `off`, etc), or a comma separated list of settings: `always` or `never` (mutually exclusive), `ignore-loops`, `instruction-threshold=N`, `skip-entry`, `skip-exit`";
     pub(crate) const parse_unpretty: &str = "`string` or `string=string`";
     pub(crate) const parse_treat_err_as_bug: &str = "either no value or a non-negative number";
@@ -1282,6 +1300,24 @@ pub(crate) fn parse_sanitizers(slot: &mut SanitizerSet, v: Option<&str>) -> bool
         }
     }
 
+    pub(crate) fn parse_sanitizer_coverage(
+        slot: &mut SanitizerCoverageOptions,
+        v: Option<&str>,
+    ) -> bool {
+        let Some(v) = v else { return false };
+
+        for s in v.split(',') {
+            match s {
+                "trace-pc-guard" => slot.trace_pc_guard = true,
+                "trace-cmp" => slot.trace_cmp = true,
+                "inline-8bit-counters" => slot.inline_8bit_counters = true,
+                "pc-table" => slot.pc_table = true,
+                _ => return false,
+            }
+        }
+        true
+    }
+
     pub(crate) fn parse_sanitizer_memory_track_origins(slot: &mut usize, v: Option<&str>) -> bool {
         match v {
             Some("2") | None => {
@@ -1573,6 +1609,7 @@ pub(crate) fn parse_coverage_options(slot: &mut CoverageOptions, v: Option<&str>
                 "block" => slot.level = CoverageLevel::Block,
                 "branch" => slot.level = CoverageLevel::Branch,
                 "condition" => slot.level = CoverageLevel::Condition,
+                "mcdc" => slot.level = CoverageLevel::Mcdc,
                 "discard-all-spans-in-codegen" => slot.discard_all_spans_in_codegen = true,
                 _ => return false,
             }
@@ -2322,7 +2359,11 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         parse_switch_with_opt_path, [UNTRACKED],
         "output statistics about monomorphization collection"),
     dump_mono_stats_format: DumpMonoStatsFormat = (DumpMonoStatsFormat::Markdown, parse_dump_mono_stats, [UNTRACKED],
-        "the format to use for -Z dump-mono-stats (`markdown` (default) or `json`)"),
+        "the format to use for -Z dump-mono-stats and -Z dump-mono-usage-graph \
+        (`markdown` (default) or `json`)"),
+    dump_mono_usage_graph: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "output the graph of which monomorphized items use which, and where"),
     #[rustc_lint_opt_deny_field_access("use `Session::dwarf_version` instead of this field")]
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
@@ -2345,6 +2386,9 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "enforce the type length limit when monomorphizing instances in codegen"),
     experimental_default_bounds: bool = (false, parse_bool, [TRACKED],
         "enable default bounds for experimental group of auto traits"),
+    explain_mono_item: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "print the shortest chain of uses from a root to each instantiation of the given \
+        item, e.g. `-Z explain-mono-item=core::ptr::drop_in_place`"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
         "export symbols from executables, as if they were dynamic libraries"),
     external_clangrt: bool = (false, parse_bool, [UNTRACKED],
@@ -2371,6 +2415,8 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "whether each function should go in its own section"),
     future_incompat_test: bool = (false, parse_bool, [UNTRACKED],
         "forces all lints to be future incompatible, used for internal testing (default: no)"),
+    future_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the size at which the `large_futures` lint starts to be emitted"),
     graphviz_dark_mode: bool = (false, parse_bool, [UNTRACKED],
         "use dark-themed colors in graphviz output (default: no)"),
     graphviz_font: String = ("Courier, monospace".to_string(), parse_string, [UNTRACKED],
@@ -2439,10 +2485,16 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    linker_map_report: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write the linker map and a JSON report of the section and symbol sizes of linked \
+        artifacts to the given directory (default: next to the artifact)"),
     lint_llvm_ir: bool = (false, parse_bool, [TRACKED],
         "lint LLVM IR (default: no)"),
     lint_mir: bool = (false, parse_bool, [UNTRACKED],
         "lint MIR before and after each transformation"),
+    lint_plugin: Vec<String> = (Vec::new(), parse_string_push, [TRACKED],
+        "load lints from the given plugin library (may be repeated)"),
     llvm_module_flag: Vec<(String, u32, String)> = (Vec::new(), parse_llvm_module_flag, [TRACKED],
         "a list of module flags to pass to LLVM (space separated)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
@@ -2558,6 +2610,9 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         (default: PLT is disabled if full relro is enabled on x86_64)"),
     polonius: Polonius = (Polonius::default(), parse_polonius, [TRACKED],
         "enable polonius-based borrow-checker (default: no)"),
+    polymorphize: bool = (false, parse_bool, [TRACKED],
+        "share the code of instantiations that only differ in generic parameters their \
+        body does not depend on (default: no)"),
     pre_link_arg: (/* redirected to pre_link_args */) = ((), parse_string_push, [UNTRACKED],
         "a single extra argument to prepend the linker invocation (can be used several times)"),
     pre_link_args: Vec<String> = (Vec::new(), parse_list, [UNTRACKED],
@@ -2619,6 +2674,11 @@ pub(crate) fn parse_align(slot: &mut Option<Align>, v: Option<&str>) -> bool {
         "enable generalizing pointer types (default: no)"),
     sanitizer_cfi_normalize_integers: Option<bool> = (None, parse_opt_bool, [TRACKED TARGET_MODIFIER],
         "enable normalizing integer types (default: no)"),
+    sanitizer_coverage: SanitizerCoverageOptions = (SanitizerCoverageOptions::default(), parse_sanitizer_coverage, [TRACKED TARGET_MODIFIER],
+        "instrument code with SanitizerCoverage to guide fuzzers (comma separated)"),
+    sanitizer_coverage_ignorelist: Vec<String> = (Vec::new(), parse_comma_list, [TRACKED],
+        "files listing the functions and source files that SanitizerCoverage does not \
+        instrument (comma separated)"),
     sanitizer_dataflow_abilist: Vec<String> = (Vec::new(), parse_comma_list, [TRACKED],
         "additional ABI list files that control how shadow parameters are passed (comma separated)"),
     sanitizer_kcfi_arity: Option<bool> = (None, parse_opt_bool, [TRACKED],
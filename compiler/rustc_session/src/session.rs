@@ -1,5 +1,5 @@
 use std::any::Any;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize};
@@ -65,6 +65,9 @@ pub struct Limits {
     /// The size at which the `large_assignments` lint starts
     /// being emitted.
     pub move_size_limit: Limit,
+    /// The size at which the `large_futures` lint starts
+    /// being emitted.
+    pub future_size_limit: Limit,
     /// The maximum length of types during monomorphization.
     pub type_length_limit: Limit,
     /// The maximum pattern complexity allowed (internal only).
@@ -323,6 +326,11 @@ pub fn instrument_coverage_condition(&self) -> bool {
             && self.opts.unstable_opts.coverage_options.level >= CoverageLevel::Condition
     }
 
+    pub fn instrument_coverage_mcdc(&self) -> bool {
+        self.instrument_coverage()
+            && self.opts.unstable_opts.coverage_options.level >= CoverageLevel::Mcdc
+    }
+
     /// Provides direct access to the `CoverageOptions` struct, so that
     /// individual flags for debugging/testing coverage instrumetation don't
     /// need separate accessors.
@@ -1243,6 +1251,24 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         }
     }
 
+    // A PC table describes the guards or counters of the instrumented edges.
+    let sanitizer_coverage = sess.opts.unstable_opts.sanitizer_coverage;
+    if sanitizer_coverage.pc_table && !sanitizer_coverage.instruments_edges() {
+        sess.dcx().emit_err(errors::SanitizerCoveragePcTableRequiresEdges);
+    }
+
+    if !sess.opts.unstable_opts.sanitizer_coverage_ignorelist.is_empty() {
+        if !sanitizer_coverage.is_enabled() {
+            sess.dcx().emit_err(errors::SanitizerCoverageIgnorelistRequiresCoverage);
+        }
+        // LLVM aborts if it cannot read an ignorelist, so report missing files here.
+        for path in &sess.opts.unstable_opts.sanitizer_coverage_ignorelist {
+            if !Path::new(path).is_file() {
+                sess.dcx().emit_err(errors::SanitizerCoverageIgnorelistNotFound { path });
+            }
+        }
+    }
+
     // LTO unit splitting requires LTO.
     if sess.is_split_lto_unit_enabled()
         && !(sess.lto() == config::Lto::Fat
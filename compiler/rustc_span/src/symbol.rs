@@ -1003,6 +1003,7 @@
         fundamental,
         fused_iterator,
         future_output,
+        future_size_limit,
         future_trait,
         fxsr,
         gdb_script_file,
@@ -1127,6 +1128,7 @@
         lang,
         lang_items,
         large_assignments,
+        large_futures,
         last,
         lateout,
         lazy_normalization_consts,
@@ -1,7 +1,11 @@
+use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use rustc_abi::{Align, AlignFromBytesError};
+use rustc_fs_util::try_canonicalize;
 
 use super::crt_objects::CrtObjects;
 use super::{
@@ -9,18 +13,44 @@
     LinkSelfContainedComponents, LinkSelfContainedDefault, LinkerFlavorCli, LldFlavor,
     MergeFunctions, Os, PanicStrategy, RelocModel, RelroLevel, RustcAbi, SanitizerSet,
     SmallDataThresholdSupport, SplitDebuginfo, StackProbeType, StaticCow, SymbolVisibility, Target,
-    TargetKind, TargetOptions, TargetWarnings, TlsModel,
+    TargetKind, TargetOptions, TargetWarnings, TlsModel, load_builtin,
 };
 use crate::json::{Json, ToJson};
 use crate::spec::{AbiMap, LlvmAbi};
 
 impl Target {
     /// Loads a target descriptor from a JSON object.
+    ///
+    /// A relative path in the `inherits` key is resolved against the current directory.
     pub fn from_json(json: &str) -> Result<(Target, TargetWarnings), String> {
-        let json_deserializer = &mut serde_json::Deserializer::from_str(json);
+        Target::from_json_file(json, None)
+    }
 
-        let json: TargetSpecJson =
-            serde_path_to_error::deserialize(json_deserializer).map_err(|err| err.to_string())?;
+    /// Like [`Target::from_json`], but a relative path in the `inherits` key is resolved against
+    /// the directory of `path`, the file that `json` was read from.
+    pub(super) fn from_json_file(
+        json: &str,
+        path: Option<&Path>,
+    ) -> Result<(Target, TargetWarnings), String> {
+        let json_deserializer = &mut serde_json::Deserializer::from_str(json);
+        let json: TargetSpecJson = match serde_path_to_error::deserialize(json_deserializer) {
+            Ok(json @ TargetSpecJson { inherits: None, .. }) => json,
+            // A specification that inherits from another one usually leaves out required
+            // fields, so only look for `inherits` again if it doesn't deserialize on its own.
+            result => {
+                let spec = match serde_json::from_str(json) {
+                    Ok(Json::Object(spec)) if spec.contains_key(INHERITS) => spec,
+                    _ => {
+                        let err = result.err().expect("`inherits` is set if `json` deserialized");
+                        return Err(err.to_string());
+                    }
+                };
+                let mut chain: Vec<PathBuf> = path.map(Path::to_owned).into_iter().collect();
+                let spec = resolve_inherits(spec, path.and_then(Path::parent), &mut chain)?;
+                serde_path_to_error::deserialize(Json::Object(spec))
+                    .map_err(|err| err.to_string())?
+            }
+        };
 
         let mut base = Target {
             llvm_target: json.llvm_target,
@@ -237,6 +267,88 @@ macro_rules! forward_opt {
 
         Ok((base, TargetWarnings { unused_fields: vec![] }))
     }
+
+    /// Resolves the `inherits` key of a JSON target specification, which names a built-in target
+    /// or another JSON file whose fields are used for those that the specification leaves out.
+    /// `path` is the file that the specification was read from, relative paths to other files
+    /// are resolved against its directory.
+    ///
+    /// Returns a specification without `inherits`, or `json` itself if it doesn't inherit from
+    /// anything. Errors in the JSON syntax are left to [`Target::from_json`] to report.
+    pub fn resolve_json_inheritance<'a>(
+        json: &'a str,
+        path: Option<&Path>,
+    ) -> Result<Cow<'a, str>, String> {
+        let Ok(Json::Object(spec)) = serde_json::from_str(json) else {
+            return Ok(Cow::Borrowed(json));
+        };
+        if !spec.contains_key(INHERITS) {
+            return Ok(Cow::Borrowed(json));
+        }
+        let mut chain: Vec<PathBuf> = path.map(Path::to_owned).into_iter().collect();
+        let spec = resolve_inherits(spec, path.and_then(Path::parent), &mut chain)?;
+        Ok(Cow::Owned(Json::Object(spec).to_string()))
+    }
+}
+
+/// The key of a JSON target specification that names the target it is based on.
+const INHERITS: &str = "inherits";
+
+/// Merges `spec` into the target named by its `inherits` key, recursively. `chain` holds the
+/// files that `spec` was inherited through, to detect cycles.
+fn resolve_inherits(
+    mut spec: serde_json::Map<String, Json>,
+    dir: Option<&Path>,
+    chain: &mut Vec<PathBuf>,
+) -> Result<serde_json::Map<String, Json>, String> {
+    let Some(base) = spec.remove(INHERITS) else { return Ok(spec) };
+    let Json::String(base) = base else {
+        return Err(format!(
+            "`{INHERITS}` must be the name of a target or the path to a JSON file"
+        ));
+    };
+
+    let mut resolved = if base.ends_with(".json") {
+        let path = dir.map_or_else(|| PathBuf::from(&base), |dir| dir.join(&base));
+        let path = try_canonicalize(&path)
+            .map_err(|err| format!("could not find base target {path:?}: {err}"))?;
+        if let Some(start) = chain.iter().position(|inherited| *inherited == path) {
+            if start == chain.len() - 1 {
+                return Err(format!("target specification {path:?} inherits from itself"));
+            }
+            let cycle: Vec<String> =
+                chain[start..].iter().chain([&path]).map(|path| format!("{path:?}")).collect();
+            return Err(format!(
+                "target specifications inherit from each other in a cycle: {}",
+                cycle.join(" -> ")
+            ));
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| format!("could not read base target {path:?}: {err}"))?;
+        let Json::Object(base_spec) = serde_json::from_str(&contents)
+            .map_err(|err| format!("invalid base target {path:?}: {err}"))?
+        else {
+            return Err(format!("base target {path:?} must be a JSON object"));
+        };
+        let dir = path.parent().map(Path::to_owned);
+        chain.push(path);
+        resolve_inherits(base_spec, dir.as_deref(), chain)?
+    } else {
+        let Some(target) = load_builtin(&base) else {
+            return Err(format!(
+                "base target `{base}` is neither a built-in target nor a path to a JSON file"
+            ));
+        };
+        let Json::Object(base_spec) = target.to_json() else {
+            unreachable!("targets are serialized as JSON objects")
+        };
+        base_spec
+    };
+
+    // Fields of the inheriting specification replace those of the base as a whole, objects and
+    // arrays like `pre-link-args` are not merged.
+    resolved.extend(spec);
+    Ok(resolved)
 }
 
 impl ToJson for Target {
@@ -497,6 +609,9 @@ struct TargetSpecJson {
     data_layout: StaticCow<str>,
     arch: Arch,
 
+    /// Only set if the specification has all required fields, see [`Target::from_json_file`].
+    inherits: Option<StaticCow<str>>,
+
     metadata: Option<TargetSpecJsonMetadata>,
 
     // options:
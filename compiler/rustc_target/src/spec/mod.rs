@@ -3658,7 +3658,8 @@ fn load_file(
                 );
             }
             let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
-            Target::from_json(&contents)
+            let path = try_canonicalize(path).map_err(|e| e.to_string())?;
+            Target::from_json_file(&contents, Some(path.as_path()))
         }
 
         match *target_tuple {
@@ -3909,6 +3910,10 @@ pub fn from_tuple(tuple: &str) -> Self {
     }
 
     /// Creates a target tuple from the passed target path.
+    ///
+    /// The contents of the target are the resolved specification if it inherits from another
+    /// target, so that they don't depend on the location of the file. Errors while resolving it
+    /// are returned as [`io::ErrorKind::InvalidData`].
     pub fn from_path(path: &Path) -> Result<Self, io::Error> {
         let canonicalized_path = try_canonicalize(path)?;
         let contents = std::fs::read_to_string(&canonicalized_path).map_err(|err| {
@@ -3917,6 +3922,10 @@ pub fn from_path(path: &Path) -> Result<Self, io::Error> {
                 format!("target path {canonicalized_path:?} is not a valid file: {err}"),
             )
         })?;
+        let contents =
+            Target::resolve_json_inheritance(&contents, Some(canonicalized_path.as_path()))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+                .into_owned();
         let tuple = canonicalized_path
             .file_stem()
             .expect("target path must not be empty")
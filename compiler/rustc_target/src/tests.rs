@@ -1,5 +1,5 @@
 use crate::json::ToJson;
-use crate::spec::Target;
+use crate::spec::{Target, TargetTuple};
 
 #[test]
 fn report_unused_fields() {
@@ -40,3 +40,38 @@ fn custom_arch_propagates_from_json() {
         assert_eq!(serialized["arch"], "customarch");
     });
 }
+
+#[test]
+fn inherits_builtin_target() {
+    let json = r#"
+    {
+        "inherits": "x86_64-unknown-none",
+        "cpu": "x86-64-v2",
+        "max-atomic-width": 32
+    }
+    "#;
+    rustc_span::create_session_if_not_set_then(rustc_span::edition::DEFAULT_EDITION, |_| {
+        let (target, _) = Target::from_json(json).expect("json target parses");
+        let mut expected = Target::expect_builtin(&TargetTuple::from_tuple("x86_64-unknown-none"));
+        expected.options.cpu = "x86-64-v2".into();
+        expected.options.max_atomic_width = Some(32);
+        assert_eq!(target.to_json(), expected.to_json());
+    });
+}
+
+#[test]
+fn inherits_unknown_target() {
+    let json = r#"{ "inherits": "x86_64-unknown-nowhere" }"#;
+    let err = Target::from_json(json).unwrap_err();
+    assert!(err.contains("base target `x86_64-unknown-nowhere` is neither"), "{err}");
+
+    let json = r#"{ "inherits": ["x86_64-unknown-none"] }"#;
+    let err = Target::from_json(json).unwrap_err();
+    assert!(err.contains("`inherits` must be the name of a target"), "{err}");
+}
+
+#[test]
+fn inheritance_leaves_other_specs_alone() {
+    let json = r#"{ "llvm-target": "x86_64-unknown-none" "#;
+    assert_eq!(Target::resolve_json_inheritance(json, None).as_deref(), Ok(json));
+}
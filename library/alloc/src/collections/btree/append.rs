@@ -61,6 +61,9 @@ pub(super) fn bulk_push<I, A: Allocator + Clone>(
             // the appended elements even if advancing the iterator panicks.
             *length += 1;
         }
+        // Pushing to the rightmost leaf does not keep track of the subtree
+        // lengths of its ancestors, so count them all at once.
+        self.borrow_mut().recalc_all_subtree_lens();
         self.fix_right_border_of_plentiful();
     }
 }
@@ -47,3 +47,41 @@ fn next(&mut self) -> Option<(K, V)> {
         }
     }
 }
+
+/// An iterator that passes on the key-value pairs of another iterator, and
+/// panics as soon as it finds a key that is less than the key before it.
+///
+/// Used by [`BTreeMap::from_sorted_iter`][1].
+///
+/// [1]: crate::collections::BTreeMap::from_sorted_iter
+pub(super) struct AssertSortedIter<K, V, I>
+where
+    I: Iterator<Item = (K, V)>,
+{
+    iter: Peekable<I>,
+}
+
+impl<K, V, I> AssertSortedIter<K, V, I>
+where
+    I: Iterator<Item = (K, V)>,
+{
+    pub(super) fn new(iter: I) -> Self {
+        Self { iter: iter.peekable() }
+    }
+}
+
+impl<K, V, I> Iterator for AssertSortedIter<K, V, I>
+where
+    K: Ord,
+    I: Iterator<Item = (K, V)>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        let next = self.iter.next()?;
+        if let Some(peeked) = self.iter.peek() {
+            assert!(next.0 <= peeked.0, "input is not sorted in ascending order");
+        }
+        Some(next)
+    }
+}
@@ -3,14 +3,15 @@
 use core::error::Error;
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
-use core::iter::{FusedIterator, TrustedLen};
+use core::iter::{self, FusedIterator, TrustedLen};
 use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop};
 use core::ops::{Bound, Index, RangeBounds};
 use core::ptr;
 
 use super::borrow::DormantMutRef;
-use super::dedup_sorted_iter::DedupSortedIter;
+use super::dedup_sorted_iter::{AssertSortedIter, DedupSortedIter};
+use super::merge_iter::MergeIterInner;
 use super::navigate::{LazyLeafRange, LeafRange};
 use super::node::ForceResult::*;
 use super::node::{self, Handle, NodeRef, Root, marker};
@@ -651,6 +652,39 @@ impl<K, V> BTreeMap<K, V> {
     pub const fn new() -> BTreeMap<K, V> {
         BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(Global), _marker: PhantomData }
     }
+
+    /// Makes a `BTreeMap` from an iterator of key-value pairs that is sorted
+    /// by key, in linear time.
+    ///
+    /// Unlike collecting into a `BTreeMap`, this does not sort the pairs
+    /// first, and it builds nodes that are filled up to their capacity, like
+    /// collecting does. If several pairs have equal keys, only the last one
+    /// is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are not in ascending order. The pairs taken from
+    /// the iterator up to that point are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_from_sorted)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_sorted_iter([(1, "a"), (2, "b"), (2, "c"), (3, "d")]);
+    ///
+    /// assert_eq!(map.len(), 3);
+    /// assert_eq!(map[&2], "c");
+    /// ```
+    #[unstable(feature = "btree_from_sorted", issue = "none")]
+    pub fn from_sorted_iter<I>(iter: I) -> BTreeMap<K, V>
+    where
+        K: Ord,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        BTreeMap::bulk_build_from_sorted_iter(AssertSortedIter::new(iter.into_iter()), Global)
+    }
 }
 
 impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A> {
@@ -794,6 +828,66 @@ pub fn get_key_value<Q: ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
         }
     }
 
+    /// Returns the key-value pair at the given position in the map, counting
+    /// from zero in ascending order of keys, or `None` if `n` is not less than
+    /// the length of the map.
+    ///
+    /// Unlike `map.iter().nth(n)`, this takes logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    /// assert_eq!(map.nth(0), Some((&10, &"a")));
+    /// assert_eq!(map.nth(2), Some((&30, &"c")));
+    /// assert_eq!(map.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn nth(&self, n: usize) -> Option<(&K, &V)> {
+        let root_node = self.root.as_ref()?.reborrow();
+        root_node.search_tree_by_rank(n)
+    }
+
+    /// Returns the number of keys in the map that are less than the given key.
+    ///
+    /// If the map contains the key, this is its position in the map, counting
+    /// from zero in ascending order of keys, so that [`nth`] returns it.
+    /// Otherwise, it is the position the key would have if it was inserted.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// [`nth`]: BTreeMap::nth
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    /// assert_eq!(map.rank(&20), 1);
+    /// assert_eq!(map.rank(&25), 2);
+    /// assert_eq!(map.rank(&5), 0);
+    /// assert_eq!(map.nth(map.rank(&30)), Some((&30, &"c")));
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn rank<Q: ?Sized>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        match &self.root {
+            Some(root) => root.reborrow().rank(key),
+            None => 0,
+        }
+    }
+
     /// Returns the first key-value pair in the map.
     /// The key in this pair is the minimum key in the map.
     ///
@@ -1236,6 +1330,12 @@ pub fn append(&mut self, other: &mut Self)
     /// Similar to [`insert`], though, the key is not overwritten,
     /// which matters for types that can be `==` without being identical.
     ///
+    /// If `other` is not much smaller than `self`, this takes time linear in
+    /// the length of both maps and leaves `self` with nodes that are filled up
+    /// to their capacity. If `conflict` panics, the key and values it was
+    /// called with are dropped, but `self` keeps its other elements, along
+    /// with the elements of `other` that were merged before the panic.
+    ///
     /// [`insert`]: BTreeMap::insert
     /// [`append`]: BTreeMap::append
     ///
@@ -1285,6 +1385,58 @@ pub fn merge(&mut self, mut other: Self, mut conflict: impl FnMut(&K, V, V) -> V
             return;
         }
 
+        // Inserting the elements of `other` one by one costs a search each,
+        // while rebuilding the tree costs a single pass over both maps, so
+        // rebuild unless `other` is much smaller than `self`.
+        if other.len().saturating_mul(self.len().ilog2() as usize) >= self.len() {
+            /// Pushes the elements of `self` that are left in `inner` onto
+            /// `map` when dropped before `done` is set, so that they are kept
+            /// if `conflict` panics.
+            struct MergeGuard<'a, K, V, A: Allocator + Clone> {
+                map: &'a mut BTreeMap<K, V, A>,
+                inner: MergeIterInner<IntoIter<K, V, A>>,
+                done: bool,
+            }
+
+            impl<K, V, A: Allocator + Clone> Drop for MergeGuard<'_, K, V, A> {
+                fn drop(&mut self) {
+                    if self.done {
+                        // The right border of the tree is already fixed up,
+                        // and pushing nothing would fix it up once more.
+                        return;
+                    }
+                    let alloc = (*self.map.alloc).clone();
+                    if let Some(root) = &mut self.map.root {
+                        root.bulk_push(self.inner.rest_of_a(), &mut self.map.length, alloc);
+                    }
+                }
+            }
+
+            let alloc = (*self.alloc).clone();
+            let self_iter = mem::replace(self, Self::new_in(alloc.clone())).into_iter();
+            let mut guard = MergeGuard {
+                map: self,
+                inner: MergeIterInner::new(self_iter, other.into_iter()),
+                done: false,
+            };
+            let root = guard.map.root.insert(Root::new(alloc.clone()));
+            let inner = &mut guard.inner;
+            let merged =
+                iter::from_fn(|| match inner.nexts(|a: &(K, V), b: &(K, V)| K::cmp(&a.0, &b.0)) {
+                    (Some((key, self_val)), Some((_, other_val))) => {
+                        let val = conflict(&key, self_val, other_val);
+                        Some((key, val))
+                    }
+                    (Some(kv), None) | (None, Some(kv)) => Some(kv),
+                    (None, None) => None,
+                });
+            // The keys of `merged` are strictly ascending, and every element
+            // that `guard` pushes afterwards is greater than all of them.
+            root.bulk_push(merged, &mut guard.map.length, alloc);
+            guard.done = true;
+            return;
+        }
+
         let mut other_iter = other.into_iter();
         let (first_other_key, first_other_val) = other_iter.next().unwrap();
 
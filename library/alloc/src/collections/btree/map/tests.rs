@@ -51,6 +51,7 @@ fn check_invariants(&self) {
 
             // Check consistency of `length` with what navigation code encounters.
             assert_eq!(self.length, root_node.calc_length());
+            assert_eq!(self.length, root_node.assert_subtree_lens());
 
             // Lastly, check the invariant causing the least harm.
             root_node.assert_min_len(if root_node.height() > 0 { 1 } else { 0 });
@@ -761,6 +762,55 @@ fn test_range_backwards_4() {
     let _ = map.range((Excluded(3), Excluded(2)));
 }
 
+#[test]
+fn test_nth_and_rank() {
+    let mut map = BTreeMap::new();
+    assert_eq!(map.nth(0), None);
+    assert_eq!(map.rank(&0), 0);
+
+    let mut rng = DeterministicRng::new();
+    for _ in 0..MIN_INSERTS_HEIGHT_2 * 4 {
+        map.insert(rng.next() % 1000, ());
+    }
+    for _ in 0..MIN_INSERTS_HEIGHT_2 * 2 {
+        map.remove(&(rng.next() % 1000));
+    }
+    map.check();
+    assert!(map.height() >= Some(2));
+
+    for (i, &k) in map.keys().enumerate() {
+        assert_eq!(map.nth(i), Some((&k, &())));
+        assert_eq!(map.rank(&k), i);
+        if !map.contains_key(&(k + 1)) {
+            assert_eq!(map.rank(&(k + 1)), i + 1);
+        }
+    }
+    assert_eq!(map.nth(map.len()), None);
+    assert_eq!(map.rank(&1000), map.len());
+}
+
+#[test]
+fn test_nth_and_rank_after_split_off_and_append() {
+    let mut map = BTreeMap::from_iter((0..MIN_INSERTS_HEIGHT_2 * 3).map(|i| (i * 2, ())));
+    let mut right = map.split_off(&(MIN_INSERTS_HEIGHT_2 + 1));
+    map.check();
+    right.check();
+    assert_eq!(map.nth(0), Some((&0, &())));
+    assert_eq!(map.nth(map.len() - 1), Some((&(MIN_INSERTS_HEIGHT_2 - 1), &())));
+    assert_eq!(right.nth(0), Some((&(MIN_INSERTS_HEIGHT_2 + 1), &())));
+    let key = MIN_INSERTS_HEIGHT_2 * 3;
+    assert_eq!(right.rank(&key), right.range(..key).count());
+
+    let mut odd = BTreeMap::from_iter((0..MIN_INSERTS_HEIGHT_2).map(|i| (i * 2 + 1, ())));
+    map.append(&mut odd);
+    map.append(&mut right);
+    map.check();
+    for (i, &k) in map.keys().enumerate() {
+        assert_eq!(map.nth(i), Some((&k, &())));
+        assert_eq!(map.rank(&k), i);
+    }
+}
+
 #[test]
 fn test_range_finding_ill_order_in_map() {
     let mut map = BTreeMap::new();
@@ -2308,6 +2358,87 @@ fn test_merge_conflict_drop_leak() {
     assert_eq!(c_val_right.dropped(), 1); // should be 2 were it not for Rust issue #47949
 }
 
+#[test]
+fn test_merge_rebuild() {
+    let mut a: BTreeMap<_, _> = (0..1000).step_by(2).map(|i| (i, i)).collect();
+    let b: BTreeMap<_, _> = (0..1000).step_by(3).map(|i| (i, i)).collect();
+    a.merge(b, |_, a_val, b_val| a_val + b_val);
+    a.check();
+    assert_eq!(a.len(), 667);
+    for (&k, &v) in &a {
+        assert_eq!(v, if k % 6 == 0 { 2 * k } else { k });
+    }
+
+    // The merged tree is as compact as it can get.
+    let mut compacted = a.clone();
+    compacted.compact();
+    assert_eq!(a.height(), compacted.height());
+}
+
+#[test]
+fn test_from_sorted_iter() {
+    for len in [1, node::CAPACITY, node::CAPACITY + 1, MIN_INSERTS_HEIGHT_2, 1000] {
+        let map = BTreeMap::from_sorted_iter((0..len).map(|i| (i, i * 2)));
+        map.check();
+        assert_eq!(map.len(), len);
+        assert!(map.into_iter().eq((0..len).map(|i| (i, i * 2))));
+    }
+
+    // Leaves are filled up to their capacity.
+    let map = BTreeMap::from_sorted_iter((0..143).map(|i| (i, ())));
+    assert_eq!(map.height(), Some(1));
+    let map = BTreeMap::from_sorted_iter((0..144).map(|i| (i, ())));
+    assert_eq!(map.height(), Some(2));
+}
+
+#[test]
+fn test_from_sorted_iter_duplicates() {
+    let map = BTreeMap::from_sorted_iter([(1, 'a'), (1, 'b'), (2, 'c'), (3, 'd'), (3, 'e')]);
+    map.check();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map[&1], 'b');
+    assert_eq!(map[&3], 'e');
+}
+
+#[test]
+#[should_panic(expected = "input is not sorted in ascending order")]
+fn test_from_sorted_iter_unsorted() {
+    BTreeMap::from_sorted_iter([(1, ()), (3, ()), (2, ())]);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_from_sorted_iter_unsorted_leak() {
+    let a = CrashTestDummy::new(0);
+    let b = CrashTestDummy::new(1);
+    let c = CrashTestDummy::new(2);
+    let pairs =
+        [(b.spawn(Panic::Never), ()), (c.spawn(Panic::Never), ()), (a.spawn(Panic::Never), ())];
+    catch_unwind(AssertUnwindSafe(|| BTreeMap::from_sorted_iter(pairs))).unwrap_err();
+    assert_eq!(a.dropped(), 1);
+    assert_eq!(b.dropped(), 1);
+    assert_eq!(c.dropped(), 1);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_merge_rebuild_conflict_panic() {
+    let mut a: BTreeMap<_, _> = (0..100).map(|i| (i, i)).collect();
+    let b: BTreeMap<_, _> = (0..100).step_by(5).map(|i| (i, i)).collect();
+    catch_unwind(AssertUnwindSafe(|| {
+        a.merge(b, |&key, _, _| if key == 50 { panic!("Panic in conflict function") } else { 0 })
+    }))
+    .unwrap_err();
+    a.check();
+
+    // Only the entry that `conflict` was called with when it panicked is lost.
+    assert_eq!(a.len(), 99);
+    assert!(a.keys().copied().eq((0..100).filter(|&i| i != 50)));
+    for (&k, &v) in &a {
+        assert_eq!(v, if k < 50 && k % 5 == 0 { 0 } else { k });
+    }
+}
+
 #[test]
 fn test_merge_ord_chaos() {
     let mut map1 = BTreeMap::new();
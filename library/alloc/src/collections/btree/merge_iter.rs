@@ -1,6 +1,7 @@
 use core::cmp::Ordering;
 use core::fmt::{self, Debug};
-use core::iter::FusedIterator;
+use core::iter::{self, FusedIterator};
+use core::option;
 
 /// Core of an iterator that merges the output of two strictly ascending iterators,
 /// for instance a union or a symmetric difference.
@@ -84,6 +85,16 @@ pub(super) fn nexts<Cmp: Fn(&I::Item, &I::Item) -> Ordering>(
         (a_next, b_next)
     }
 
+    /// Returns the items of the first source that haven't been returned yet,
+    /// discarding a peeked item of the second source.
+    pub(super) fn rest_of_a(&mut self) -> iter::Chain<option::IntoIter<I::Item>, &mut I> {
+        let peeked = match self.peeked.take() {
+            Some(Peeked::A(next)) => Some(next),
+            _ => None,
+        };
+        peeked.into_iter().chain(&mut self.a)
+    }
+
     /// Returns a pair of upper bounds for the `size_hint` of the final iterator.
     pub(super) fn lens(&self) -> (usize, usize)
     where
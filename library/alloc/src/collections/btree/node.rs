@@ -10,6 +10,7 @@
 //     keys: [K; 2 * B - 1],
 //     vals: [V; 2 * B - 1],
 //     edges: [if height > 0 { Box<Node<K, V, height - 1>> } else { () }; 2 * B],
+//     subtree_len: if height > 0 { usize } else { () },
 //     parent: Option<(NonNull<Node<K, V, height + 1>>, u16)>,
 //     len: u16,
 // }
@@ -30,6 +31,11 @@
 //   For a leaf node, "having an edge" only means we can identify a position in the node,
 //   since leaf edges are empty and need no data representation. In an internal node,
 //   an edge both identifies a position and contains a pointer to a child node.
+// - An internal node knows the number of key-value pairs in the subtree it heads,
+//   which is its own length plus the subtree lengths of all of its children.
+//   Operations that move key-value pairs or edges between nodes keep this up to date
+//   for the nodes they touch, but it's up to their callers to adjust the ancestors of
+//   a node that gained or lost key-value pairs.
 
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
@@ -108,6 +114,10 @@ struct InternalNode<K, V> {
     /// initialized and valid, except that near the end, while the tree is held
     /// through borrow type `Dying`, some of these pointers are dangling.
     edges: [MaybeUninit<BoxedNode<K, V>>; 2 * B],
+
+    /// The number of keys and values stored in this node and all of its descendants.
+    /// Leaf nodes don't need this, since for them it's the same as `len`.
+    subtree_len: usize,
 }
 
 impl<K, V> InternalNode<K, V> {
@@ -122,7 +132,9 @@ unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
             // SAFETY: argument points to the `node.data` `LeafNode`
             LeafNode::init(&raw mut (*node.as_mut_ptr()).data);
-            // SAFETY: `node.data` was just initialized and `node.edges` is MaybeUninit.
+            (&raw mut (*node.as_mut_ptr()).subtree_len).write(0);
+            // SAFETY: `node.data` and `node.subtree_len` were just initialized
+            // and `node.edges` is MaybeUninit.
             node.assume_init()
         }
     }
@@ -237,6 +249,7 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
     fn new_internal<A: Allocator + Clone>(child: Root<K, V>, alloc: A) -> Self {
         let mut new_node = unsafe { InternalNode::new(alloc) };
         new_node.edges[0].write(child.node);
+        new_node.subtree_len = child.subtree_len();
         NodeRef::from_new_internal(new_node, NonZero::new(child.height + 1).unwrap())
     }
 
@@ -299,6 +312,19 @@ pub(super) fn height(&self) -> usize {
         self.height
     }
 
+    /// Finds the number of keys or values in the node and all of its descendants.
+    /// Like `len`, calling this function can have the side effect of invalidating
+    /// mutable references that unsafe code has created.
+    pub(super) fn subtree_len(&self) -> usize {
+        if self.height == 0 {
+            self.len()
+        } else {
+            // We only access the `subtree_len` field, for the same reason as in `len`.
+            let internal = self.node.as_ptr() as *const InternalNode<K, V>;
+            unsafe { (*internal).subtree_len }
+        }
+    }
+
     /// Temporarily takes out another, immutable reference to the same node.
     pub(super) fn reborrow(&self) -> NodeRef<marker::Immut<'_>, K, V, Type> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
@@ -565,6 +591,76 @@ fn correct_all_childrens_parent_links(&mut self) {
         let len = self.len();
         unsafe { self.correct_childrens_parent_links(0..=len) };
     }
+
+    /// Recalculates the subtree length of the node from its length and the
+    /// subtree lengths of its children, which must be correct.
+    fn recalc_subtree_len(&mut self) {
+        let len = self.len();
+        let mut subtree_len = len;
+        for i in 0..=len {
+            subtree_len += unsafe { Handle::new_edge(self.reborrow(), i) }.descend().subtree_len();
+        }
+        unsafe { (*Self::as_internal_ptr(self)).subtree_len = subtree_len };
+    }
+}
+
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    /// Adjusts the subtree length of the node, unless it is a leaf, and of all of
+    /// its ancestors, after a key-value pair was added to or removed from the
+    /// subtree headed by the node.
+    pub(super) fn adjust_subtree_lens_upwards(self, added: bool) {
+        let mut node = self;
+        loop {
+            if node.height > 0 {
+                let internal = node.node.as_ptr() as *mut InternalNode<K, V>;
+                unsafe {
+                    if added {
+                        (*internal).subtree_len += 1;
+                    } else {
+                        (*internal).subtree_len -= 1;
+                    }
+                }
+            }
+            node = match node.ascend() {
+                Ok(parent) => parent.into_node().forget_type(),
+                Err(_) => return,
+            };
+        }
+    }
+
+    /// Recalculates the subtree lengths of all ancestors of the node, and of the
+    /// node itself unless it is a leaf, from the bottom up. Every other node
+    /// below those must have a correct subtree length.
+    pub(super) fn recalc_subtree_lens_upwards(self) {
+        let mut node = self;
+        loop {
+            if let ForceResult::Internal(mut internal) = unsafe { node.reborrow_mut() }.force() {
+                internal.recalc_subtree_len();
+            }
+            node = match node.ascend() {
+                Ok(parent) => parent.into_node().forget_type(),
+                Err(_) => return,
+            };
+        }
+    }
+
+    /// Recalculates the subtree lengths of all nodes in the subtree headed by
+    /// the node, and returns the subtree length of the node.
+    pub(super) fn recalc_all_subtree_lens(self) -> usize {
+        match self.force() {
+            ForceResult::Leaf(leaf) => leaf.len(),
+            ForceResult::Internal(mut internal) => {
+                let len = internal.len();
+                let mut subtree_len = len;
+                for i in 0..=len {
+                    let child = unsafe { Handle::new_edge(internal.reborrow_mut(), i) }.descend();
+                    subtree_len += child.recalc_all_subtree_lens();
+                }
+                unsafe { (*NodeRef::as_internal_ptr(&internal)).subtree_len = subtree_len };
+                subtree_len
+            }
+        }
+    }
 }
 
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
@@ -693,6 +789,9 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
     pub(super) fn push(&mut self, key: K, val: V, edge: Root<K, V>) {
         assert!(edge.height == self.height - 1);
 
+        let added_len = 1 + edge.subtree_len();
+        unsafe { (*Self::as_internal_ptr(self)).subtree_len += added_len };
+
         let len = self.len_mut();
         let idx = usize::from(*len);
         assert!(idx < CAPACITY);
@@ -1042,6 +1141,8 @@ fn insert<A: Allocator + Clone>(
                 },
             };
             insertion_edge.insert_fit(key, val, edge);
+            result.left.recalc_subtree_len();
+            result.right.borrow_mut().recalc_subtree_len();
             Some(result)
         }
     }
@@ -1063,19 +1164,36 @@ pub(super) fn insert_recursing<A: Allocator + Clone>(
         split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal>),
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
         let (mut split, handle) = match self.insert(key, value, alloc.clone()) {
-            // SAFETY: we have finished splitting and can now re-awaken the
-            // handle to the inserted element.
-            (None, handle) => return unsafe { handle.awaken() },
+            (None, handle) => {
+                // SAFETY: we have finished splitting and can now re-awaken the
+                // handle to the inserted element.
+                let mut handle = unsafe { handle.awaken() };
+                unsafe { handle.reborrow_mut() }
+                    .into_node()
+                    .forget_type()
+                    .adjust_subtree_lens_upwards(true);
+                return handle;
+            }
             (Some(split), handle) => (split.forget_node_type(), handle),
         };
 
         loop {
             split = match split.left.ascend() {
                 Ok(parent) => {
+                    // The nodes split so far have correct subtree lengths, but the
+                    // node that ends up with room for the split off part and all of
+                    // its ancestors still need to account for the new element.
+                    let dormant_parent = parent.dormant();
                     match parent.insert(split.kv.0, split.kv.1, split.right, alloc.clone()) {
-                        // SAFETY: we have finished splitting and can now re-awaken the
-                        // handle to the inserted element.
-                        None => return unsafe { handle.awaken() },
+                        None => {
+                            // SAFETY: `parent` has been consumed without splitting
+                            // its node, so the node is still in place.
+                            let parent = unsafe { dormant_parent.awaken() };
+                            parent.into_node().forget_type().adjust_subtree_lens_upwards(true);
+                            // SAFETY: we have finished splitting and can now re-awaken the
+                            // handle to the inserted element.
+                            return unsafe { handle.awaken() };
+                        }
                         Some(split) => split.forget_node_type(),
                     }
                 }
@@ -1445,6 +1563,7 @@ fn do_merge<
                 );
 
                 left_node.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
+                left_node.recalc_subtree_len();
 
                 alloc.deallocate(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
             } else {
@@ -1580,6 +1699,9 @@ pub(super) fn bulk_steal_left(&mut self, count: usize) {
                     );
 
                     right.correct_childrens_parent_links(0..new_right_len + 1);
+
+                    left.recalc_subtree_len();
+                    right.recalc_subtree_len();
                 }
                 (ForceResult::Leaf(_), ForceResult::Leaf(_)) => {}
                 _ => unreachable!(),
@@ -1644,6 +1766,9 @@ pub(super) fn bulk_steal_right(&mut self, count: usize) {
 
                     left.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
                     right.correct_childrens_parent_links(0..new_right_len + 1);
+
+                    left.recalc_subtree_len();
+                    right.recalc_subtree_len();
                 }
                 (ForceResult::Leaf(_), ForceResult::Leaf(_)) => {}
                 _ => unreachable!(),
@@ -1708,6 +1833,9 @@ pub(super) unsafe fn cast_to_leaf_unchecked(
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
     /// Move the suffix after `self` from one node to another one. `right` must be empty.
     /// The first edge of `right` remains unchanged.
+    ///
+    /// Does not update the subtree lengths of either node, since the children
+    /// usually get changed next.
     pub(super) fn move_suffix(
         &mut self,
         right: &mut NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
@@ -29,6 +29,9 @@ fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
         alloc: A,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         let (old_kv, mut pos) = self.remove();
+        // Account for the removal before rebalancing, which relies on correct
+        // subtree lengths in the children of the nodes it changes.
+        unsafe { pos.reborrow_mut() }.into_node().forget_type().adjust_subtree_lens_upwards(false);
         let len = pos.reborrow().into_node().len();
         if len < MIN_LEN {
             let idx = pos.idx();
@@ -184,6 +184,73 @@ pub(super) fn find_upper_bound_edge<'r, Q>(
     }
 }
 
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+    /// Looks up the key-value pair at a given position, counting from zero in
+    /// ascending order, in a (sub)tree headed by the node. Returns `None` if
+    /// the tree doesn't have that many key-value pairs.
+    pub(super) fn search_tree_by_rank(mut self, mut rank: usize) -> Option<(&'a K, &'a V)> {
+        loop {
+            let internal = match self.force() {
+                Leaf(leaf) if rank < leaf.len() => {
+                    return Some(unsafe { Handle::new_kv(leaf, rank) }.into_kv());
+                }
+                Leaf(_) => return None,
+                Internal(internal) => internal,
+            };
+            let mut edge = internal.first_edge();
+            self = loop {
+                let child = edge.descend();
+                let child_len = child.subtree_len();
+                if rank < child_len {
+                    break child;
+                }
+                rank -= child_len;
+                let kv = edge.right_kv().ok()?;
+                if rank == 0 {
+                    return Some(kv.into_kv());
+                }
+                rank -= 1;
+                edge = kv.right_edge();
+            };
+        }
+    }
+
+    /// Counts the key-value pairs in a (sub)tree headed by the node whose keys
+    /// are less than the given key.
+    ///
+    /// The result is meaningful only if the tree is ordered by key, like the tree
+    /// in a `BTreeMap` is.
+    pub(super) fn rank<Q: ?Sized>(mut self, key: &Q) -> usize
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut rank = 0;
+        loop {
+            let (idx, found) = match unsafe { self.find_key_index(key, 0) } {
+                IndexResult::KV(idx) => (idx, true),
+                IndexResult::Edge(idx) => (idx, false),
+            };
+            // All keys to the left of the index are less than the key.
+            rank += idx;
+            let internal = match self.force() {
+                Leaf(_) => return rank,
+                Internal(internal) => internal,
+            };
+            for i in 0..idx {
+                rank += unsafe { Handle::new_edge(internal, i) }.descend().subtree_len();
+            }
+            // The child at the index holds keys that are less than the key if
+            // the key was found, or that still need to be compared otherwise.
+            let child = unsafe { Handle::new_edge(internal, idx) }.descend();
+            if found {
+                return rank + child.subtree_len();
+            }
+            self = child;
+        }
+    }
+}
+
 impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
     /// Looks up a given key in the node, without recursion.
     /// Returns a `Found` with the handle of the matching KV, if any. Otherwise,
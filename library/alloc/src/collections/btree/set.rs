@@ -344,6 +344,40 @@ impl<T> BTreeSet<T> {
     pub const fn new() -> BTreeSet<T> {
         BTreeSet { map: BTreeMap::new() }
     }
+
+    /// Makes a `BTreeSet` from an iterator of values in sorted order, in
+    /// linear time.
+    ///
+    /// Unlike collecting into a `BTreeSet`, this does not sort the values
+    /// first, and it builds nodes that are filled up to their capacity, like
+    /// collecting does. If several values are equal, only the last one is
+    /// kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not in ascending order. The values taken from
+    /// the iterator up to that point are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_from_sorted)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_sorted_iter([1, 2, 2, 3]);
+    ///
+    /// assert_eq!(set.len(), 3);
+    /// assert!(set.contains(&2));
+    /// ```
+    #[unstable(feature = "btree_from_sorted", issue = "none")]
+    pub fn from_sorted_iter<I>(iter: I) -> BTreeSet<T>
+    where
+        T: Ord,
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter().map(|k| (k, SetValZST::default()));
+        BTreeSet { map: BTreeMap::from_sorted_iter(iter) }
+    }
 }
 
 impl<T, A: Allocator + Clone> BTreeSet<T, A> {
@@ -768,6 +802,63 @@ pub fn is_superset(&self, other: &BTreeSet<T, A>) -> bool
         other.is_subset(self)
     }
 
+    /// Returns a reference to the element at the given position in the set,
+    /// counting from zero in ascending order, or `None` if `n` is not less
+    /// than the length of the set.
+    ///
+    /// Unlike `set.iter().nth(n)`, this takes logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from([30, 10, 20]);
+    /// assert_eq!(set.nth(0), Some(&10));
+    /// assert_eq!(set.nth(2), Some(&30));
+    /// assert_eq!(set.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn nth(&self, n: usize) -> Option<&T> {
+        self.map.nth(n).map(|(k, _)| k)
+    }
+
+    /// Returns the number of elements in the set that are less than the given
+    /// value.
+    ///
+    /// If the set contains the value, this is its position in the set,
+    /// counting from zero in ascending order, so that [`nth`] returns it.
+    /// Otherwise, it is the position the value would have if it was inserted.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    ///
+    /// [`nth`]: BTreeSet::nth
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from([30, 10, 20]);
+    /// assert_eq!(set.rank(&20), 1);
+    /// assert_eq!(set.rank(&25), 2);
+    /// assert_eq!(set.rank(&5), 0);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn rank<Q: ?Sized>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.rank(value)
+    }
+
     /// Returns a reference to the first element in the set, if any.
     /// This element is always the minimum of all elements in the set.
     ///
@@ -1476,12 +1567,12 @@ fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BTreeSet<T> {
 
         // use stable sort to preserve the insertion order.
         inputs.sort();
-        BTreeSet::from_sorted_iter(inputs.into_iter(), Global)
+        BTreeSet::bulk_build_from_sorted_iter(inputs.into_iter(), Global)
     }
 }
 
 impl<T: Ord, A: Allocator + Clone> BTreeSet<T, A> {
-    fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A> {
+    fn bulk_build_from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A> {
         let iter = iter.map(|k| (k, SetValZST::default()));
         let map = BTreeMap::bulk_build_from_sorted_iter(iter, alloc);
         BTreeSet { map }
@@ -1511,7 +1602,7 @@ fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A>
 
         // use stable sort to preserve the insertion order.
         arr.sort();
-        BTreeSet::from_sorted_iter(IntoIterator::into_iter(arr), Global)
+        BTreeSet::bulk_build_from_sorted_iter(IntoIterator::into_iter(arr), Global)
     }
 }
 
@@ -1659,7 +1750,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> Sub<&BTreeSet<T, A>> for &BTreeSet<T,
     /// assert_eq!(result, BTreeSet::from([1, 2]));
     /// ```
     fn sub(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
-        BTreeSet::from_sorted_iter(
+        BTreeSet::bulk_build_from_sorted_iter(
             self.difference(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
         )
@@ -1684,7 +1775,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> BitXor<&BTreeSet<T, A>> for &BTreeSet
     /// assert_eq!(result, BTreeSet::from([1, 4]));
     /// ```
     fn bitxor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
-        BTreeSet::from_sorted_iter(
+        BTreeSet::bulk_build_from_sorted_iter(
             self.symmetric_difference(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
         )
@@ -1709,7 +1800,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> BitAnd<&BTreeSet<T, A>> for &BTreeSet
     /// assert_eq!(result, BTreeSet::from([2, 3]));
     /// ```
     fn bitand(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
-        BTreeSet::from_sorted_iter(
+        BTreeSet::bulk_build_from_sorted_iter(
             self.intersection(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
         )
@@ -1734,7 +1825,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> BitOr<&BTreeSet<T, A>> for &BTreeSet<
     /// assert_eq!(result, BTreeSet::from([1, 2, 3, 4, 5]));
     /// ```
     fn bitor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
-        BTreeSet::from_sorted_iter(
+        BTreeSet::bulk_build_from_sorted_iter(
             self.union(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
         )
@@ -854,3 +854,28 @@ fn test_range_panic_2() {
 
     let _invalid_range = set.range((Excluded(&5), Excluded(&5)));
 }
+
+#[test]
+fn test_nth_and_rank() {
+    let set: BTreeSet<i32> = (0..100).map(|i| i * 3).collect();
+    for i in 0..100 {
+        assert_eq!(set.nth(i as usize), Some(&(i * 3)));
+        assert_eq!(set.rank(&(i * 3)), i as usize);
+        assert_eq!(set.rank(&(i * 3 + 1)), i as usize + 1);
+    }
+    assert_eq!(set.nth(100), None);
+    assert_eq!(set.rank(&-1), 0);
+}
+
+#[test]
+fn test_from_sorted_iter() {
+    let set = BTreeSet::from_sorted_iter([1, 2, 2, 3, 5, 5]);
+    assert_eq!(set.len(), 4);
+    assert!(set.iter().eq([1, 2, 3, 5].iter()));
+}
+
+#[test]
+#[should_panic(expected = "input is not sorted in ascending order")]
+fn test_from_sorted_iter_unsorted() {
+    BTreeSet::from_sorted_iter([2, 1]);
+}
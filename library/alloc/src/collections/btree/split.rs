@@ -58,7 +58,12 @@ pub(super) fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(
                     left_node = edge.descend();
                     right_node = node.first_edge().descend();
                 }
-                (Leaf(_), Leaf(_)) => break,
+                (Leaf(edge), Leaf(node)) => {
+                    // Only the nodes along the split have changed.
+                    edge.into_node().forget_type().recalc_subtree_lens_upwards();
+                    node.forget_type().recalc_subtree_lens_upwards();
+                    break;
+                }
                 _ => unreachable!(),
             }
         }
@@ -7,6 +7,21 @@
 const HI_USIZE: usize = usize::repeat_u8(0x80);
 const USIZE_BYTES: usize = size_of::<usize>();
 
+/// Whether the vectorized byte and substring searches are used, i.e. whether
+/// the target has 128-bit vectors from which a bitmask of the result of a
+/// comparison is cheap to extract. This is used by `str::pattern` too.
+pub(crate) const USE_SIMD: bool = cfg_select! {
+    any(
+        all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+        all(target_arch = "loongarch64", target_feature = "lsx"),
+        all(any(target_arch = "arm", target_arch = "aarch64"), target_feature = "neon"),
+        all(any(target_arch = "powerpc", target_arch = "powerpc64"), target_feature = "altivec"),
+        all(any(target_arch = "riscv32", target_arch = "riscv64"), target_feature = "v"),
+        all(target_arch = "wasm32", target_feature = "simd128"),
+    ) => true,
+    _ => false,
+};
+
 /// Returns `true` if `x` contains any zero byte.
 ///
 /// From *Matters Computational*, J. Arndt:
@@ -56,6 +71,10 @@ const fn memchr_aligned(x: u8, text: &[u8]) -> Option<usize> {
         if const {
             memchr_naive(x, text)
         } else {
+            if USE_SIMD && text.len() >= simd::Block::LEN {
+                return simd::memchr(x, text);
+            }
+
             // Scan for a single byte value by reading two `usize` words at a time.
             //
             // Split `text` in three parts
@@ -109,6 +128,10 @@ const fn memchr_aligned(x: u8, text: &[u8]) -> Option<usize> {
 /// Returns the last index matching the byte `x` in `text`.
 #[must_use]
 pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+    if USE_SIMD && text.len() >= simd::Block::LEN {
+        return simd::memrchr(x, text);
+    }
+
     // Scan for a single byte value by reading two `usize` words at a time.
     //
     // Split `text` in three parts:
@@ -159,3 +182,81 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     // Find the byte before the point the body loop stopped.
     text[..offset].iter().rposition(|elt| *elt == x)
 }
+
+/// Byte search that compares a whole vector of bytes at a time, used if
+/// [`USE_SIMD`] is set.
+///
+/// The vectors are read unaligned. The last vector (or the first one, when
+/// searching backwards) is aligned with the end of the text rather than
+/// following the previous one, so it overlaps with bytes that were already
+/// searched, which contain no match.
+mod simd {
+    use crate::simd::cmp::SimdPartialEq;
+    pub(super) use crate::simd::u8x16 as Block;
+
+    /// Returns a bitmask of the bytes equal to `x` in the block at `idx`,
+    /// with the first byte in the lowest bit.
+    ///
+    /// # Safety
+    ///
+    /// `idx + Block::LEN` must not be greater than `text.len()`.
+    #[inline]
+    unsafe fn test_block(x: Block, text: &[u8], idx: usize) -> u16 {
+        // SAFETY: the caller ensures that `Block::LEN` bytes are readable at `idx`.
+        let block: Block = unsafe { text.as_ptr().add(idx).cast::<Block>().read_unaligned() };
+        block.simd_eq(x).to_bitmask() as u16
+    }
+
+    /// Returns the first index matching the byte `x` in `text`, which must
+    /// be at least `Block::LEN` bytes long.
+    #[inline]
+    pub(super) fn memchr(x: u8, text: &[u8]) -> Option<usize> {
+        debug_assert!(text.len() >= Block::LEN);
+        let x = Block::splat(x);
+        let mut idx = 0;
+        while idx + Block::LEN <= text.len() {
+            // SAFETY: the loop condition ensures that the block is in bounds.
+            let mask = unsafe { test_block(x, text, idx) };
+            if mask != 0 {
+                return Some(idx + mask.trailing_zeros() as usize);
+            }
+            idx += Block::LEN;
+        }
+        if idx < text.len() {
+            let idx = text.len() - Block::LEN;
+            // SAFETY: `text` is at least `Block::LEN` bytes long.
+            let mask = unsafe { test_block(x, text, idx) };
+            if mask != 0 {
+                return Some(idx + mask.trailing_zeros() as usize);
+            }
+        }
+        None
+    }
+
+    /// Returns the last index matching the byte `x` in `text`, which must
+    /// be at least `Block::LEN` bytes long.
+    #[inline]
+    pub(super) fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+        debug_assert!(text.len() >= Block::LEN);
+        let x = Block::splat(x);
+        let last_bit = Block::LEN - 1;
+        let mut end = text.len();
+        while end >= Block::LEN {
+            let idx = end - Block::LEN;
+            // SAFETY: the loop condition ensures that the block is in bounds.
+            let mask = unsafe { test_block(x, text, idx) };
+            if mask != 0 {
+                return Some(idx + last_bit - mask.leading_zeros() as usize);
+            }
+            end = idx;
+        }
+        if end > 0 {
+            // SAFETY: `text` is at least `Block::LEN` bytes long.
+            let mask = unsafe { test_block(x, text, 0) };
+            if mask != 0 {
+                return Some(last_bit - mask.leading_zeros() as usize);
+            }
+        }
+        None
+    }
+}
@@ -995,14 +995,9 @@ fn is_contained_in(self, haystack: &str) -> bool {
                     return haystack.as_bytes().contains(&self.as_bytes()[0]);
                 }
 
-                #[cfg(any(
-                    all(target_arch = "x86_64", target_feature = "sse2"),
-                    all(target_arch = "loongarch64", target_feature = "lsx"),
-                    all(target_arch = "aarch64", target_feature = "neon")
-                ))]
-                if self.len() <= 32 {
-                    if let Some(result) = simd_contains(self, haystack) {
-                        return result;
+                if memchr::USE_SIMD && self.len() <= 32 {
+                    if let Some(result) = simd_find(self.as_bytes(), haystack.as_bytes()) {
+                        return result.is_some();
                     }
                 }
 
@@ -1178,6 +1173,21 @@ fn next_match(&mut self) -> Option<(usize, usize)> {
                 }
             },
             StrSearcherImpl::TwoWay(ref mut searcher) => {
+                // Short needles are found faster by scanning for their bytes,
+                // which finds the same leftmost match as two-way does.
+                let needle = self.needle.as_bytes();
+                let haystack = &self.haystack.as_bytes()[searcher.position..];
+                if let [byte] = *needle {
+                    let found = memchr::memchr(byte, haystack);
+                    return searcher.skip_to_match(found, 1, self.haystack.len());
+                }
+
+                if memchr::USE_SIMD && needle.len() <= 32 {
+                    if let Some(found) = simd_find(needle, haystack) {
+                        return searcher.skip_to_match(found, needle.len(), self.haystack.len());
+                    }
+                }
+
                 let is_long = searcher.memory == usize::MAX;
                 // write out `true` and `false` cases to encourage the compiler
                 // to specialize the two cases separately.
@@ -1452,6 +1462,28 @@ fn byteset_contains(&self, byte: u8) -> bool {
         (self.byteset >> ((byte & 0x3f) as usize)) & 1 != 0
     }
 
+    /// Moves the cursor past a match that was found at `offset` from it by
+    /// another search method, or to the end of the haystack if there is none,
+    /// as `next::<MatchOnly>` would have done.
+    #[inline]
+    fn skip_to_match(
+        &mut self,
+        offset: Option<usize>,
+        needle_len: usize,
+        haystack_len: usize,
+    ) -> Option<(usize, usize)> {
+        let Some(offset) = offset else {
+            self.position = haystack_len;
+            return None;
+        };
+        let match_pos = self.position + offset;
+        self.position = match_pos + needle_len;
+        if self.memory != usize::MAX {
+            self.memory = 0;
+        }
+        Some((match_pos, match_pos + needle_len))
+    }
+
     // One of the main ideas of Two-Way is that we factorize the needle into
     // two halves, (u, v), and begin trying to find v in the haystack by scanning
     // left to right. If v matches, we try to match u by scanning right to left.
@@ -1769,28 +1801,23 @@ fn matching(a: usize, b: usize) -> Self::Output {
 /// does) by probing the first and last byte of the needle for the whole vector width
 /// and only doing full needle comparisons when the vectorized probe indicated potential matches.
 ///
+/// Returns the index of the first match, or `None` (in the outer option) if the needle
+/// consists of bytes that would make the probes degenerate and another search method
+/// should be used.
+///
 /// Since the x86_64 baseline only offers SSE2 we only use u8x16 here.
-/// If we ever ship std with for x86-64-v3 or adapt this for other platforms then wider vectors
-/// should be evaluated.
+/// If we ever ship std with for x86-64-v3 then wider vectors should be evaluated.
 ///
-/// Similarly, on LoongArch the 128-bit LSX vector extension is the baseline,
-/// so we also use `u8x16` there. Wider vector widths may be considered
-/// for future LoongArch extensions (e.g., LASX).
+/// Similarly, the other targets on which this is used (see [`memchr::USE_SIMD`]) have 128-bit
+/// vectors as their baseline, so we also use `u8x16` there. Wider vector widths may be
+/// considered for future extensions (e.g., LASX on LoongArch).
 ///
 /// For haystacks smaller than vector-size + needle length it falls back to
 /// a naive O(n*m) search so this implementation should not be called on larger needles.
 ///
 /// [0]: http://0x80.pl/articles/simd-strfind.html#sse-avx2
-#[cfg(any(
-    all(target_arch = "x86_64", target_feature = "sse2"),
-    all(target_arch = "loongarch64", target_feature = "lsx"),
-    all(target_arch = "aarch64", target_feature = "neon")
-))]
 #[inline]
-fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
-    let needle = needle.as_bytes();
-    let haystack = haystack.as_bytes();
-
+fn simd_find(needle: &[u8], haystack: &[u8]) -> Option<Option<usize>> {
     debug_assert!(needle.len() > 1);
 
     use crate::ops::BitAnd;
@@ -1819,7 +1846,7 @@ fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
 
     // do a naive search if the haystack is too small to fit
     if haystack.len() < Block::LEN + last_byte_offset {
-        return Some(haystack.windows(needle.len()).any(|c| c == needle));
+        return Some(haystack.windows(needle.len()).position(|c| c == needle));
     }
 
     let first_probe: Block = Block::splat(first_probe);
@@ -1830,14 +1857,11 @@ fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
 
     // this #[cold] is load-bearing, benchmark before removing it...
     let check_mask = #[cold]
-    |idx, mask: u16, skip: bool| -> bool {
-        if skip {
-            return false;
-        }
-
+    |idx, mask: u16| -> Option<usize> {
         // and so is this. optimizations are weird.
         let mut mask = mask;
 
+        // The lowest bits are checked first, so this finds the first match in the chunk.
         while mask != 0 {
             let trailing = mask.trailing_zeros();
             let offset = idx + trailing as usize + 1;
@@ -1846,12 +1870,12 @@ fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
             unsafe {
                 let sub = haystack.get_unchecked(offset..).get_unchecked(..trimmed_needle.len());
                 if small_slice_eq(sub, trimmed_needle) {
-                    return true;
+                    return Some(offset - 1);
                 }
             }
             mask &= !(1 << trailing);
         }
-        false
+        None
     };
 
     let test_chunk = |idx| -> u16 {
@@ -1871,27 +1895,31 @@ fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
     };
 
     let mut i = 0;
-    let mut result = false;
     // The loop condition must ensure that there's enough headroom to read LANE bytes,
     // and not only at the current index but also at the index shifted by block_offset
     const UNROLL: usize = 4;
-    while i + last_byte_offset + UNROLL * Block::LEN < haystack.len() && !result {
+    while i + last_byte_offset + UNROLL * Block::LEN < haystack.len() {
         let mut masks = [0u16; UNROLL];
         for j in 0..UNROLL {
             masks[j] = test_chunk(i + j * Block::LEN);
         }
+        // Check the chunks in order, so that the first match is found.
         for j in 0..UNROLL {
             let mask = masks[j];
             if mask != 0 {
-                result |= check_mask(i + j * Block::LEN, mask, result);
+                if let Some(found) = check_mask(i + j * Block::LEN, mask) {
+                    return Some(Some(found));
+                }
             }
         }
         i += UNROLL * Block::LEN;
     }
-    while i + last_byte_offset + Block::LEN < haystack.len() && !result {
+    while i + last_byte_offset + Block::LEN < haystack.len() {
         let mask = test_chunk(i);
         if mask != 0 {
-            result |= check_mask(i, mask, result);
+            if let Some(found) = check_mask(i, mask) {
+                return Some(Some(found));
+            }
         }
         i += Block::LEN;
     }
@@ -1899,14 +1927,16 @@ fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
     // Process the tail that didn't fit into LANES-sized steps.
     // This simply repeats the same procedure but as right-aligned chunk instead
     // of a left-aligned one. The last byte must be exactly flush with the string end so
-    // we don't miss a single byte or read out of bounds.
+    // we don't miss a single byte or read out of bounds. The part of the chunk that
+    // overlaps with the previous ones has no matches, so the first match in it is
+    // still the first match overall.
     let i = haystack.len() - last_byte_offset - Block::LEN;
     let mask = test_chunk(i);
     if mask != 0 {
-        result |= check_mask(i, mask, result);
+        return Some(check_mask(i, mask));
     }
 
-    Some(result)
+    Some(None)
 }
 
 /// Compares short slices for equality.
@@ -1917,11 +1947,6 @@ fn simd_contains(needle: &str, haystack: &str) -> Option<bool> {
 /// # Safety
 ///
 /// Both slices must have the same length.
-#[cfg(any(
-    all(target_arch = "x86_64", target_feature = "sse2"),
-    all(target_arch = "loongarch64", target_feature = "lsx"),
-    all(target_arch = "aarch64", target_feature = "neon")
-))]
 #[inline]
 unsafe fn small_slice_eq(x: &[u8], y: &[u8]) -> bool {
     debug_assert_eq!(x.len(), y.len());
@@ -501,3 +501,61 @@ fn double_ended_regression_test() {
         [InRange(10, 13), Rejects(13, 14), InRange(37, 40), Rejects(34, 37), Done]
     );
 }
+
+// Returns the start of each non-overlapping occurrence of `needle`, found the
+// slowest possible way.
+fn naive_match_indices(haystack: &str, needle: &str) -> Vec<usize> {
+    let (haystack, needle) = (haystack.as_bytes(), needle.as_bytes());
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= haystack.len() {
+        if &haystack[i..i + needle.len()] == needle {
+            result.push(i);
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    result
+}
+
+#[test]
+fn test_short_needles_long_haystack() {
+    // Long enough to be searched a vector at a time, with matches at every
+    // offset within a vector and across the ends of vectors.
+    let mut haystack = String::new();
+    for i in 0..40 {
+        haystack.push_str(&"ab".repeat(i % 7));
+        haystack.push_str("abc");
+        haystack.push_str(&"x".repeat(i));
+        haystack.push('é');
+    }
+    for needle in
+        ["a", "c", "é", "ab", "ba", "bab", "abc", "cxxx", "xxxxxxxxxx", "xé", "aaaa", "abcd"]
+    {
+        let expected = naive_match_indices(&haystack, needle);
+        let found: Vec<usize> = haystack.match_indices(needle).map(|(i, _)| i).collect();
+        assert_eq!(found, expected, "needle {needle:?}");
+        assert_eq!(haystack.find(needle), expected.first().copied(), "needle {needle:?}");
+        assert_eq!(haystack.split(needle).count(), expected.len() + 1, "needle {needle:?}");
+    }
+}
+
+#[test]
+fn test_str_searcher_next_after_next_match() {
+    let haystack = "xxxxxxxxxxxxxxxxxxxxabxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxabxxé";
+    search_asserts!(
+        haystack,
+        "ab",
+        "next after a match found by next_match",
+        [next_match, next, next_match, next_match],
+        [InRange(20, 22), Rejects(22, 24), InRange(52, 54), Done]
+    );
+    search_asserts!(
+        haystack,
+        "x",
+        "next after a single byte match found by next_match",
+        [next_match, next, next_match],
+        [InRange(0, 1), Matches(1, 2), InRange(2, 3)]
+    );
+}
@@ -1826,6 +1826,31 @@ fn each_alignment_reversed() {
             assert_eq!(Some(pos - start), memrchr(needle, &data[start..]));
         }
     }
+
+    #[test]
+    fn each_position_long() {
+        let mut data = [1u8; 80];
+        for len in 16..data.len() {
+            for pos in 0..len {
+                data[pos] = 2;
+                assert_eq!(Some(pos), memchr(2, &data[..len]));
+                assert_eq!(Some(pos), memrchr(2, &data[..len]));
+                data[pos] = 1;
+            }
+            assert_eq!(None, memchr(2, &data[..len]));
+            assert_eq!(None, memrchr(2, &data[..len]));
+        }
+    }
+
+    #[test]
+    fn first_and_last_of_many_long() {
+        let data: Vec<u8> = (0..100).map(|i| if i % 17 == 3 { 0 } else { 1 }).collect();
+        for start in 0..16 {
+            let text = &data[start..];
+            assert_eq!(text.iter().position(|&b| b == 0), memchr(0, text));
+            assert_eq!(text.iter().rposition(|&b| b == 0), memrchr(0, text));
+        }
+    }
 }
 
 #[test]
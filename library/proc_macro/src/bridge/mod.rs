@@ -13,7 +13,8 @@
 use std::sync::Once;
 use std::{fmt, marker, mem, panic, thread};
 
-use crate::{Delimiter, Level};
+use crate::context::Edition;
+use crate::{Applicability, Delimiter, Level};
 
 /// Higher-order macro describing the server RPC API, allowing automatic
 /// generation of type-safe Rust APIs, both client-side and server-side.
@@ -39,6 +40,11 @@ macro_rules! with_api {
             fn track_path(path: &str);
             fn literal_from_str(s: &str) -> Result<Literal<$Span, $Symbol>, String>;
             fn emit_diagnostic(diagnostic: Diagnostic<$Span>);
+            fn expansion_crate_name() -> String;
+            fn expansion_edition() -> Edition;
+            fn expansion_cfgs() -> Vec<Cfg>;
+            fn expansion_module_path() -> String;
+            fn expansion_item_path() -> String;
 
             fn ts_drop(stream: $TokenStream);
             fn ts_clone(stream: &$TokenStream) -> $TokenStream;
@@ -216,6 +222,9 @@ fn unmark(self) -> Self::Unmarked {
     Delimiter,
     LitKind,
     Level,
+    Applicability,
+    Edition,
+    Cfg,
     Bound<usize>,
     Range<usize>,
 }
@@ -236,6 +245,23 @@ enum Level {
         Help,
     }
 );
+rpc_encode_decode!(
+    enum Edition {
+        Edition2015,
+        Edition2018,
+        Edition2021,
+        Edition2024,
+        EditionFuture,
+    }
+);
+rpc_encode_decode!(
+    enum Applicability {
+        MachineApplicable,
+        MaybeIncorrect,
+        HasPlaceholders,
+        Unspecified,
+    }
+);
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum LitKind {
@@ -407,16 +433,48 @@ enum TokenTree<TokenStream, Span, Symbol> {
     }
 );
 
+#[derive(Clone, Debug)]
+pub struct Cfg {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+rpc_encode_decode!(
+    struct Cfg { name, value }
+);
+
+#[derive(Clone, Debug)]
+pub struct SuggestionPart<Span> {
+    pub span: Span,
+    pub snippet: String,
+}
+
+compound_traits!(
+    struct SuggestionPart<Span> { span, snippet }
+);
+
+#[derive(Clone, Debug)]
+pub struct Suggestion<Span> {
+    pub message: String,
+    pub parts: Vec<SuggestionPart<Span>>,
+    pub applicability: Applicability,
+}
+
+compound_traits!(
+    struct Suggestion<Span> { message, parts, applicability }
+);
+
 #[derive(Clone, Debug)]
 pub struct Diagnostic<Span> {
     pub level: Level,
     pub message: String,
     pub spans: Vec<Span>,
     pub children: Vec<Diagnostic<Span>>,
+    pub suggestions: Vec<Suggestion<Span>>,
 }
 
 compound_traits!(
-    struct Diagnostic<Span> { level, message, spans, children }
+    struct Diagnostic<Span> { level, message, spans, children, suggestions }
 );
 
 /// Globals provided alongside the initial inputs for a macro expansion.
@@ -0,0 +1,99 @@
+//! Read-only information about the context a procedural macro is expanded in.
+//!
+//! These functions describe the crate being compiled and the location of the
+//! macro invocation within it. They can only be called while a procedural
+//! macro is being expanded, and panic otherwise.
+
+use crate::BridgeMethods;
+
+/// An edition of the Rust language.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Edition {
+    /// The 2015 edition.
+    Edition2015,
+    /// The 2018 edition.
+    Edition2018,
+    /// The 2021 edition.
+    Edition2021,
+    /// The 2024 edition.
+    Edition2024,
+    /// An unstable edition that has not been released yet.
+    EditionFuture,
+}
+
+/// A configuration option that is set for the crate being compiled, as
+/// tested by `#[cfg]` and `cfg!`.
+///
+/// Options like `unix` have only a name, while options like
+/// `target_os = "linux"` also have a value.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cfg {
+    name: String,
+    value: Option<String>,
+}
+
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+impl Cfg {
+    /// Returns the name of this option, for example `target_os`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of this option, for example `linux` for
+    /// `target_os = "linux"`, or `None` if it doesn't have one.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
+
+/// Returns the name of the crate being compiled, as it appears in paths.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+pub fn crate_name() -> String {
+    BridgeMethods::expansion_crate_name()
+}
+
+/// Returns the edition of the crate being compiled.
+///
+/// Note that the edition of the code passed to the macro can differ from it,
+/// for example if it comes from a `macro_rules!` macro defined in another
+/// crate. Use `Span` hygiene to handle such code correctly.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+pub fn edition() -> Edition {
+    BridgeMethods::expansion_edition()
+}
+
+/// Returns the configuration options that are set for the crate being
+/// compiled.
+///
+/// This includes the options set with `--cfg` on the command line as well as
+/// the ones derived from the target and the compiler flags.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+pub fn cfgs() -> Vec<Cfg> {
+    BridgeMethods::expansion_cfgs()
+        .into_iter()
+        .map(|cfg| Cfg { name: cfg.name, value: cfg.value })
+        .collect()
+}
+
+/// Returns the path of the module containing the macro invocation, starting
+/// with the crate name, like `module_path!()` does.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+pub fn module_path() -> String {
+    BridgeMethods::expansion_module_path()
+}
+
+/// Returns the path of the item containing the macro invocation, starting with
+/// the crate name.
+///
+/// For a macro invoked inside a function body, this is the path of the
+/// function. For an attribute or derive macro, this is the path of the item
+/// or module containing the item the macro is applied to, not of the item
+/// itself. Items without a name, such as `impl` blocks, are represented with
+/// a placeholder like `{impl#0}`.
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+pub fn item_path() -> String {
+    BridgeMethods::expansion_item_path()
+}
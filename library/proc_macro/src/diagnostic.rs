@@ -15,6 +15,58 @@ pub enum Level {
     Help,
 }
 
+/// How confident a proc macro is that a suggestion is what the user intended.
+///
+/// Tools such as `cargo fix` only apply suggestions that are
+/// [`MachineApplicable`](Applicability::MachineApplicable) automatically.
+#[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended, or maintains the
+    /// exact meaning of the code. It may be applied automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is uncertain. It
+    /// should result in valid code if it is applied.
+    MaybeIncorrect,
+    /// The suggestion contains placeholders like `(...)` or `{ /* fields */ }`
+    /// that the user has to fill in. It will not result in valid code if it is
+    /// applied as is.
+    HasPlaceholders,
+    /// The applicability of the suggestion is unknown.
+    Unspecified,
+}
+
+/// A suggested edit of the user's code, attached to a [`Diagnostic`].
+///
+/// A suggestion replaces the code at one or more spans, all of which are part
+/// of the same edit.
+#[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Suggestion {
+    message: String,
+    parts: Vec<(Span, String)>,
+    applicability: Applicability,
+}
+
+#[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+impl Suggestion {
+    /// Returns the message describing this suggestion.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the spans to replace along with their replacement text.
+    pub fn parts(&self) -> &[(Span, String)] {
+        &self.parts
+    }
+
+    /// Returns the applicability of this suggestion.
+    pub fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+}
+
 /// Trait implemented by types that can be converted into a set of `Span`s.
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
 pub trait MultiSpan {
@@ -52,6 +104,7 @@ pub struct Diagnostic {
     message: String,
     spans: Vec<Span>,
     children: Vec<Diagnostic>,
+    suggestions: Vec<Suggestion>,
 }
 
 macro_rules! diagnostic_child_methods {
@@ -97,7 +150,13 @@ impl Diagnostic {
     /// Creates a new diagnostic with the given `level` and `message`.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn new<T: Into<String>>(level: Level, message: T) -> Diagnostic {
-        Diagnostic { level, message: message.into(), spans: vec![], children: vec![] }
+        Diagnostic {
+            level,
+            message: message.into(),
+            spans: vec![],
+            children: vec![],
+            suggestions: vec![],
+        }
     }
 
     /// Creates a new diagnostic with the given `level` and `message` pointing to
@@ -108,7 +167,13 @@ pub fn spanned<S, T>(spans: S, level: Level, message: T) -> Diagnostic
         S: MultiSpan,
         T: Into<String>,
     {
-        Diagnostic { level, message: message.into(), spans: spans.into_spans(), children: vec![] }
+        Diagnostic {
+            level,
+            message: message.into(),
+            spans: spans.into_spans(),
+            children: vec![],
+            suggestions: vec![],
+        }
     }
 
     diagnostic_child_methods!(span_error, error, Level::Error);
@@ -158,15 +223,92 @@ pub fn children(&self) -> Children<'_> {
         Children(self.children.iter())
     }
 
+    /// Adds a suggestion to replace the code at `span` with `replacement`.
+    ///
+    /// The `message` describes the edit, for example "add a `Default` bound".
+    /// Suggestions are shown along with the diagnostic, and are included in
+    /// rustc's JSON output, so that tools like `cargo fix` can apply them
+    /// according to their `applicability`. Suggestions added to a child
+    /// diagnostic are shown as part of its parent.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore (requires a proc macro context)
+    /// Diagnostic::spanned(field.span(), Level::Error, "fields must be public")
+    ///     .span_suggestion(
+    ///         field.span().start(),
+    ///         "make the field public",
+    ///         "pub ",
+    ///         Applicability::MachineApplicable,
+    ///     )
+    ///     .emit();
+    /// ```
+    #[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+    pub fn span_suggestion<T, R>(
+        self,
+        span: Span,
+        message: T,
+        replacement: R,
+        applicability: Applicability,
+    ) -> Diagnostic
+    where
+        T: Into<String>,
+        R: Into<String>,
+    {
+        self.multipart_suggestion(message, vec![(span, replacement.into())], applicability)
+    }
+
+    /// Adds a suggestion consisting of several edits that have to be applied
+    /// together, each replacing the code at a span with a replacement.
+    ///
+    /// The spans of the parts must not overlap, and there must be at least
+    /// one part. Suggestions that don't satisfy this are ignored.
+    ///
+    /// See [`Diagnostic::span_suggestion`] for more details.
+    #[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+    pub fn multipart_suggestion<T: Into<String>>(
+        mut self,
+        message: T,
+        parts: Vec<(Span, String)>,
+        applicability: Applicability,
+    ) -> Diagnostic {
+        self.suggestions.push(Suggestion { message: message.into(), parts, applicability });
+        self
+    }
+
+    /// Returns the suggestions in `self`.
+    #[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
     /// Emit the diagnostic.
     #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
     pub fn emit(self) {
-        fn to_internal(diag: Diagnostic) -> crate::bridge::Diagnostic<crate::bridge::client::Span> {
+        use crate::bridge::client::Span;
+
+        fn to_internal(diag: Diagnostic) -> crate::bridge::Diagnostic<Span> {
             crate::bridge::Diagnostic {
                 level: diag.level,
                 message: diag.message,
                 spans: diag.spans.into_iter().map(|s| s.0).collect(),
                 children: diag.children.into_iter().map(to_internal).collect(),
+                suggestions: diag
+                    .suggestions
+                    .into_iter()
+                    .map(|suggestion| crate::bridge::Suggestion {
+                        message: suggestion.message,
+                        parts: suggestion
+                            .parts
+                            .into_iter()
+                            .map(|(span, snippet)| crate::bridge::SuggestionPart {
+                                span: span.0,
+                                snippet,
+                            })
+                            .collect(),
+                        applicability: suggestion.applicability,
+                    })
+                    .collect(),
             }
         }
 
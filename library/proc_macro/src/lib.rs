@@ -39,6 +39,8 @@
 #[unstable(feature = "proc_macro_internals", issue = "27812")]
 #[doc(hidden)]
 pub mod bridge;
+#[unstable(feature = "proc_macro_expansion_context", issue = "none")]
+pub mod context;
 
 mod diagnostic;
 mod escape;
@@ -51,6 +53,8 @@
 use std::str::FromStr;
 use std::{error, fmt};
 
+#[unstable(feature = "proc_macro_diagnostic_suggestion", issue = "none")]
+pub use diagnostic::{Applicability, Suggestion};
 #[unstable(feature = "proc_macro_diagnostic", issue = "54140")]
 pub use diagnostic::{Diagnostic, Level, MultiSpan};
 #[unstable(feature = "proc_macro_value", issue = "136652")]
@@ -53,6 +53,9 @@
 //! The `#[global_allocator]` can only be used once in a crate
 //! or its recursive dependencies.
 //!
+//! To count the allocations of a program, the [`CountingAlloc`] wrapper can
+//! be used as the global allocator around [`System`] or another allocator.
+//!
 //! [^system-alloc]: Note that the Rust standard library internals may still
 //! directly call [`System`] when necessary (for example for the runtime
 //! support typically required to implement a global allocator, see [re-entrance] on [`GlobalAlloc`]
@@ -71,6 +74,11 @@
 #[doc(inline)]
 pub use alloc_crate::alloc::*;
 
+#[unstable(feature = "alloc_stats", issue = "none")]
+pub use self::stats::{AllocStats, CountingAlloc, thread_alloc_stats};
+
+mod stats;
+
 /// The default memory allocator provided by the operating system.
 ///
 /// This is based on `malloc` on Unix platforms and `HeapAlloc` on Windows,
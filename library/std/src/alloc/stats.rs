@@ -0,0 +1,341 @@
+//! Counting allocations made through a global allocator.
+
+use super::{GlobalAlloc, Layout, System};
+use crate::fmt;
+use crate::sync::atomic::AtomicUsize;
+use crate::sync::atomic::Ordering::Relaxed;
+
+/// A global allocator that counts the allocations it makes and the memory it
+/// keeps in use, and forwards them to another allocator.
+///
+/// The counters of the allocator as a whole can be read with
+/// [`stats`](CountingAlloc::stats). Additionally, the counters of each thread
+/// can be read with [`thread_alloc_stats`].
+///
+/// `CountingAlloc` wraps [`System`] by default, but it can wrap any other
+/// [`GlobalAlloc`] as well, so it can be combined with a custom allocator.
+/// Requests that fail are not counted.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(alloc_stats)]
+/// use std::alloc::{CountingAlloc, System};
+///
+/// #[global_allocator]
+/// static GLOBAL: CountingAlloc = CountingAlloc::new(System);
+///
+/// fn main() {
+///     let v = vec![0u8; 1024];
+///     let stats = GLOBAL.stats();
+///     assert!(stats.bytes_in_use() >= 1024);
+///     println!("{} allocations, peak {} bytes", stats.allocations(), stats.peak_bytes_in_use());
+///     drop(v);
+/// }
+/// ```
+#[unstable(feature = "alloc_stats", issue = "none")]
+pub struct CountingAlloc<A = System> {
+    inner: A,
+    counters: Counters,
+}
+
+impl<A> CountingAlloc<A> {
+    /// Creates an allocator that counts the requests it forwards to `inner`.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    #[must_use]
+    pub const fn new(inner: A) -> CountingAlloc<A> {
+        CountingAlloc { inner, counters: Counters::new() }
+    }
+
+    /// Returns a reference to the allocator the requests are forwarded to.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns a snapshot of the counters of this allocator, covering all
+    /// threads.
+    ///
+    /// The counters are updated independently of each other, so a snapshot
+    /// taken while other threads allocate may not be consistent.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    pub fn stats(&self) -> AllocStats {
+        self.counters.snapshot()
+    }
+}
+
+#[unstable(feature = "alloc_stats", issue = "none")]
+impl<A: fmt::Debug> fmt::Debug for CountingAlloc<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountingAlloc")
+            .field("inner", &self.inner)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[unstable(feature = "alloc_stats", issue = "none")]
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller must uphold the safety contract of `alloc`.
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if !ptr.is_null() {
+            self.counters.record_alloc(layout.size());
+            thread::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller must uphold the safety contract of `alloc_zeroed`.
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            self.counters.record_alloc(layout.size());
+            thread::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller must uphold the safety contract of `dealloc`.
+        unsafe { self.inner.dealloc(ptr, layout) };
+        self.counters.record_dealloc(layout.size());
+        thread::record_dealloc(layout.size());
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the caller must uphold the safety contract of `realloc`.
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            self.counters.record_realloc(layout.size(), new_size);
+            thread::record_realloc(layout.size(), new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Returns a snapshot of the counters of the current thread, covering the
+/// requests it made to any [`CountingAlloc`].
+///
+/// Memory that is deallocated by another thread than the one that allocated
+/// it lowers the bytes in use of the deallocating thread. The bytes in use
+/// of a thread that deallocates more than it allocates are reported as zero.
+///
+/// # Platform-specific behavior
+///
+/// The counters of each thread are only kept on platforms with native
+/// thread-local storage. On other platforms, this always returns zeroed
+/// counters.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(alloc_stats)]
+/// use std::alloc::{CountingAlloc, System, thread_alloc_stats};
+///
+/// #[global_allocator]
+/// static GLOBAL: CountingAlloc = CountingAlloc::new(System);
+///
+/// fn main() {
+///     let before = thread_alloc_stats();
+///     let v: Vec<u64> = Vec::with_capacity(16);
+///     let after = thread_alloc_stats();
+///     println!("allocated {} times", after.allocations() - before.allocations());
+///     drop(v);
+/// }
+/// ```
+#[unstable(feature = "alloc_stats", issue = "none")]
+pub fn thread_alloc_stats() -> AllocStats {
+    thread::snapshot()
+}
+
+/// A snapshot of allocation counters, as returned by [`CountingAlloc::stats`]
+/// and [`thread_alloc_stats`].
+///
+/// Resizing an allocation in place or by moving it counts as a single
+/// reallocation, not as an allocation and a deallocation. All counters wrap
+/// around on overflow.
+#[unstable(feature = "alloc_stats", issue = "none")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AllocStats {
+    allocations: usize,
+    deallocations: usize,
+    reallocations: usize,
+    bytes_in_use: usize,
+    peak_bytes_in_use: usize,
+}
+
+impl AllocStats {
+    /// Returns the number of successful allocations.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    #[must_use]
+    pub fn allocations(&self) -> usize {
+        self.allocations
+    }
+
+    /// Returns the number of deallocations.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    #[must_use]
+    pub fn deallocations(&self) -> usize {
+        self.deallocations
+    }
+
+    /// Returns the number of successful reallocations.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    #[must_use]
+    pub fn reallocations(&self) -> usize {
+        self.reallocations
+    }
+
+    /// Returns the number of bytes that were allocated and not deallocated
+    /// yet, as requested by the layouts of the allocations.
+    ///
+    /// This does not include the overhead of the underlying allocator.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    #[must_use]
+    pub fn bytes_in_use(&self) -> usize {
+        self.bytes_in_use
+    }
+
+    /// Returns the largest value [`bytes_in_use`](AllocStats::bytes_in_use)
+    /// has had so far.
+    #[unstable(feature = "alloc_stats", issue = "none")]
+    #[must_use]
+    pub fn peak_bytes_in_use(&self) -> usize {
+        self.peak_bytes_in_use
+    }
+}
+
+/// The counters of a `CountingAlloc`, shared by all threads.
+struct Counters {
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+    reallocations: AtomicUsize,
+    bytes_in_use: AtomicUsize,
+    peak_bytes_in_use: AtomicUsize,
+}
+
+impl Counters {
+    const fn new() -> Counters {
+        Counters {
+            allocations: AtomicUsize::new(0),
+            deallocations: AtomicUsize::new(0),
+            reallocations: AtomicUsize::new(0),
+            bytes_in_use: AtomicUsize::new(0),
+            peak_bytes_in_use: AtomicUsize::new(0),
+        }
+    }
+
+    fn record_alloc(&self, size: usize) {
+        self.allocations.fetch_add(1, Relaxed);
+        self.grow(size);
+    }
+
+    fn record_dealloc(&self, size: usize) {
+        self.deallocations.fetch_add(1, Relaxed);
+        self.bytes_in_use.fetch_sub(size, Relaxed);
+    }
+
+    fn record_realloc(&self, old_size: usize, new_size: usize) {
+        self.reallocations.fetch_add(1, Relaxed);
+        if new_size >= old_size {
+            self.grow(new_size - old_size);
+        } else {
+            self.bytes_in_use.fetch_sub(old_size - new_size, Relaxed);
+        }
+    }
+
+    fn grow(&self, size: usize) {
+        let bytes_in_use = self.bytes_in_use.fetch_add(size, Relaxed).wrapping_add(size);
+        self.peak_bytes_in_use.fetch_max(bytes_in_use, Relaxed);
+    }
+
+    fn snapshot(&self) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations.load(Relaxed),
+            deallocations: self.deallocations.load(Relaxed),
+            reallocations: self.reallocations.load(Relaxed),
+            bytes_in_use: self.bytes_in_use.load(Relaxed),
+            peak_bytes_in_use: self.peak_bytes_in_use.load(Relaxed),
+        }
+    }
+}
+
+/// The counters of the current thread. These must be accessible without
+/// allocating, which rules out the lazily allocated keys `thread_local!` falls
+/// back to on platforms without native thread-local storage.
+mod thread {
+    use super::AllocStats;
+
+    cfg_select! {
+        target_thread_local => {
+            use crate::cell::Cell;
+
+            struct ThreadCounters {
+                allocations: Cell<usize>,
+                deallocations: Cell<usize>,
+                reallocations: Cell<usize>,
+                // Signed, as the thread may deallocate memory allocated by other threads.
+                bytes_in_use: Cell<isize>,
+                peak_bytes_in_use: Cell<usize>,
+            }
+
+            #[thread_local]
+            static COUNTERS: ThreadCounters = ThreadCounters {
+                allocations: Cell::new(0),
+                deallocations: Cell::new(0),
+                reallocations: Cell::new(0),
+                bytes_in_use: Cell::new(0),
+                peak_bytes_in_use: Cell::new(0),
+            };
+
+            fn add_bytes(delta: isize) {
+                let bytes_in_use = COUNTERS.bytes_in_use.get().wrapping_add(delta);
+                COUNTERS.bytes_in_use.set(bytes_in_use);
+                if bytes_in_use > 0 && bytes_in_use as usize > COUNTERS.peak_bytes_in_use.get() {
+                    COUNTERS.peak_bytes_in_use.set(bytes_in_use as usize);
+                }
+            }
+
+            pub(super) fn record_alloc(size: usize) {
+                COUNTERS.allocations.set(COUNTERS.allocations.get().wrapping_add(1));
+                add_bytes(size as isize);
+            }
+
+            pub(super) fn record_dealloc(size: usize) {
+                COUNTERS.deallocations.set(COUNTERS.deallocations.get().wrapping_add(1));
+                add_bytes((size as isize).wrapping_neg());
+            }
+
+            pub(super) fn record_realloc(old_size: usize, new_size: usize) {
+                COUNTERS.reallocations.set(COUNTERS.reallocations.get().wrapping_add(1));
+                add_bytes((new_size as isize).wrapping_sub(old_size as isize));
+            }
+
+            pub(super) fn snapshot() -> AllocStats {
+                AllocStats {
+                    allocations: COUNTERS.allocations.get(),
+                    deallocations: COUNTERS.deallocations.get(),
+                    reallocations: COUNTERS.reallocations.get(),
+                    bytes_in_use: COUNTERS.bytes_in_use.get().max(0) as usize,
+                    peak_bytes_in_use: COUNTERS.peak_bytes_in_use.get(),
+                }
+            }
+        }
+        _ => {
+            pub(super) fn record_alloc(_size: usize) {}
+
+            pub(super) fn record_dealloc(_size: usize) {}
+
+            pub(super) fn record_realloc(_old_size: usize, _new_size: usize) {}
+
+            pub(super) fn snapshot() -> AllocStats {
+                AllocStats::default()
+            }
+        }
+    }
+}
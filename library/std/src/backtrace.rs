@@ -89,12 +89,14 @@
 // a backtrace or actually symbolizing it.
 
 use crate::backtrace_rs::{self, BytesOrWideString};
+use crate::borrow::Cow;
 use crate::ffi::c_void;
 use crate::panic::UnwindSafe;
+use crate::path::Path;
 use crate::sync::LazyLock;
 use crate::sync::atomic::Ordering::Relaxed;
 use crate::sync::atomic::{Atomic, AtomicU8};
-use crate::sys::backtrace::{lock, output_filename, set_image_base};
+use crate::sys::backtrace::{filename_to_path, lock, output_filename, set_image_base};
 use crate::{env, fmt};
 
 /// A captured OS thread stack backtrace.
@@ -159,7 +161,12 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A symbol resolved for a [`BacktraceFrame`].
+///
+/// A single frame may resolve to several symbols when functions were inlined
+/// into each other, see [`BacktraceFrame::symbols`].
+#[unstable(feature = "backtrace_symbols", issue = "none")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
@@ -207,6 +214,7 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_symbols", issue = "none")]
 impl fmt::Debug for BacktraceSymbol {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME: improve formatting: https://github.com/rust-lang/rust/issues/65280
@@ -378,6 +386,109 @@ pub fn frames(&'a self) -> &'a [BacktraceFrame] {
     }
 }
 
+impl BacktraceFrame {
+    /// Returns the current instruction pointer of this frame.
+    ///
+    /// This is normally the next instruction to execute in the frame, but not
+    /// all implementations list this with 100% accuracy (but it's generally
+    /// pretty close).
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the starting address of the function this frame belongs to.
+    ///
+    /// This is an estimate that the unwinder made, and may be null if it is
+    /// not known.
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn symbol_address(&self) -> *mut c_void {
+        self.frame.symbol_address()
+    }
+
+    /// Returns the symbols this frame resolved to.
+    ///
+    /// There may be more than one symbol when functions were inlined into each
+    /// other, in which case the innermost function comes first. The list is
+    /// empty if no symbol information could be found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace_frames, backtrace_symbols)]
+    ///
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::force_capture();
+    /// for frame in backtrace.frames() {
+    ///     for symbol in frame.symbols() {
+    ///         let name = symbol.name().unwrap_or_else(|| "<unknown>".to_string());
+    ///         match (symbol.filename(), symbol.lineno()) {
+    ///             (Some(file), Some(line)) => println!("{name} at {}:{line}", file.display()),
+    ///             _ => println!("{name}"),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the demangled name of this symbol, without the hash that is
+    /// part of mangled Rust symbol names.
+    ///
+    /// Names that are not mangled, for example those of C functions, are
+    /// returned as they are, and names that are not valid UTF-8 are converted
+    /// lossily.
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|b| format!("{:#}", backtrace_rs::SymbolName::new(b)))
+    }
+
+    /// Returns the raw name of this symbol, as found in the debug information.
+    ///
+    /// For Rust functions, this is usually the mangled name.
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn raw_name(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns the file this symbol is defined in.
+    ///
+    /// Returns `None` if the file is not known, or if its name can't be
+    /// represented as a path on this platform.
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn filename(&self) -> Option<Cow<'_, Path>> {
+        filename_to_path(match self.filename.as_ref()? {
+            BytesOrWide::Bytes(w) => BytesOrWideString::Bytes(w),
+            BytesOrWide::Wide(w) => BytesOrWideString::Wide(w),
+        })
+    }
+
+    /// Returns the line number of this symbol within its file, if known.
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number of this symbol within its line, if known.
+    #[must_use]
+    #[unstable(feature = "backtrace_symbols", issue = "none")]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+}
+
 #[stable(feature = "backtrace", since = "1.65.0")]
 impl fmt::Display for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -475,4 +586,12 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => crate::ptr::without_provenance_mut(1),
         }
     }
+
+    fn symbol_address(&self) -> *mut c_void {
+        match self {
+            RawFrame::Actual(frame) => frame.symbol_address(),
+            #[cfg(test)]
+            RawFrame::Fake => crate::ptr::null_mut(),
+        }
+    }
 }
@@ -91,6 +91,27 @@ fn test_frames() {
     assert!(iter.all(|(f, e)| format!("{f:#?}") == *e));
 }
 
+#[test]
+fn test_symbols() {
+    let frames = generate_fake_frames();
+
+    assert!(frames.iter().all(|f| !f.ip().is_null()));
+    assert_eq!(frames[1].symbols().len(), 1);
+    assert_eq!(frames[2].symbols().len(), 2);
+
+    let symbol = &frames[2].symbols()[0];
+    assert_eq!(symbol.name().as_deref(), Some("std::rt::lang_start_internal"));
+    assert_eq!(symbol.raw_name(), Some(&b"std::rt::lang_start_internal"[..]));
+    assert_eq!(symbol.filename().as_deref(), Some(Path::new("rust/rt.rs")));
+    assert_eq!(symbol.lineno(), Some(300));
+    assert_eq!(symbol.colno(), Some(5));
+
+    let symbol = &frames[1].symbols()[0];
+    assert_eq!(symbol.filename(), None);
+    assert_eq!(symbol.lineno(), None);
+    assert_eq!(symbol.colno(), None);
+}
+
 #[test]
 fn backtrace_unwind_safe() {
     fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
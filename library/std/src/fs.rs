@@ -40,6 +40,7 @@
     ))
 ))]
 mod tests;
+mod walk;
 
 use crate::ffi::OsString;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
@@ -50,6 +51,9 @@
 use crate::time::SystemTime;
 use crate::{error, fmt};
 
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub use self::walk::{WalkDir, WalkDirEntry, WalkDirIter, copy_dir_all, walk_dir};
+
 /// An object providing access to an open file on the filesystem.
 ///
 /// An instance of a `File` can be read and/or written depending on what options
@@ -2546,3 +2546,203 @@ fn test_dir_read_file() {
     let buf = check!(io::read_to_string(f));
     assert_eq!("bar", &buf);
 }
+
+#[test]
+fn walk_dir_sorted() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("root");
+    check!(fs::create_dir_all(root.join("b/c")));
+    check!(fs::create_dir_all(root.join("a")));
+    check!(fs::write(root.join("a/file"), b"a"));
+    check!(fs::write(root.join("b/c/file"), b"c"));
+    check!(fs::write(root.join("d"), b"d"));
+
+    let entries = |walk: fs::WalkDir| -> Vec<(String, usize)> {
+        walk.sort_by_file_name()
+            .into_iter()
+            .map(|entry| {
+                let entry = check!(entry);
+                let path = entry.path().strip_prefix(&root).unwrap();
+                (path.to_str().unwrap().replace('\\', "/"), entry.depth())
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        entries(fs::walk_dir(&root)),
+        [
+            ("".to_string(), 0),
+            ("a".to_string(), 1),
+            ("a/file".to_string(), 2),
+            ("b".to_string(), 1),
+            ("b/c".to_string(), 2),
+            ("b/c/file".to_string(), 3),
+            ("d".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        entries(fs::walk_dir(&root).min_depth(1).max_depth(2)),
+        [
+            ("a".to_string(), 1),
+            ("a/file".to_string(), 2),
+            ("b".to_string(), 1),
+            ("b/c".to_string(), 2),
+            ("d".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn walk_dir_skip_current_dir() {
+    let tmpdir = tmpdir();
+    check!(fs::create_dir_all(tmpdir.join("skipped/inner")));
+    check!(fs::create_dir_all(tmpdir.join("visited")));
+    check!(fs::write(tmpdir.join("visited/file"), b"file"));
+
+    let mut names = Vec::new();
+    let mut walk = fs::walk_dir(tmpdir.path()).min_depth(1).sort_by_file_name().into_iter();
+    while let Some(entry) = walk.next() {
+        let entry = check!(entry);
+        if entry.file_name() == "skipped" {
+            walk.skip_current_dir();
+        }
+        names.push(entry.file_name().to_owned());
+    }
+    assert_eq!(names, ["skipped", "visited", "file"]);
+}
+
+#[test]
+fn walk_dir_skip_current_dir_at_max_depth() {
+    let tmpdir = tmpdir();
+    check!(fs::create_dir_all(tmpdir.join("top/skipped/inner")));
+    check!(fs::create_dir_all(tmpdir.join("top/visited")));
+
+    // `skipped` is not descended into, so skipping it must not skip `visited`.
+    let mut names = Vec::new();
+    let mut walk =
+        fs::walk_dir(tmpdir.path()).min_depth(1).max_depth(2).sort_by_file_name().into_iter();
+    while let Some(entry) = walk.next() {
+        let entry = check!(entry);
+        if entry.file_name() == "skipped" {
+            walk.skip_current_dir();
+        }
+        names.push(entry.file_name().to_owned());
+    }
+    assert_eq!(names, ["top", "skipped", "visited"]);
+}
+
+#[test]
+fn walk_dir_of_file() {
+    let tmpdir = tmpdir();
+    let file = tmpdir.join("file");
+    check!(fs::write(&file, b"file"));
+
+    let entries = check!(fs::walk_dir(&file).into_iter().collect::<io::Result<Vec<_>>>());
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path(), file);
+    assert!(entries[0].file_type().is_file());
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn walk_dir_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+
+    let root = tmpdir.join("root");
+    let outside = tmpdir.join("outside");
+    check!(fs::create_dir_all(&root));
+    check!(fs::create_dir_all(&outside));
+    check!(fs::write(outside.join("file"), b"file"));
+    check!(symlink_dir(&outside, root.join("link")));
+    check!(symlink_dir(&root, root.join("loop")));
+
+    let entries = check!(fs::walk_dir(&root).into_iter().collect::<io::Result<Vec<_>>>());
+    assert_eq!(entries.len(), 3);
+    assert!(entries[1..].iter().all(|entry| entry.file_type().is_symlink()));
+
+    let mut found_file = false;
+    let mut found_loop = false;
+    for entry in fs::walk_dir(&root).follow_links(true) {
+        match entry {
+            Ok(entry) if entry.file_name() == "file" => {
+                assert_eq!(entry.path(), root.join("link").join("file"));
+                found_file = true;
+            }
+            Ok(entry) => assert!(entry.file_type().is_dir()),
+            Err(err) => {
+                assert_eq!(err.kind(), ErrorKind::FilesystemLoop);
+                found_loop = true;
+            }
+        }
+    }
+    assert!(found_file);
+    assert!(found_loop);
+}
+
+#[test]
+fn copy_dir_all() {
+    let tmpdir = tmpdir();
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    check!(fs::create_dir_all(src.join("a/b")));
+    check!(fs::create_dir_all(src.join("empty")));
+    check!(fs::write(src.join("file"), b"top"));
+    check!(fs::write(src.join("a/b/file"), b"nested"));
+
+    check!(fs::copy_dir_all(&src, &dst));
+    assert_eq!(check!(fs::read(dst.join("file"))), b"top");
+    assert_eq!(check!(fs::read(dst.join("a/b/file"))), b"nested");
+    assert!(dst.join("empty").is_dir());
+
+    // Files that already exist are not overwritten.
+    let result = fs::copy_dir_all(&src, &dst);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::AlreadyExists);
+
+    // The destination can't be inside of the source.
+    let result = fs::copy_dir_all(&src, src.join("a/copy"));
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert!(!src.join("a/copy").exists());
+}
+
+#[test]
+fn copy_dir_all_deep() {
+    let tmpdir = tmpdir();
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    let nested = "d/".repeat(100);
+    check!(fs::create_dir_all(src.join(&nested)));
+    check!(fs::write(src.join(&nested).join("file"), b"deep"));
+
+    check!(fs::copy_dir_all(&src, &dst));
+    assert_eq!(check!(fs::read(dst.join(&nested).join("file"))), b"deep");
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn copy_dir_all_copies_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    let outside = tmpdir.join("outside");
+    check!(fs::create_dir_all(&src));
+    check!(fs::create_dir_all(&outside));
+    check!(fs::write(outside.join("file"), b"outside"));
+    check!(symlink_dir(&outside, src.join("link")));
+
+    check!(fs::copy_dir_all(&src, &dst));
+    assert!(check!(fs::symlink_metadata(dst.join("link"))).file_type().is_symlink());
+    assert_eq!(check!(fs::read_link(dst.join("link"))), outside);
+}
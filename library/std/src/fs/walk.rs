@@ -0,0 +1,611 @@
+//! Recursive directory traversal and copying.
+
+use super::{File, FileType, Metadata, Permissions};
+use crate::cmp::Ordering;
+use crate::ffi::OsStr;
+use crate::io::{self, ErrorKind};
+use crate::path::{Path, PathBuf};
+use crate::sys::{FromInner, fs as fs_imp};
+use crate::{fmt, fs, vec};
+
+type Sorter = Box<dyn FnMut(&WalkDirEntry, &WalkDirEntry) -> Ordering + Send + Sync + 'static>;
+
+/// Returns a builder for recursively walking the directory tree at `root`.
+///
+/// Iterating over the returned [`WalkDir`] yields the entry for `root` itself
+/// first, followed by the entries of the tree below it in depth-first order:
+/// every directory is immediately followed by its contents. The options of
+/// [`WalkDir`] can restrict the depth of the walk, follow symbolic links and
+/// sort the entries of each directory, and [`WalkDirIter::skip_current_dir`]
+/// skips the rest of a subtree.
+///
+/// If `root` is a symbolic link, it is always followed. Other symbolic links
+/// are only followed if [`WalkDir::follow_links`] is enabled.
+///
+/// # Platform-specific behavior
+///
+/// On most Unix platforms, every directory is opened relative to its parent
+/// with `openat`, using `O_NOFOLLOW` unless symbolic links are followed. On
+/// Windows, every directory is listed through its handle and opened relative
+/// to its parent with `NtOpenFile`, without following reparse points unless
+/// symbolic links are followed. A directory that is replaced with a symbolic
+/// link while it is being walked is therefore reported as an error instead of
+/// redirecting the walk, like in [`remove_dir_all`](super::remove_dir_all).
+/// On other platforms,
+/// directories are checked before they are opened by path, which is subject
+/// to [TOCTOU](super#time-of-check-to-time-of-use-toctou) races.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// Errors are yielded by the iterator rather than returned by this function.
+/// An error opening or reading a directory doesn't stop the walk, so the
+/// remaining entries can be visited by skipping over errors.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     for entry in fs::walk_dir("src").max_depth(2).sort_by_file_name() {
+///         let entry = entry?;
+///         println!("{}", entry.path().display());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn walk_dir<P: AsRef<Path>>(root: P) -> WalkDir {
+    WalkDir {
+        root: root.as_ref().to_path_buf(),
+        min_depth: 0,
+        max_depth: usize::MAX,
+        follow_links: false,
+        sorter: None,
+    }
+}
+
+/// A builder for a recursive walk of a directory tree.
+///
+/// This struct is created by [`walk_dir`]. See its documentation for more.
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[must_use = "a `WalkDir` does nothing unless iterated over"]
+pub struct WalkDir {
+    root: PathBuf,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sorter: Option<Sorter>,
+}
+
+impl WalkDir {
+    /// Sets the minimum depth of the entries to yield.
+    ///
+    /// The root has a depth of `0`, its entries have a depth of `1`, and so
+    /// on. Entries below the minimum depth are still traversed, but they are
+    /// not yielded. The default is `0`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn min_depth(mut self, depth: usize) -> Self {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Sets the maximum depth of the entries to yield.
+    ///
+    /// Directories at the maximum depth are yielded, but not descended
+    /// into. The default is unlimited.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether symbolic links below the root are followed.
+    ///
+    /// When enabled, symbolic links are reported as the file they point to
+    /// and symbolic links to directories are descended into. A symbolic link
+    /// to one of its own ancestors yields an error of kind
+    /// [`FilesystemLoop`](ErrorKind::FilesystemLoop) instead. The default is
+    /// `false`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn follow_links(mut self, follow: bool) -> Self {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Sorts the entries of every directory with the comparison function
+    /// `compare` before they are yielded.
+    ///
+    /// This requires reading a directory completely before yielding any of
+    /// its entries. By default, entries are yielded in the order they are
+    /// read from the directory, which is platform and filesystem dependent.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by<F>(mut self, compare: F) -> Self
+    where
+        F: FnMut(&WalkDirEntry, &WalkDirEntry) -> Ordering + Send + Sync + 'static,
+    {
+        self.sorter = Some(Box::new(compare));
+        self
+    }
+
+    /// Sorts the entries of every directory by their file name.
+    ///
+    /// See [`WalkDir::sort_by`] for more.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by_file_name(self) -> Self {
+        self.sort_by(|a, b| a.file_name().cmp(b.file_name()))
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl IntoIterator for WalkDir {
+    type Item = io::Result<WalkDirEntry>;
+    type IntoIter = WalkDirIter;
+
+    fn into_iter(self) -> WalkDirIter {
+        WalkDirIter {
+            root: Some(self.root),
+            min_depth: self.min_depth,
+            max_depth: self.max_depth,
+            follow_links: self.follow_links,
+            sorter: self.sorter,
+            stack: Vec::new(),
+            pending: None,
+            yielded_dir: false,
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDir")
+            .field("root", &self.root)
+            .field("min_depth", &self.min_depth)
+            .field("max_depth", &self.max_depth)
+            .field("follow_links", &self.follow_links)
+            .field("sorted", &self.sorter.is_some())
+            .finish()
+    }
+}
+
+/// An entry yielded by [`WalkDirIter`].
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Clone)]
+pub struct WalkDirEntry {
+    path: PathBuf,
+    file_type: FileType,
+    depth: usize,
+    follow_link: bool,
+}
+
+impl WalkDirEntry {
+    /// Returns the full path to this entry, which is the root passed to
+    /// [`walk_dir`] joined with the names of the directories leading to it.
+    #[must_use]
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Converts this entry into its full path.
+    #[must_use]
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the file name of this entry.
+    ///
+    /// For the root, this is its whole path if it has no file name, for
+    /// example if it is `/` or `..`.
+    #[must_use]
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Returns the depth of this entry below the root, which has a depth of
+    /// `0`.
+    #[must_use]
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the file type of this entry.
+    ///
+    /// If this entry is a symbolic link that was followed, this is the file
+    /// type of its target.
+    #[must_use]
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns `true` if this entry is a symbolic link, whether or not it was
+    /// followed.
+    #[must_use]
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn path_is_symlink(&self) -> bool {
+        self.follow_link || self.file_type.is_symlink()
+    }
+
+    /// Queries the metadata of this entry.
+    ///
+    /// Like [`WalkDirEntry::file_type`], this follows the entry if it is a
+    /// symbolic link that was followed during the walk.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        if self.follow_link { fs::metadata(&self.path) } else { fs::symlink_metadata(&self.path) }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDirEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDirEntry")
+            .field("path", &self.path)
+            .field("depth", &self.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Frame {
+    dir: fs_imp::WalkDirStream,
+    path: PathBuf,
+    // Depth of the entries of this directory.
+    depth: usize,
+    // All entries of the directory, if they have to be sorted.
+    sorted: Option<vec::IntoIter<io::Result<WalkDirEntry>>>,
+    // Canonical path of the directory, to detect loops when following links.
+    canonical: Option<PathBuf>,
+}
+
+impl Frame {
+    fn read_entry(&mut self, follow_links: bool) -> Option<io::Result<WalkDirEntry>> {
+        let entry = match self.dir.next()? {
+            Ok(entry) => entry,
+            Err(err) => return Some(Err(err)),
+        };
+        let path = self.path.join(entry.file_name());
+        let result: io::Result<WalkDirEntry> = try {
+            let mut file_type = FileType(entry.file_type()?);
+            let follow_link = follow_links && file_type.is_symlink();
+            if follow_link {
+                file_type = fs::metadata(&path)?.file_type();
+            }
+            WalkDirEntry { path, file_type, depth: self.depth, follow_link }
+        };
+        Some(result)
+    }
+
+    fn next_entry(&mut self, follow_links: bool) -> Option<io::Result<WalkDirEntry>> {
+        match &mut self.sorted {
+            Some(entries) => entries.next(),
+            None => self.read_entry(follow_links),
+        }
+    }
+}
+
+/// An iterator over the entries of a directory tree.
+///
+/// This struct is created by iterating over a [`WalkDir`]. See [`walk_dir`]
+/// for more.
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct WalkDirIter {
+    root: Option<PathBuf>,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sorter: Option<Sorter>,
+    stack: Vec<Frame>,
+    // The last directory that was yielded, which is descended into next.
+    pending: Option<WalkDirEntry>,
+    // Whether the last entry yielded was a directory, even one that is not
+    // descended into because it is at the maximum depth.
+    yielded_dir: bool,
+}
+
+impl WalkDirIter {
+    /// Skips the contents of the directory that is currently being walked.
+    ///
+    /// If the last entry yielded was a directory, its contents are skipped,
+    /// even if it wouldn't have been descended into because of
+    /// [`WalkDir::max_depth`].
+    /// Otherwise, the remaining entries of the directory containing the last
+    /// entry are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut entries = fs::walk_dir(".").into_iter();
+    ///     while let Some(entry) = entries.next() {
+    ///         let entry = entry?;
+    ///         if entry.file_name() == ".git" {
+    ///             entries.skip_current_dir();
+    ///             continue;
+    ///         }
+    ///         println!("{}", entry.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn skip_current_dir(&mut self) {
+        self.pending = None;
+        if !self.yielded_dir {
+            self.stack.pop();
+        }
+        // Skipping again skips the directory containing the last entry.
+        self.yielded_dir = false;
+    }
+
+    fn root_entry(&self, path: PathBuf) -> io::Result<WalkDirEntry> {
+        let mut file_type = fs::symlink_metadata(&path)?.file_type();
+        let follow_link = file_type.is_symlink();
+        if follow_link {
+            file_type = fs::metadata(&path)?.file_type();
+        }
+        Ok(WalkDirEntry { path, file_type, depth: 0, follow_link })
+    }
+
+    fn descend(&mut self, entry: WalkDirEntry) -> io::Result<()> {
+        let dir = match self.stack.last() {
+            Some(parent) => {
+                let name = entry.path.file_name().unwrap();
+                parent.dir.open_child(name, &entry.path, entry.follow_link)?
+            }
+            None => fs_imp::WalkDirStream::open(&entry.path)?,
+        };
+
+        let canonical = if self.follow_links {
+            let canonical = fs::canonicalize(&entry.path)?;
+            if self.stack.iter().any(|frame| frame.canonical.as_ref() == Some(&canonical)) {
+                return Err(io::const_error!(
+                    ErrorKind::FilesystemLoop,
+                    "a symbolic link points to one of its ancestors",
+                ));
+            }
+            Some(canonical)
+        } else {
+            None
+        };
+
+        let mut frame =
+            Frame { dir, path: entry.path, depth: entry.depth + 1, sorted: None, canonical };
+        if let Some(sorter) = &mut self.sorter {
+            let mut entries = Vec::new();
+            while let Some(entry) = frame.read_entry(self.follow_links) {
+                entries.push(entry);
+            }
+            // Errors are yielded first, before the sorted entries.
+            entries.sort_by(|a, b| match (a, b) {
+                (Ok(a), Ok(b)) => sorter(a, b),
+                (Err(_), Err(_)) => Ordering::Equal,
+                (Err(_), Ok(_)) => Ordering::Less,
+                (Ok(_), Err(_)) => Ordering::Greater,
+            });
+            frame.sorted = Some(entries.into_iter());
+        }
+        self.stack.push(frame);
+        Ok(())
+    }
+
+    fn should_descend(&self, entry: &WalkDirEntry) -> bool {
+        entry.file_type.is_dir() && entry.depth < self.max_depth
+    }
+
+    fn yield_entry(&mut self, entry: WalkDirEntry) -> io::Result<WalkDirEntry> {
+        self.yielded_dir = entry.file_type.is_dir();
+        Ok(entry)
+    }
+
+    /// Opens the last entry yielded, which must be a regular file below the
+    /// root, relative to its parent directory.
+    fn open_file(&self, entry: &WalkDirEntry) -> io::Result<fs_imp::File> {
+        let parent = self.stack.last().unwrap();
+        parent.dir.open_file(entry.path.file_name().unwrap(), &entry.path)
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for WalkDirIter {
+    type Item = io::Result<WalkDirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkDirEntry>> {
+        if let Some(root) = self.root.take() {
+            let entry = match self.root_entry(root) {
+                Ok(entry) => entry,
+                Err(err) => return Some(Err(err)),
+            };
+            if self.should_descend(&entry) {
+                self.pending = Some(entry.clone());
+            }
+            if self.min_depth == 0 {
+                return Some(self.yield_entry(entry));
+            }
+        }
+
+        loop {
+            if let Some(dir) = self.pending.take()
+                && let Err(err) = self.descend(dir)
+            {
+                self.yielded_dir = false;
+                return Some(Err(err));
+            }
+
+            let frame = self.stack.last_mut()?;
+            match frame.next_entry(self.follow_links) {
+                None => {
+                    self.stack.pop();
+                }
+                Some(Err(err)) => {
+                    self.yielded_dir = false;
+                    return Some(Err(err));
+                }
+                Some(Ok(entry)) => {
+                    if self.should_descend(&entry) {
+                        self.pending = Some(entry.clone());
+                    }
+                    if entry.depth >= self.min_depth {
+                        return Some(self.yield_entry(entry));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDirIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDirIter")
+            .field("current_dir", &self.stack.last().map(|frame| &frame.path))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Recursively copies the directory at `from` and all of its contents to
+/// `to`.
+///
+/// Regular files are copied like with [`copy`](super::copy), including their
+/// permissions. Subdirectories are created with the permissions of their
+/// counterparts in `from`, and symbolic links are copied as symbolic links
+/// with the same target rather than followed. If `from` is a symbolic link,
+/// the directory it points to is copied.
+///
+/// `to` is created if it doesn't exist, but its parent must exist. If `to`
+/// already exists, it must be a directory, and none of the files copied into
+/// it may already exist in it.
+///
+/// # Platform-specific behavior
+///
+/// The source tree is read like with [`walk_dir`]: on most Unix platforms and
+/// on Windows, directories and files are opened relative to their parent
+/// without following symbolic links, so that replacing part of the source
+/// tree with a symbolic link while it is being copied can't make this
+/// function copy from outside of `from`.
+///
+/// The destination tree, however, is written by path. Files are created with
+/// `O_EXCL` or its equivalent, so a symbolic link at the path of a file is
+/// never followed, but a directory of `to` that is replaced with a symbolic
+/// link while it is being copied into can redirect the copy outside of `to`.
+/// Don't copy into a directory that other users can modify.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `from` is not a directory.
+/// * `to` is inside of `from`.
+/// * `to` exists and is not a directory, or a file copied into it already
+///   exists.
+/// * `from` contains a file that is neither a regular file, a directory nor
+///   a symbolic link, such as a named pipe.
+/// * The user lacks permissions to read `from` or to write to `to`.
+///
+/// If an error occurs, the files copied so far are not removed.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::copy_dir_all("assets", "target/assets")?;
+///     Ok(())
+/// }
+/// ```
+#[doc(alias = "cp", alias = "xcopy")]
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn copy_dir_all<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+
+    // The root is always yielded first, and its contents are only opened by
+    // the following call to `next`.
+    let mut entries = walk_dir(from).into_iter();
+    let root = entries.next().unwrap()?;
+    if !root.file_type().is_dir() {
+        return Err(io::const_error!(ErrorKind::NotADirectory, "the source is not a directory"));
+    }
+    if absolute_destination(to)?.starts_with(fs::canonicalize(from)?) {
+        return Err(io::const_error!(
+            ErrorKind::InvalidInput,
+            "cannot copy a directory into itself",
+        ));
+    }
+
+    match fs::create_dir(to) {
+        Err(err) if err.kind() == ErrorKind::AlreadyExists && fs::metadata(to)?.is_dir() => {}
+        result => result?,
+    }
+    copy_dir_contents(&mut entries, to)?;
+    fs::set_permissions(to, root.metadata()?.permissions())
+}
+
+/// Returns the canonical form of `to`, which may not exist yet.
+fn absolute_destination(to: &Path) -> io::Result<PathBuf> {
+    match (to.parent(), to.file_name()) {
+        (Some(parent), Some(name)) if !fs::exists(to)? => {
+            let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+            Ok(fs::canonicalize(parent)?.join(name))
+        }
+        _ => fs::canonicalize(to),
+    }
+}
+
+fn copy_dir_contents(entries: &mut WalkDirIter, to: &Path) -> io::Result<()> {
+    // The destinations of the directories being copied, from the shallowest
+    // down, with the permissions they get once all of their contents are
+    // copied, as they might not be writable.
+    let mut dirs: Vec<(PathBuf, Permissions)> = Vec::new();
+    while let Some(entry) = entries.next() {
+        let entry = entry?;
+        while dirs.len() >= entry.depth() {
+            let (dst, permissions) = dirs.pop().unwrap();
+            fs::set_permissions(&dst, permissions)?;
+        }
+        let parent = dirs.last().map_or(to, |(dst, _)| dst.as_path());
+        let dst = parent.join(entry.file_name());
+        let file_type = entry.file_type();
+
+        if file_type.is_dir() {
+            fs::create_dir(&dst)?;
+            dirs.push((dst, entry.metadata()?.permissions()));
+        } else if file_type.is_symlink() {
+            fs_imp::copy_symlink(entry.path(), &dst)?;
+        } else if file_type.is_file() {
+            let mut reader = File::from_inner(entries.open_file(&entry)?);
+            let metadata = reader.metadata()?;
+            if !metadata.is_file() {
+                return Err(fs_imp::common::NOT_FILE_ERROR);
+            }
+            let mut writer = File::create_new(&dst)?;
+            io::copy(&mut reader, &mut writer)?;
+            writer.set_permissions(metadata.permissions())?;
+        } else {
+            return Err(io::const_error!(
+                ErrorKind::InvalidInput,
+                "cannot copy a file that is neither a regular file, a directory nor a symlink",
+            ));
+        }
+    }
+    for (dst, permissions) in dirs.into_iter().rev() {
+        fs::set_permissions(&dst, permissions)?;
+    }
+    Ok(())
+}
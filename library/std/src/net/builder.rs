@@ -0,0 +1,198 @@
+#[cfg(all(
+    test,
+    not(any(
+        target_os = "emscripten",
+        all(target_os = "wasi", target_env = "p1"),
+        target_os = "xous",
+        target_os = "trusty",
+    ))
+))]
+mod tests;
+
+use crate::fmt;
+use crate::io;
+use crate::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use crate::sys::{FromInner, net as net_imp};
+use crate::time::Duration;
+
+/// A builder for TCP and UDP sockets, which configures a socket before it is
+/// bound or connected.
+///
+/// [`TcpListener::bind`], [`TcpStream::connect`] and [`UdpSocket::bind`]
+/// create, bind or connect a socket in one step, so options that only take
+/// effect if they are set beforehand, like `SO_REUSEPORT` or the backlog of a
+/// listener, can't be set with them. A `SocketBuilder` collects such options
+/// and applies them to every socket it creates.
+///
+/// Options that are not set keep the defaults of the functions above. In
+/// particular, [`listen`](SocketBuilder::listen) enables `SO_REUSEADDR` on
+/// platforms other than Windows unless [`reuse_address`] is set.
+///
+/// [`reuse_address`]: SocketBuilder::reuse_address
+///
+/// # Platform-specific behavior
+///
+/// On platforms without BSD sockets, such as UEFI or SGX, options can only be
+/// set to their defaults: disabled, or a backlog of 128. Creating a socket
+/// with any other option set fails with an error of kind
+/// [`Unsupported`](io::ErrorKind::Unsupported).
+///
+/// # Examples
+///
+/// Bind several listeners to the same port, so that the operating system
+/// distributes incoming connections between them:
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+/// use std::net::SocketBuilder;
+///
+/// fn main() -> std::io::Result<()> {
+///     let listener = SocketBuilder::new()
+///         .reuse_port(true)
+///         .backlog(1024)
+///         .listen("0.0.0.0:8080")?;
+///
+///     for stream in listener.incoming() {
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "socket_builder", issue = "none")]
+#[derive(Clone)]
+pub struct SocketBuilder(net_imp::SocketBuilder);
+
+impl SocketBuilder {
+    /// Creates a new builder with no options set.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    #[must_use]
+    pub fn new() -> SocketBuilder {
+        SocketBuilder(net_imp::SocketBuilder::new())
+    }
+
+    /// Sets the `SO_REUSEADDR` option, which allows binding to an address
+    /// that is still in use by a socket in the `TIME_WAIT` state.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, `SO_REUSEADDR` allows binding to an address that is
+    /// actively in use, which allows other processes to take over the
+    /// connections of a listener.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_address(&mut self, reuse_address: bool) -> &mut Self {
+        self.0.reuse_address(reuse_address);
+        self
+    }
+
+    /// Sets the `SO_REUSEPORT` option, which allows several sockets to be
+    /// bound to the same address and port.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is only supported on Linux, Android, the BSDs and Apple platforms.
+    /// On other platforms, creating a socket with this option enabled fails
+    /// with an error of kind [`Unsupported`](io::ErrorKind::Unsupported).
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn reuse_port(&mut self, reuse_port: bool) -> &mut Self {
+        self.0.reuse_port(reuse_port);
+        self
+    }
+
+    /// Sets the `IPV6_V6ONLY` option, which restricts IPv6 sockets to IPv6
+    /// traffic. If it is unset, an IPv6 socket bound to the unspecified
+    /// address may also accept IPv4 traffic, depending on the platform.
+    ///
+    /// This option is ignored for IPv4 addresses.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn only_v6(&mut self, only_v6: bool) -> &mut Self {
+        self.0.only_v6(only_v6);
+        self
+    }
+
+    /// Sets the maximum number of pending connections of a listener created
+    /// by [`listen`](SocketBuilder::listen).
+    ///
+    /// The operating system may limit the backlog to a smaller value. It is
+    /// 128 on most platforms by default.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn backlog(&mut self, backlog: u32) -> &mut Self {
+        self.0.backlog(backlog);
+        self
+    }
+
+    /// Sets the size of the receive buffer of the socket (`SO_RCVBUF`), in
+    /// bytes.
+    ///
+    /// The operating system may adjust this value. For example, Linux doubles
+    /// it to leave room for bookkeeping.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&mut self, size: usize) -> &mut Self {
+        self.0.recv_buffer_size(size);
+        self
+    }
+
+    /// Sets the size of the send buffer of the socket (`SO_SNDBUF`), in
+    /// bytes.
+    ///
+    /// The operating system may adjust this value. For example, Linux doubles
+    /// it to leave room for bookkeeping.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn send_buffer_size(&mut self, size: usize) -> &mut Self {
+        self.0.send_buffer_size(size);
+        self
+    }
+
+    /// Creates a TCP listener bound to `addr` with the options of this
+    /// builder.
+    ///
+    /// Like [`TcpListener::bind`], this tries each of the addresses `addr`
+    /// resolves to until one succeeds, and returns the error of the last
+    /// attempt otherwise.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn listen<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpListener> {
+        self.0.listen(addr).map(TcpListener::from_inner)
+    }
+
+    /// Opens a TCP connection to `addr` with the options of this builder.
+    ///
+    /// Like [`TcpStream::connect`], this tries each of the addresses `addr`
+    /// resolves to until one succeeds, and returns the error of the last
+    /// attempt otherwise.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpStream> {
+        self.0.connect(addr).map(TcpStream::from_inner)
+    }
+
+    /// Opens a TCP connection to `addr` with a timeout and the options of
+    /// this builder.
+    ///
+    /// See [`TcpStream::connect_timeout`] for more details.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.0.connect_timeout(addr, timeout).map(TcpStream::from_inner)
+    }
+
+    /// Creates a UDP socket bound to `addr` with the options of this builder.
+    ///
+    /// Like [`UdpSocket::bind`], this tries each of the addresses `addr`
+    /// resolves to until one succeeds, and returns the error of the last
+    /// attempt otherwise.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn bind_udp<A: ToSocketAddrs>(&self, addr: A) -> io::Result<UdpSocket> {
+        self.0.bind_udp(addr).map(UdpSocket::from_inner)
+    }
+}
+
+#[unstable(feature = "socket_builder", issue = "none")]
+impl Default for SocketBuilder {
+    fn default() -> SocketBuilder {
+        SocketBuilder::new()
+    }
+}
+
+#[unstable(feature = "socket_builder", issue = "none")]
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::io::prelude::*;
+use crate::net::test::{next_test_ip4, next_test_ip6};
+use crate::net::*;
+use crate::thread;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(t) => t,
+            Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+        }
+    };
+}
+
+#[test]
+fn listen_and_connect() {
+    for addr in [next_test_ip4(), next_test_ip6()] {
+        let listener = t!(SocketBuilder::new().backlog(16).reuse_address(true).listen(&addr));
+        let local_addr = t!(listener.local_addr());
+
+        let _t = thread::spawn(move || {
+            let mut stream = t!(SocketBuilder::new().send_buffer_size(8192).connect(&local_addr));
+            t!(stream.write(&[99]));
+        });
+
+        let mut stream = t!(listener.accept()).0;
+        let mut buf = [0];
+        t!(stream.read(&mut buf));
+        assert_eq!(buf[0], 99);
+    }
+}
+
+#[test]
+fn default_options() {
+    // Setting options to their defaults is supported on every platform.
+    let addr = next_test_ip4();
+    let listener =
+        t!(SocketBuilder::new().reuse_port(false).only_v6(false).backlog(128).listen(&addr));
+    let local_addr = t!(listener.local_addr());
+    t!(SocketBuilder::new().reuse_address(false).reuse_port(false).connect(&local_addr));
+}
+
+#[test]
+fn bind_udp() {
+    let addr = next_test_ip6();
+    let socket = t!(SocketBuilder::new().only_v6(true).recv_buffer_size(8192).bind_udp(&addr));
+    assert_eq!(t!(socket.local_addr()), addr);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+fn reuse_port() {
+    let addr = next_test_ip4();
+    let mut builder = SocketBuilder::new();
+    builder.reuse_port(true);
+    let _first = t!(builder.listen(&addr));
+    let _second = t!(builder.listen(&addr));
+
+    // Sockets without `SO_REUSEPORT` can't share the port.
+    assert!(SocketBuilder::new().listen(&addr).is_err());
+}
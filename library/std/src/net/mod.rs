@@ -8,6 +8,7 @@
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`SocketBuilder`] configures sockets before they are bound or connected
 //! * [`IpAddr`] represents IP addresses of either IPv4 or IPv6; [`Ipv4Addr`] and
 //!   [`Ipv6Addr`] are respectively IPv4 and IPv6 addresses
 //! * [`SocketAddr`] represents socket addresses of either IPv4 or IPv6; [`SocketAddrV4`]
@@ -25,6 +26,8 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::net::AddrParseError;
 
+#[unstable(feature = "socket_builder", issue = "none")]
+pub use self::builder::SocketBuilder;
 #[unstable(feature = "gethostname", issue = "135142")]
 pub use self::hostname::hostname;
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -38,6 +41,7 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
 
+mod builder;
 mod hostname;
 mod ip_addr;
 mod socket_addr;
@@ -11,6 +11,7 @@
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner};
 #[cfg(not(doc))]
 use crate::sys::{fd::FileDesc, linux::pidfd::PidFd as InnerPidFd};
+use crate::time::Duration;
 
 #[cfg(doc)]
 struct InnerPidFd;
@@ -86,6 +87,20 @@ pub fn wait(&self) -> Result<ExitStatus> {
     pub fn try_wait(&self) -> Result<Option<ExitStatus>> {
         Ok(self.inner.try_wait()?.map(FromInner::from_inner))
     }
+
+    /// Waits for the child to exit, giving up once `timeout` has elapsed.
+    ///
+    /// Returns `Ok(None)` if the child is still running when the timeout elapses.
+    /// This blocks on the pidfd with [`poll(2)`] rather than periodically checking
+    /// whether the child has exited.
+    ///
+    /// The same caveats about reaping as for [`try_wait`] apply.
+    ///
+    /// [`try_wait`]: PidFd::try_wait
+    /// [`poll(2)`]: https://man7.org/linux/man-pages/man2/poll.2.html
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<Option<ExitStatus>> {
+        Ok(self.inner.wait_timeout(timeout)?.map(FromInner::from_inner))
+    }
 }
 
 impl AsInner<InnerPidFd> for PidFd {
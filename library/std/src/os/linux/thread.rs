@@ -0,0 +1,54 @@
+//! Linux-specific extensions to primitives in the [`std::thread`] module.
+//!
+//! [`std::thread`]: crate::thread
+
+#![unstable(feature = "thread_spawn_options", issue = "none")]
+
+use crate::sealed::Sealed;
+use crate::sys::AsInnerMut;
+use crate::thread;
+
+/// Linux-specific extensions to [`thread::Builder`].
+///
+/// See also the platform-independent Unix extensions in
+/// [`std::os::unix::thread::BuilderExt`](crate::os::unix::thread::BuilderExt).
+pub trait BuilderExt: Sealed {
+    /// Restricts the new thread to run only on the given CPUs.
+    ///
+    /// The CPUs are identified by their index, as used by `sched_setaffinity`.
+    /// The affinity is applied before the thread starts, so the thread never
+    /// runs on any other CPU.
+    ///
+    /// Spawning the thread fails if an index is out of range, or if none of
+    /// the given CPUs are available to the process. Setting the affinity
+    /// before the thread starts is currently only supported with glibc; on
+    /// other C libraries, spawning the thread fails with
+    /// [`ErrorKind::Unsupported`](crate::io::ErrorKind::Unsupported).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_spawn_options)]
+    ///
+    /// use std::os::linux::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// // Pin the thread to the first two CPUs.
+    /// let handle = thread::Builder::new()
+    ///     .affinity([0, 1])
+    ///     .spawn(|| {
+    ///         // thread code
+    ///     })
+    ///     .unwrap();
+    ///
+    /// handle.join().unwrap();
+    /// ```
+    fn affinity<I: IntoIterator<Item = usize>>(self, cpus: I) -> thread::Builder;
+}
+
+impl BuilderExt for thread::Builder {
+    fn affinity<I: IntoIterator<Item = usize>>(mut self, cpus: I) -> thread::Builder {
+        self.as_inner_mut().affinity(cpus.into_iter().collect());
+        self
+    }
+}
@@ -93,6 +93,17 @@ mod platform {
 pub mod net;
 pub mod process;
 pub mod raw;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+    target_os = "solaris",
+    target_vendor = "apple",
+))]
+pub mod signal;
 pub mod thread;
 
 /// A prelude for conveniently writing platform-specific code.
@@ -0,0 +1,179 @@
+//! Unix-specific signal handling.
+//!
+//! Signal handlers run in a very restricted context: they may interrupt any
+//! code at any point, so they can only call async-signal-safe functions and
+//! must not allocate or take locks. Instead of running user code in the
+//! handler, [`Signals`] forwards the signals it is registered for to a file
+//! descriptor, from which they can be read like any other event, either by
+//! blocking in [`recv`], or by polling the descriptor in an event loop.
+//!
+//! The standard library uses `SIGSEGV` and `SIGBUS` to detect stack overflows,
+//! so these, like the other signals that indicate a fault in the running code,
+//! can't be listened for.
+//!
+//! [`recv`]: Signals::recv
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(unix_signals)]
+//!
+//! use std::os::unix::signal::{SIGHUP, SIGTERM, Signals};
+//!
+//! fn main() -> std::io::Result<()> {
+//!     let signals = Signals::new([SIGTERM, SIGHUP])?;
+//!     loop {
+//!         match signals.recv()? {
+//!             SIGHUP => println!("reloading configuration"),
+//!             _ => break,
+//!         }
+//!     }
+//!     println!("shutting down");
+//!     Ok(())
+//! }
+//! ```
+
+#![unstable(feature = "unix_signals", issue = "none")]
+
+use crate::ffi::c_int;
+use crate::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use crate::sys::signal as imp;
+use crate::{fmt, io};
+
+// Tests for this module
+#[cfg(test)]
+mod tests;
+
+macro_rules! signals {
+    ($($(#[$attr:meta])* $name:ident,)*) => {$(
+        $(#[$attr])*
+        #[unstable(feature = "unix_signals", issue = "none")]
+        pub const $name: c_int = libc::$name;
+    )*};
+}
+
+signals! {
+    /// Hangup of the controlling terminal, commonly used to ask daemons to
+    /// reload their configuration.
+    SIGHUP,
+    /// Interrupt from the keyboard, usually through Ctrl-C.
+    SIGINT,
+    /// Quit from the keyboard.
+    SIGQUIT,
+    /// Timer set by `alarm` expired.
+    SIGALRM,
+    /// Termination request, such as sent by `kill` by default.
+    SIGTERM,
+    /// A child process stopped or terminated.
+    SIGCHLD,
+    /// Write to a pipe with no readers.
+    SIGPIPE,
+    /// User-defined signal 1.
+    SIGUSR1,
+    /// User-defined signal 2.
+    SIGUSR2,
+    /// The terminal window size changed.
+    SIGWINCH,
+}
+
+/// A listener for a set of signals.
+///
+/// While a `Signals` exists, each of the signals it was created for is caught
+/// by a handler installed by the standard library, and queued for this
+/// listener instead of triggering the signal's previous disposition. When all
+/// listeners for a signal have been dropped, the previous disposition is
+/// restored. Several listeners may exist for the same signal, in which case
+/// each of them receives it.
+///
+/// Signals are queued in a pipe, which may be inspected through
+/// [`AsFd`] to integrate a listener into an event loop: it becomes readable
+/// when a signal is pending. Deliveries of the same signal may be coalesced if
+/// the listener does not keep up, as the operating system does for pending
+/// signals too.
+///
+/// Installing handlers is process-global: it replaces handlers set up by other
+/// code, such as the disposition chosen with `-Zon-broken-pipe` for `SIGPIPE`,
+/// for as long as the listener exists.
+///
+/// In particular, listening for [`SIGCHLD`] replaces an ignored disposition,
+/// which makes the operating system reap terminated children automatically.
+/// While the listener exists, terminated children remain zombies until they
+/// are waited for, for example with [`Child::wait`] or `waitpid`.
+///
+/// [`Child::wait`]: crate::process::Child::wait
+#[unstable(feature = "unix_signals", issue = "none")]
+pub struct Signals(imp::Signals);
+
+impl Signals {
+    /// Starts listening for the given signals.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`] error if one of the signals is not a valid
+    /// signal number, or is one of `SIGKILL`, `SIGSTOP`, `SIGSEGV`, `SIGBUS`,
+    /// `SIGILL` and `SIGFPE`, which can't be listened for. On Linux, the
+    /// real-time signals below `SIGRTMIN` are reserved by the C library and
+    /// can't be listened for either. If installing the handler for one of the
+    /// signals fails, the error is returned and the dispositions of all the
+    /// signals are left as they were. Only a limited
+    /// number of listeners may exist at once; creating more fails with
+    /// [`QuotaExceeded`].
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    /// [`QuotaExceeded`]: io::ErrorKind::QuotaExceeded
+    #[unstable(feature = "unix_signals", issue = "none")]
+    pub fn new<I: IntoIterator<Item = c_int>>(signals: I) -> io::Result<Signals> {
+        let signals: Vec<c_int> = signals.into_iter().collect();
+        imp::Signals::new(&signals).map(Signals)
+    }
+
+    /// Blocks until one of the signals is received, and returns its number.
+    #[unstable(feature = "unix_signals", issue = "none")]
+    pub fn recv(&self) -> io::Result<c_int> {
+        self.0.recv()
+    }
+
+    /// Returns the number of a pending signal, if any, without blocking.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_signals)]
+    ///
+    /// use std::os::unix::signal::{SIGCHLD, Signals};
+    ///
+    /// let signals = Signals::new([SIGCHLD])?;
+    /// // ... spawn some children ...
+    /// while let Some(_) = signals.try_recv()? {
+    ///     // reap the children that exited
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "unix_signals", issue = "none")]
+    pub fn try_recv(&self) -> io::Result<Option<c_int>> {
+        self.0.try_recv()
+    }
+}
+
+#[unstable(feature = "unix_signals", issue = "none")]
+impl AsFd for Signals {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+#[unstable(feature = "unix_signals", issue = "none")]
+impl AsRawFd for Signals {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+#[unstable(feature = "unix_signals", issue = "none")]
+impl fmt::Debug for Signals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
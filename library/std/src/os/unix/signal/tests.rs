@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn invalid_signals() {
+    for signal in [0, -1, 1000, libc::SIGKILL, libc::SIGSTOP, libc::SIGSEGV, libc::SIGBUS] {
+        let err = Signals::new([signal]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "signal {signal}");
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[cfg_attr(miri, ignore)] // Miri does not support signals
+fn reserved_signals() {
+    // The C library uses these internally, e.g. glibc reserves 32 and 33.
+    for signal in (32..libc::SIGRTMIN()).chain([libc::SIGRTMAX() + 1]) {
+        let err = Signals::new([signal]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "signal {signal}");
+    }
+    // The real-time signals from `SIGRTMIN` on are available.
+    drop(Signals::new([libc::SIGRTMIN(), libc::SIGRTMAX()]).unwrap());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri does not support signals
+fn reuse_slots() {
+    // Dropping a listener frees its slot for the next one. This uses a signal
+    // that `raise` doesn't listen for, as the tests run in the same process.
+    for _ in 0..100 {
+        let signals = Signals::new([SIGWINCH]).unwrap();
+        unsafe { assert_eq!(libc::raise(SIGWINCH), 0) };
+        assert_eq!(signals.recv().unwrap(), SIGWINCH);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri does not support signals
+fn raise() {
+    let a = Signals::new([SIGUSR1, SIGUSR2]).unwrap();
+    let b = Signals::new([SIGUSR1]).unwrap();
+    assert_eq!(a.try_recv().unwrap(), None);
+
+    unsafe { assert_eq!(libc::raise(SIGUSR1), 0) };
+    assert_eq!(a.recv().unwrap(), SIGUSR1);
+    assert_eq!(b.recv().unwrap(), SIGUSR1);
+
+    unsafe { assert_eq!(libc::raise(SIGUSR2), 0) };
+    assert_eq!(a.recv().unwrap(), SIGUSR2);
+    assert_eq!(b.try_recv().unwrap(), None);
+
+    // With `b` gone, `a` is still listening for SIGUSR1.
+    drop(b);
+    unsafe { assert_eq!(libc::raise(SIGUSR1), 0) };
+    assert_eq!(a.try_recv().unwrap(), Some(SIGUSR1));
+}
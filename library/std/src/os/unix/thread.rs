@@ -4,10 +4,12 @@
 
 #![stable(feature = "thread_extensions", since = "1.9.0")]
 
+use crate::ffi::c_int;
 #[allow(deprecated)]
 use crate::os::unix::raw::pthread_t;
-use crate::sys::{AsInner, IntoInner};
-use crate::thread::JoinHandle;
+use crate::sealed::Sealed;
+use crate::sys::{AsInner, AsInnerMut, IntoInner};
+use crate::thread::{self, JoinHandle};
 
 #[stable(feature = "thread_extensions", since = "1.9.0")]
 #[allow(deprecated)]
@@ -39,3 +41,105 @@ fn into_pthread_t(self) -> RawPthread {
         self.into_inner().into_id() as RawPthread
     }
 }
+
+/// Unix-specific extensions to [`thread::Builder`].
+///
+/// The options set through this trait are applied to the thread's attributes
+/// before it is created, so they are already in effect when it starts running.
+/// If the platform does not support an option, or rejects its value, spawning
+/// the thread fails with the corresponding error.
+#[unstable(feature = "thread_spawn_options", issue = "none")]
+pub trait BuilderExt: Sealed {
+    /// Sets the size of the guard area at the end of the new thread's stack.
+    ///
+    /// Overflowing the stack into the guard area reliably causes a fault
+    /// instead of silently corrupting other memory. A size of zero disables
+    /// the guard area. The size may be rounded up to a multiple of the page
+    /// size by the platform.
+    ///
+    /// This corresponds to `pthread_attr_setguardsize`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_spawn_options)]
+    ///
+    /// use std::os::unix::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// let handle = thread::Builder::new()
+    ///     .guard_size(64 * 1024)
+    ///     .spawn(|| {
+    ///         // thread code
+    ///     })
+    ///     .unwrap();
+    ///
+    /// handle.join().unwrap();
+    /// ```
+    #[unstable(feature = "thread_spawn_options", issue = "none")]
+    fn guard_size(self, size: usize) -> thread::Builder;
+
+    /// Sets the scheduling policy of the new thread, such as `SCHED_FIFO` or
+    /// `SCHED_RR`.
+    ///
+    /// By default, a new thread inherits the scheduling policy and priority of
+    /// the thread spawning it. Setting a policy or a [priority] makes the new
+    /// thread use the explicitly given ones instead. Real-time policies usually
+    /// require elevated privileges, without which spawning the thread fails.
+    ///
+    /// This corresponds to `pthread_attr_setschedpolicy`.
+    ///
+    /// [priority]: BuilderExt::sched_priority
+    #[unstable(feature = "thread_spawn_options", issue = "none")]
+    fn sched_policy(self, policy: c_int) -> thread::Builder;
+
+    /// Sets the scheduling priority of the new thread.
+    ///
+    /// The valid range of priorities depends on the [scheduling policy], see
+    /// `sched_get_priority_min` and `sched_get_priority_max`.
+    ///
+    /// This corresponds to the `sched_priority` field set by
+    /// `pthread_attr_setschedparam`.
+    ///
+    /// [scheduling policy]: BuilderExt::sched_policy
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(thread_spawn_options)]
+    ///
+    /// use std::os::unix::thread::BuilderExt;
+    /// use std::thread;
+    ///
+    /// # const SCHED_FIFO: i32 = 1;
+    /// let handle = thread::Builder::new()
+    ///     .sched_policy(SCHED_FIFO)
+    ///     .sched_priority(10)
+    ///     .spawn(|| {
+    ///         // latency-sensitive work
+    ///     })
+    ///     .expect("failed to spawn real-time thread");
+    ///
+    /// handle.join().unwrap();
+    /// ```
+    #[unstable(feature = "thread_spawn_options", issue = "none")]
+    fn sched_priority(self, priority: c_int) -> thread::Builder;
+}
+
+#[unstable(feature = "thread_spawn_options", issue = "none")]
+impl BuilderExt for thread::Builder {
+    fn guard_size(mut self, size: usize) -> thread::Builder {
+        self.as_inner_mut().guard_size(size);
+        self
+    }
+
+    fn sched_policy(mut self, policy: c_int) -> thread::Builder {
+        self.as_inner_mut().sched_policy(policy);
+        self
+    }
+
+    fn sched_priority(mut self, priority: c_int) -> thread::Builder {
+        self.as_inner_mut().sched_priority(priority);
+        self
+    }
+}
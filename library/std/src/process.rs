@@ -168,6 +168,7 @@
 use crate::num::NonZero;
 use crate::path::Path;
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner, process as imp};
+use crate::time::Duration;
 use crate::{fmt, format_args_nl, fs, str};
 
 /// Representation of a running or exited child process.
@@ -2333,6 +2334,53 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit, giving up once `timeout` has elapsed.
+    ///
+    /// If the child exits before the timeout, then `Ok(Some(status))` is
+    /// returned and, as with [`try_wait`], the process ID is reaped on Unix.
+    /// If the timeout elapses first, then `Ok(None)` is returned and the child
+    /// is left running. If an error occurs, then that error is returned.
+    ///
+    /// A zero `timeout` behaves like [`try_wait`]. Like `try_wait`, and unlike
+    /// [`wait`], this function will not attempt to drop stdin.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, if the child was spawned with a pidfd, this blocks on the
+    /// pidfd with [`poll(2)`]. On Windows, this uses `WaitForSingleObject`.
+    /// Elsewhere, this function is implemented by periodically checking whether
+    /// the child has exited, so it may return slightly after the child exits.
+    /// Note that this [may change in the future][changes].
+    ///
+    /// [`try_wait`]: Child::try_wait
+    /// [`wait`]: Child::wait
+    /// [`poll(2)`]: https://man7.org/linux/man-pages/man2/poll.2.html
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("60").spawn()?;
+    ///
+    /// match child.wait_timeout(Duration::from_secs(5))? {
+    ///     Some(status) => println!("exited with: {status}"),
+    ///     None => {
+    ///         println!("child is taking too long, killing it");
+    ///         child.kill()?;
+    ///         child.wait()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.wait_timeout(timeout)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -85,6 +85,28 @@ fn signal_reported_right() {
     }
 }
 
+#[test]
+#[cfg(unix)]
+#[cfg_attr(
+    any(target_os = "vxworks", all(target_vendor = "apple", not(target_os = "macos"))),
+    ignore = "no shell available"
+)]
+fn wait_timeout() {
+    use crate::time::Duration;
+
+    let mut p = shell_cmd().arg("-c").arg("read a").stdin(Stdio::piped()).spawn().unwrap();
+    assert!(p.wait_timeout(Duration::ZERO).unwrap().is_none());
+    assert!(p.wait_timeout(Duration::from_millis(50)).unwrap().is_none());
+
+    // Closing stdin makes `read` fail, so the shell exits with a non-zero code.
+    drop(p.stdin.take());
+    let status = p.wait_timeout(Duration::from_secs(60)).unwrap().expect("child did not exit");
+    assert!(!status.success());
+
+    // Once the child has been reaped, the cached status is returned immediately.
+    assert_eq!(p.wait_timeout(Duration::ZERO).unwrap(), Some(status));
+}
+
 pub fn run_output(mut cmd: Command) -> String {
     let p = cmd.spawn();
     assert!(p.is_ok());
@@ -155,6 +155,9 @@
 //!   writer at a time. In some cases, this can be more efficient than
 //!   a mutex.
 //!
+//! - [`Semaphore`]: A counting semaphore, limiting how many threads can
+//!   hold one of its permits at the same time.
+//!
 //! [`Arc`]: crate::sync::Arc
 //! [`Barrier`]: crate::sync::Barrier
 //! [`Condvar`]: crate::sync::Condvar
@@ -164,6 +167,7 @@
 //! [`Once`]: crate::sync::Once
 //! [`OnceLock`]: crate::sync::OnceLock
 //! [`RwLock`]: crate::sync::RwLock
+//! [`Semaphore`]: crate::sync::Semaphore
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
@@ -243,6 +247,10 @@
 #[doc(inline)]
 pub use self::poison::{MappedMutexGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard};
 
+#[unstable(feature = "semaphore", issue = "none")]
+#[doc(inline)]
+pub use self::poison::{Semaphore, SemaphorePermit};
+
 /// A type indicating whether a timed wait on a condition variable returned
 /// due to a time out or not.
 ///
@@ -39,7 +39,10 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 pub use self::rwlock::{MappedRwLockReadGuard, MappedRwLockWriteGuard};
 #[unstable(feature = "nonpoison_rwlock", issue = "134645")]
 pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[unstable(feature = "nonpoison_semaphore", issue = "none")]
+pub use self::semaphore::{Semaphore, SemaphorePermit};
 
 mod condvar;
 mod mutex;
 mod rwlock;
+mod semaphore;
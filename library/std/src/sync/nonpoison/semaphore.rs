@@ -0,0 +1,232 @@
+use crate::fmt;
+use crate::mem;
+use crate::sync::nonpoison::{TryLockResult, WouldBlock};
+use crate::sys::sync as sys;
+use crate::time::Duration;
+
+/// A counting semaphore that does not keep track of poisoning.
+///
+/// For more information about semaphores, check out the documentation for the
+/// poisoning variant of this type at [`poison::Semaphore`].
+///
+/// [`poison::Semaphore`]: crate::sync::poison::Semaphore
+///
+/// # Examples
+///
+/// ```
+/// #![feature(nonpoison_semaphore)]
+///
+/// use std::sync::{Arc, nonpoison::Semaphore};
+/// use std::thread;
+///
+/// // Allow at most two threads to do work at the same time.
+/// let semaphore = Arc::new(Semaphore::new(2));
+///
+/// let handles: Vec<_> = (0..8)
+///     .map(|_| {
+///         let semaphore = Arc::clone(&semaphore);
+///         thread::spawn(move || {
+///             let _permit = semaphore.acquire();
+///             // ... do work ...
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// assert_eq!(semaphore.available_permits(), 2);
+/// ```
+#[unstable(feature = "nonpoison_semaphore", issue = "none")]
+pub struct Semaphore {
+    inner: sys::Semaphore,
+}
+
+/// An RAII guard holding permits acquired from a [`Semaphore`].
+///
+/// The permits are released back to the semaphore when this guard is dropped,
+/// unless [`forget`] is called.
+///
+/// This structure is created by the [`acquire`] and [`try_acquire`] methods on
+/// [`Semaphore`], and their variants.
+///
+/// [`forget`]: SemaphorePermit::forget
+/// [`acquire`]: Semaphore::acquire
+/// [`try_acquire`]: Semaphore::try_acquire
+#[must_use = "if unused the permits will immediately be released"]
+#[unstable(feature = "nonpoison_semaphore", issue = "none")]
+#[clippy::has_significant_drop]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl Semaphore {
+    /// The maximum number of permits a semaphore can hold.
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub const MAX_PERMITS: usize = sys::SEMAPHORE_MAX_PERMITS;
+
+    /// Creates a new semaphore with the given number of available permits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is greater than [`MAX_PERMITS`](Self::MAX_PERMITS).
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    #[inline]
+    pub const fn new(permits: usize) -> Semaphore {
+        Semaphore { inner: sys::Semaphore::new(permits) }
+    }
+
+    /// Acquires a single permit, blocking the current thread until one is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(nonpoison_semaphore)]
+    ///
+    /// use std::sync::nonpoison::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.acquire();
+    /// assert_eq!(semaphore.available_permits(), 0);
+    /// drop(permit);
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1)
+    }
+
+    /// Acquires `n` permits at once, blocking the current thread until they
+    /// are all available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than [`MAX_PERMITS`](Self::MAX_PERMITS), as
+    /// such a request could never be satisfied.
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn acquire_many(&self, n: usize) -> SemaphorePermit<'_> {
+        self.inner.acquire(n, None);
+        SemaphorePermit { semaphore: self, permits: n }
+    }
+
+    /// Attempts to acquire a single permit without blocking.
+    ///
+    /// # Errors
+    ///
+    /// If no permit is available, this returns [`WouldBlock`].
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn try_acquire(&self) -> TryLockResult<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire `n` permits at once without blocking.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `n` permits are available, this returns [`WouldBlock`]
+    /// and no permits are taken.
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn try_acquire_many(&self, n: usize) -> TryLockResult<SemaphorePermit<'_>> {
+        if self.inner.try_acquire(n) {
+            Ok(SemaphorePermit { semaphore: self, permits: n })
+        } else {
+            Err(WouldBlock)
+        }
+    }
+
+    /// Acquires a single permit, blocking the current thread for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// If no permit became available before the timeout elapsed, this returns
+    /// [`WouldBlock`].
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn acquire_timeout(&self, timeout: Duration) -> TryLockResult<SemaphorePermit<'_>> {
+        self.acquire_many_timeout(1, timeout)
+    }
+
+    /// Acquires `n` permits at once, blocking the current thread for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// If the permits did not become available before the timeout elapsed,
+    /// this returns [`WouldBlock`] and no permits are taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than [`MAX_PERMITS`](Self::MAX_PERMITS).
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn acquire_many_timeout(
+        &self,
+        n: usize,
+        timeout: Duration,
+    ) -> TryLockResult<SemaphorePermit<'_>> {
+        if self.inner.acquire(n, Some(timeout)) {
+            Ok(SemaphorePermit { semaphore: self, permits: n })
+        } else {
+            Err(WouldBlock)
+        }
+    }
+
+    /// Adds `n` new permits to the semaphore, waking up threads waiting for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would make the number of available permits exceed
+    /// [`MAX_PERMITS`](Self::MAX_PERMITS).
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn add_permits(&self, n: usize) {
+        self.inner.release(n);
+    }
+
+    /// Returns the number of permits that are currently available.
+    ///
+    /// If other threads are active, the returned value may already be out of
+    /// date by the time it is used.
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn available_permits(&self) -> usize {
+        self.inner.available()
+    }
+}
+
+#[unstable(feature = "nonpoison_semaphore", issue = "none")]
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("available_permits", &self.available_permits())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held by this guard.
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes the guard without releasing its permits.
+    ///
+    /// The permits are permanently removed from the semaphore. They can be
+    /// replaced with [`Semaphore::add_permits`].
+    #[unstable(feature = "nonpoison_semaphore", issue = "none")]
+    pub fn forget(self) {
+        mem::forget(self);
+    }
+}
+
+#[unstable(feature = "nonpoison_semaphore", issue = "none")]
+impl Drop for SemaphorePermit<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.semaphore.inner.release(self.permits);
+    }
+}
+
+#[unstable(feature = "nonpoison_semaphore", issue = "none")]
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit").field("permits", &self.permits).finish_non_exhaustive()
+    }
+}
@@ -52,6 +52,13 @@
 //!   while it is locked exclusively (write mode). If a panic occurs in any reader,
 //!   then the lock will not be poisoned.
 //!
+//! - [`Semaphore`]: A counting semaphore, which hands out a limited number of
+//!   permits to the threads acquiring them.
+//!
+//!   The semaphore becomes poisoned if a thread panics while holding permits.
+//!   The acquired permits are still handed out when it is poisoned, inside the
+//!   [`PoisonError`].
+//!
 //! Note that the [`Once`] type also employs poisoning, but since it has non-poisoning `force`
 //! methods available on it, there is no separate `nonpoison` and `poison` version.
 //!
@@ -70,6 +77,8 @@
 pub use self::rwlock::{MappedRwLockReadGuard, MappedRwLockWriteGuard};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[unstable(feature = "semaphore", issue = "none")]
+pub use self::semaphore::{Semaphore, SemaphorePermit};
 use crate::error::Error;
 use crate::fmt;
 #[cfg(panic = "unwind")]
@@ -81,6 +90,7 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 mod mutex;
 mod rwlock;
+mod semaphore;
 
 pub(crate) struct Flag {
     #[cfg(panic = "unwind")]
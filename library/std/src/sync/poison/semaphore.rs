@@ -0,0 +1,360 @@
+use crate::fmt;
+use crate::mem;
+use crate::sync::{LockResult, TryLockError, TryLockResult, poison};
+use crate::sys::sync as sys;
+use crate::time::Duration;
+
+/// A counting semaphore.
+///
+/// A semaphore holds a number of permits. Threads can acquire permits, blocking
+/// until enough of them are available, and give them back when they are done,
+/// which makes a semaphore useful for limiting how many threads can use some
+/// resource at the same time.
+///
+/// Acquiring permits returns a [`SemaphorePermit`] guard, which hands the
+/// permits back to the semaphore when it is dropped.
+///
+/// # Poisoning
+///
+/// Like [`Mutex`], this semaphore becomes poisoned if a thread panics while
+/// holding permits. After that, the methods acquiring permits will return an
+/// error which still contains the acquired permits, so it is possible to
+/// ignore the poisoning and carry on. For a semaphore that does not keep track
+/// of poisoning, see [`nonpoison::Semaphore`].
+///
+/// [`Mutex`]: crate::sync::Mutex
+/// [`nonpoison::Semaphore`]: crate::sync::nonpoison::Semaphore
+///
+/// # Examples
+///
+/// ```
+/// #![feature(semaphore)]
+///
+/// use std::sync::{Arc, Semaphore};
+/// use std::thread;
+///
+/// // Allow at most two threads to do work at the same time.
+/// let semaphore = Arc::new(Semaphore::new(2));
+///
+/// let handles: Vec<_> = (0..8)
+///     .map(|_| {
+///         let semaphore = Arc::clone(&semaphore);
+///         thread::spawn(move || {
+///             let _permit = semaphore.acquire().unwrap();
+///             // ... do work ...
+///         })
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// assert_eq!(semaphore.available_permits(), 2);
+/// ```
+#[unstable(feature = "semaphore", issue = "none")]
+pub struct Semaphore {
+    inner: sys::Semaphore,
+    poison: poison::Flag,
+}
+
+/// An RAII guard holding permits acquired from a [`Semaphore`].
+///
+/// The permits are released back to the semaphore when this guard is dropped,
+/// unless [`forget`] is called.
+///
+/// This structure is created by the [`acquire`] and [`try_acquire`] methods on
+/// [`Semaphore`], and their variants.
+///
+/// [`forget`]: SemaphorePermit::forget
+/// [`acquire`]: Semaphore::acquire
+/// [`try_acquire`]: Semaphore::try_acquire
+#[must_use = "if unused the permits will immediately be released"]
+#[unstable(feature = "semaphore", issue = "none")]
+#[clippy::has_significant_drop]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+    poison: poison::Guard,
+}
+
+impl Semaphore {
+    /// The maximum number of permits a semaphore can hold.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub const MAX_PERMITS: usize = sys::SEMAPHORE_MAX_PERMITS;
+
+    /// Creates a new semaphore with the given number of available permits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is greater than [`MAX_PERMITS`](Self::MAX_PERMITS).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(3);
+    /// assert_eq!(semaphore.available_permits(), 3);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    #[inline]
+    pub const fn new(permits: usize) -> Semaphore {
+        Semaphore { inner: sys::Semaphore::new(permits), poison: poison::Flag::new() }
+    }
+
+    /// Acquires a single permit, blocking the current thread until one is available.
+    ///
+    /// # Errors
+    ///
+    /// If the semaphore is poisoned, the permit is still acquired, and is
+    /// returned inside the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.acquire().unwrap();
+    /// assert_eq!(semaphore.available_permits(), 0);
+    /// drop(permit);
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire(&self) -> LockResult<SemaphorePermit<'_>> {
+        self.acquire_many(1)
+    }
+
+    /// Acquires `n` permits at once, blocking the current thread until they
+    /// are all available.
+    ///
+    /// The permits are taken in one step, so a thread waiting for many permits
+    /// never holds on to some of them while waiting for the rest.
+    ///
+    /// # Errors
+    ///
+    /// If the semaphore is poisoned, the permits are still acquired, and are
+    /// returned inside the error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than [`MAX_PERMITS`](Self::MAX_PERMITS), as
+    /// such a request could never be satisfied.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire_many(&self, n: usize) -> LockResult<SemaphorePermit<'_>> {
+        self.inner.acquire(n, None);
+        SemaphorePermit::new(self, n)
+    }
+
+    /// Attempts to acquire a single permit without blocking.
+    ///
+    /// # Errors
+    ///
+    /// If no permit is available, this returns [`WouldBlock`]. If the semaphore
+    /// is poisoned and a permit could be acquired, this returns [`Poisoned`],
+    /// which contains the permit.
+    ///
+    /// [`WouldBlock`]: TryLockError::WouldBlock
+    /// [`Poisoned`]: TryLockError::Poisoned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.try_acquire().unwrap();
+    /// assert!(semaphore.try_acquire().is_err());
+    /// drop(permit);
+    /// assert!(semaphore.try_acquire().is_ok());
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn try_acquire(&self) -> TryLockResult<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire `n` permits at once without blocking.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `n` permits are available, this returns [`WouldBlock`]
+    /// and no permits are taken. If the semaphore is poisoned and the permits
+    /// could be acquired, this returns [`Poisoned`], which contains the permits.
+    ///
+    /// [`WouldBlock`]: TryLockError::WouldBlock
+    /// [`Poisoned`]: TryLockError::Poisoned
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn try_acquire_many(&self, n: usize) -> TryLockResult<SemaphorePermit<'_>> {
+        if self.inner.try_acquire(n) {
+            Ok(SemaphorePermit::new(self, n)?)
+        } else {
+            Err(TryLockError::WouldBlock)
+        }
+    }
+
+    /// Acquires a single permit, blocking the current thread for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// If no permit became available before the timeout elapsed, this returns
+    /// [`WouldBlock`]. If the semaphore is poisoned and a permit could be
+    /// acquired, this returns [`Poisoned`], which contains the permit.
+    ///
+    /// [`WouldBlock`]: TryLockError::WouldBlock
+    /// [`Poisoned`]: TryLockError::Poisoned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::{Semaphore, TryLockError};
+    /// use std::time::Duration;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// let result = semaphore.acquire_timeout(Duration::from_millis(10));
+    /// assert!(matches!(result, Err(TryLockError::WouldBlock)));
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire_timeout(&self, timeout: Duration) -> TryLockResult<SemaphorePermit<'_>> {
+        self.acquire_many_timeout(1, timeout)
+    }
+
+    /// Acquires `n` permits at once, blocking the current thread for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// If the permits did not become available before the timeout elapsed,
+    /// this returns [`WouldBlock`] and no permits are taken. If the semaphore
+    /// is poisoned and the permits could be acquired, this returns
+    /// [`Poisoned`], which contains the permits.
+    ///
+    /// [`WouldBlock`]: TryLockError::WouldBlock
+    /// [`Poisoned`]: TryLockError::Poisoned
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than [`MAX_PERMITS`](Self::MAX_PERMITS).
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire_many_timeout(
+        &self,
+        n: usize,
+        timeout: Duration,
+    ) -> TryLockResult<SemaphorePermit<'_>> {
+        if self.inner.acquire(n, Some(timeout)) {
+            Ok(SemaphorePermit::new(self, n)?)
+        } else {
+            Err(TryLockError::WouldBlock)
+        }
+    }
+
+    /// Adds `n` new permits to the semaphore, waking up threads waiting for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would make the number of available permits exceed
+    /// [`MAX_PERMITS`](Self::MAX_PERMITS).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// semaphore.add_permits(2);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn add_permits(&self, n: usize) {
+        self.inner.release(n);
+    }
+
+    /// Returns the number of permits that are currently available.
+    ///
+    /// If other threads are active, the returned value may already be out of
+    /// date by the time it is used.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn available_permits(&self) -> usize {
+        self.inner.available()
+    }
+
+    /// Determines whether the semaphore is poisoned.
+    ///
+    /// If another thread is active, the semaphore can still become poisoned at
+    /// any time. You should not trust a `false` value for program correctness
+    /// without additional synchronization.
+    #[unstable(feature = "semaphore", issue = "none")]
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.poison.get()
+    }
+
+    /// Clears the poisoned state from a semaphore.
+    ///
+    /// If the semaphore is poisoned, it will remain poisoned until this
+    /// function is called.
+    #[unstable(feature = "semaphore", issue = "none")]
+    #[inline]
+    pub fn clear_poison(&self) {
+        self.poison.clear();
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("available_permits", &self.available_permits())
+            .field("poisoned", &self.poison.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> SemaphorePermit<'a> {
+    fn new(semaphore: &'a Semaphore, permits: usize) -> LockResult<SemaphorePermit<'a>> {
+        poison::map_result(semaphore.poison.guard(), |guard| SemaphorePermit {
+            semaphore,
+            permits,
+            poison: guard,
+        })
+    }
+
+    /// Returns the number of permits held by this guard.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes the guard without releasing its permits.
+    ///
+    /// The permits are permanently removed from the semaphore. They can be
+    /// replaced with [`Semaphore::add_permits`].
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn forget(self) {
+        mem::forget(self);
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl Drop for SemaphorePermit<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.semaphore.poison.done(&self.poison);
+        self.semaphore.inner.release(self.permits);
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit").field("permits", &self.permits).finish_non_exhaustive()
+    }
+}
@@ -1,8 +1,9 @@
 #![allow(dead_code)] // not used on all platforms
 
+use crate::ffi::OsStr;
 use crate::io::{self, Error, ErrorKind};
 use crate::path::{Path, PathBuf};
-use crate::sys::fs::{File, OpenOptions};
+use crate::sys::fs::{DirEntry, File, OpenOptions, ReadDir};
 use crate::sys::helpers::ignore_notfound;
 use crate::{fmt, fs};
 
@@ -52,6 +53,48 @@ fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
     ignore_notfound(fs::remove_dir(path))
 }
 
+/// A directory that is being walked.
+///
+/// This fallback opens everything by path. It checks that a subdirectory is
+/// not a symlink before opening it, but unlike the implementations based on
+/// directory handles it can't rule out that it is replaced in between.
+pub struct WalkDirStream(ReadDir);
+
+impl WalkDirStream {
+    pub fn open(path: &Path) -> io::Result<WalkDirStream> {
+        crate::sys::fs::read_dir(path).map(WalkDirStream)
+    }
+
+    pub fn open_child(
+        &self,
+        _name: &OsStr,
+        path: &Path,
+        follow_links: bool,
+    ) -> io::Result<WalkDirStream> {
+        if !follow_links && fs::symlink_metadata(path)?.file_type().is_symlink() {
+            return Err(io::const_error!(
+                ErrorKind::NotADirectory,
+                "the directory was replaced with a symlink",
+            ));
+        }
+        Self::open(path)
+    }
+
+    pub fn open_file(&self, _name: &OsStr, path: &Path) -> io::Result<File> {
+        let mut opts = OpenOptions::new();
+        opts.read(true);
+        File::open(path, &opts)
+    }
+}
+
+impl Iterator for WalkDirStream {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        self.0.next()
+    }
+}
+
 pub fn exists(path: &Path) -> io::Result<bool> {
     match fs::metadata(path) {
         Ok(_) => Ok(true),
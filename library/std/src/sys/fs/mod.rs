@@ -51,6 +51,13 @@
     }
 }
 
+#[cfg(not(any(target_family = "unix", target_os = "wasi", target_os = "windows")))]
+pub use common::WalkDirStream;
+#[cfg(any(target_family = "unix", target_os = "wasi"))]
+pub use unix::WalkDirStream;
+#[cfg(target_os = "windows")]
+pub use windows::WalkDirStream;
+
 // FIXME: Replace this with platform-specific path conversion functions.
 #[cfg(not(any(target_family = "unix", target_os = "windows", target_os = "wasi")))]
 #[inline]
@@ -102,6 +109,20 @@ pub fn symlink(original: &Path, link: &Path) -> io::Result<()> {
     })
 }
 
+/// Creates a symlink at `link` pointing to the same target as the symlink at
+/// `original`.
+pub fn copy_symlink(original: &Path, link: &Path) -> io::Result<()> {
+    let target = read_link(original)?;
+    #[cfg(windows)]
+    return imp::symlink_inner(
+        &target,
+        link,
+        symlink_metadata(original)?.file_type().is_symlink_dir(),
+    );
+    #[cfg(not(windows))]
+    symlink(&target, link)
+}
+
 pub fn hard_link(original: &Path, link: &Path) -> io::Result<()> {
     with_native_path(original, &|original| {
         with_native_path(link, &|link| imp::link(original, link))
@@ -2687,3 +2687,130 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         run_path_with_cstr(p, &remove_dir_all_modern)
     }
 }
+
+pub use walk_dir_impl::WalkDirStream;
+
+// Fallback for REDOX, ESP-ID, Horizon, Vita, Vxworks and Miri
+#[cfg(any(
+    target_os = "redox",
+    target_os = "espidf",
+    target_os = "horizon",
+    target_os = "vita",
+    target_os = "nto",
+    target_os = "vxworks",
+    miri
+))]
+mod walk_dir_impl {
+    pub use crate::sys::fs::common::WalkDirStream;
+}
+
+// Modern implementation using openat() and fdopendir(), sharing the symlink handling of
+// `remove_dir_all`
+#[cfg(not(any(
+    target_os = "redox",
+    target_os = "espidf",
+    target_os = "horizon",
+    target_os = "vita",
+    target_os = "nto",
+    target_os = "vxworks",
+    miri
+)))]
+mod walk_dir_impl {
+    #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+    use libc::{fdopendir, openat};
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    use libc::{fdopendir, openat64 as openat};
+
+    use super::remove_dir_impl::openat_nofollow_dironly;
+    use super::{
+        AsRawFd, DirEntry, DirStream, File, FromRawFd, InnerReadDir, IntoRawFd, OwnedFd, RawFd,
+        ReadDir,
+    };
+    use crate::ffi::{CStr, OsStr};
+    use crate::io;
+    use crate::path::Path;
+    use crate::sys::cvt_r;
+    use crate::sys::fd::FileDesc;
+    use crate::sys::helpers::{run_path_with_cstr, run_with_cstr};
+
+    /// A directory that is being walked.
+    ///
+    /// Subdirectories and files are opened relative to the file descriptor of
+    /// the directory instead of by path, so replacing a directory that is
+    /// being walked with a symlink can't redirect the walk elsewhere.
+    pub struct WalkDirStream {
+        dir: ReadDir,
+        // owned by `dir`, closed by libc::closedir()
+        fd: RawFd,
+    }
+
+    fn openat_dironly(parent_fd: RawFd, p: &CStr) -> io::Result<OwnedFd> {
+        let fd = cvt_r(|| unsafe {
+            openat(parent_fd, p.as_ptr(), libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY)
+        })?;
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    impl WalkDirStream {
+        pub fn open(path: &Path) -> io::Result<WalkDirStream> {
+            let fd = run_path_with_cstr(path, &|p| openat_dironly(libc::AT_FDCWD, p))?;
+            Self::from_fd(fd, path)
+        }
+
+        /// Opens the subdirectory `name`, whose full path is `path`. Unless
+        /// `follow_links` is set, this fails if `name` is a symlink.
+        pub fn open_child(
+            &self,
+            name: &OsStr,
+            path: &Path,
+            follow_links: bool,
+        ) -> io::Result<WalkDirStream> {
+            let fd = run_with_cstr(name.as_encoded_bytes(), &|name| {
+                if follow_links {
+                    openat_dironly(self.fd, name)
+                } else {
+                    openat_nofollow_dironly(Some(self.fd), name)
+                }
+            })?;
+            Self::from_fd(fd, path)
+        }
+
+        /// Opens the file `name` for reading without following symlinks.
+        pub fn open_file(&self, name: &OsStr, _path: &Path) -> io::Result<File> {
+            let fd = run_with_cstr(name.as_encoded_bytes(), &|name| {
+                // O_NONBLOCK keeps this from hanging if the file was replaced
+                // with a FIFO, it has no effect on reading regular files.
+                cvt_r(|| unsafe {
+                    openat(
+                        self.fd,
+                        name.as_ptr(),
+                        libc::O_CLOEXEC | libc::O_RDONLY | libc::O_NOFOLLOW | libc::O_NONBLOCK,
+                    )
+                })
+            })?;
+            Ok(File(unsafe { FileDesc::from_raw_fd(fd) }))
+        }
+
+        fn from_fd(dir_fd: OwnedFd, path: &Path) -> io::Result<WalkDirStream> {
+            let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
+            if ptr.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let dirp = DirStream(ptr);
+            // file descriptor is automatically closed by libc::closedir() now, so give up ownership
+            let fd = dir_fd.into_raw_fd();
+            // unlike in `remove_dir_all`, the real root is needed because `DirEntry`s may fall back
+            // to functions involving their full path on some platforms
+            let inner = InnerReadDir { dirp, root: path.to_path_buf() };
+            Ok(WalkDirStream { dir: ReadDir::new(inner), fd })
+        }
+    }
+
+    impl Iterator for WalkDirStream {
+        type Item = io::Result<DirEntry>;
+
+        fn next(&mut self) -> Option<io::Result<DirEntry>> {
+            self.dir.next()
+        }
+    }
+}
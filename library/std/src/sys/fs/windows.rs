@@ -22,6 +22,8 @@
 pub use dir::Dir;
 mod remove_dir_all;
 use remove_dir_all::remove_dir_all_iterative;
+mod walk_dir;
+pub use walk_dir::WalkDirStream;
 
 pub struct File {
     handle: Handle,
@@ -169,6 +171,27 @@ fn new(root: &Arc<PathBuf>, wfd: &c::WIN32_FIND_DATAW) -> Option<DirEntry> {
         Some(DirEntry { root: root.clone(), data: *wfd })
     }
 
+    /// Creates an entry from a directory listing that was read through a
+    /// handle to the directory, see `DirBuff`.
+    fn from_dir_buff(root: &Arc<PathBuf>, entry: &DirBuffEntry<'_>) -> io::Result<DirEntry> {
+        let mut data = c::WIN32_FIND_DATAW::default();
+        // Leave room for the nul terminator.
+        if entry.name.len() >= data.cFileName.len() {
+            return Err(io::const_error!(io::ErrorKind::InvalidFilename, "file name is too long"));
+        }
+        data.cFileName[..entry.name.len()].copy_from_slice(&entry.name);
+        let filetime =
+            |t: i64| c::FILETIME { dwLowDateTime: t as u32, dwHighDateTime: (t >> 32) as u32 };
+        data.dwFileAttributes = entry.attributes;
+        data.ftCreationTime = filetime(entry.creation_time);
+        data.ftLastAccessTime = filetime(entry.last_access_time);
+        data.ftLastWriteTime = filetime(entry.last_write_time);
+        data.nFileSizeHigh = (entry.file_size >> 32) as u32;
+        data.nFileSizeLow = entry.file_size as u32;
+        data.dwReserved0 = entry.reparse_tag;
+        Ok(DirEntry { root: root.clone(), data })
+    }
+
     pub fn path(&self) -> PathBuf {
         self.root.join(self.file_name())
     }
@@ -937,9 +960,26 @@ fn as_ref(&self) -> &[MaybeUninit<u8>] {
     }
 }
 
+/// An entry stored in a `DirBuff`.
+struct DirBuffEntry<'a> {
+    /// The file name, UTF-16 encoded.
+    name: Cow<'a, [u16]>,
+    attributes: u32,
+    /// The reparse tag, if this is a reparse point.
+    reparse_tag: u32,
+    creation_time: i64,
+    last_access_time: i64,
+    last_write_time: i64,
+    file_size: i64,
+}
+
+impl DirBuffEntry<'_> {
+    fn is_directory(&self) -> bool {
+        self.attributes & c::FILE_ATTRIBUTE_DIRECTORY != 0
+    }
+}
+
 /// An iterator over entries stored in a `DirBuff`.
-///
-/// Currently only returns file names (UTF-16 encoded).
 struct DirBuffIter<'a> {
     buffer: Option<&'a [MaybeUninit<u8>]>,
     cursor: usize,
@@ -950,11 +990,11 @@ fn new(buffer: &'a DirBuff) -> Self {
     }
 }
 impl<'a> Iterator for DirBuffIter<'a> {
-    type Item = (Cow<'a, [u16]>, bool);
+    type Item = DirBuffEntry<'a>;
     fn next(&mut self) -> Option<Self::Item> {
         let buffer = &self.buffer?[self.cursor..];
 
-        // Get the name and next entry from the buffer.
+        // Get the entry and the offset of the next one from the buffer.
         // SAFETY:
         // - The buffer contains a `FILE_ID_BOTH_DIR_INFO` struct but the last
         //   field (the file name) is unsized. So an offset has to be used to
@@ -962,7 +1002,7 @@ fn next(&mut self) -> Option<Self::Item> {
         // - The OS has guaranteed initialization of the fields of
         //   `FILE_ID_BOTH_DIR_INFO` and the trailing filename (for at least
         //   `FileNameLength` bytes)
-        let (name, is_directory, next_entry) = unsafe {
+        let (entry, next_entry) = unsafe {
             let info = buffer.as_ptr().cast::<c::FILE_ID_BOTH_DIR_INFO>();
             // While this is guaranteed to be aligned in documentation for
             // https://docs.microsoft.com/en-us/windows/win32/api/winbase/ns-winbase-file_id_both_dir_info
@@ -971,14 +1011,27 @@ fn next(&mut self) -> Option<Self::Item> {
             // presumably, this can be blamed on buggy filesystem drivers, but who knows.
             let next_entry = (&raw const (*info).NextEntryOffset).read_unaligned() as usize;
             let length = (&raw const (*info).FileNameLength).read_unaligned() as usize;
-            let attrs = (&raw const (*info).FileAttributes).read_unaligned();
+            let attributes = (&raw const (*info).FileAttributes).read_unaligned();
             let name = from_maybe_unaligned(
                 (&raw const (*info).FileName).cast::<u16>(),
                 length / size_of::<u16>(),
             );
-            let is_directory = (attrs & c::FILE_ATTRIBUTE_DIRECTORY) != 0;
+            let entry = DirBuffEntry {
+                name,
+                attributes,
+                // `EaSize` holds the reparse tag for reparse points.
+                reparse_tag: if attributes & c::FILE_ATTRIBUTE_REPARSE_POINT != 0 {
+                    (&raw const (*info).EaSize).read_unaligned()
+                } else {
+                    0
+                },
+                creation_time: (&raw const (*info).CreationTime).read_unaligned(),
+                last_access_time: (&raw const (*info).LastAccessTime).read_unaligned(),
+                last_write_time: (&raw const (*info).LastWriteTime).read_unaligned(),
+                file_size: (&raw const (*info).EndOfFile).read_unaligned(),
+            };
 
-            (name, is_directory, next_entry)
+            (entry, next_entry)
         };
 
         if next_entry == 0 {
@@ -989,9 +1042,9 @@ fn next(&mut self) -> Option<Self::Item> {
 
         // Skip `.` and `..` pseudo entries.
         const DOT: u16 = b'.' as u16;
-        match &name[..] {
+        match &entry.name[..] {
             [DOT] | [DOT, DOT] => self.next(),
-            _ => Some((name, is_directory)),
+            _ => Some(entry),
         }
     }
 }
@@ -72,7 +72,7 @@ unsafe fn nt_open_file(
 
 /// Open the file `path` in the directory `parent`, requesting the given `access` rights.
 /// `options` will be OR'd with `FILE_OPEN_REPARSE_POINT`.
-fn open_link_no_reparse(
+pub(super) fn open_link_no_reparse(
     parent: &File,
     path: UnicodeStrRef<'_>,
     access: u32,
@@ -177,9 +177,9 @@ pub fn remove_dir_all_iterative(dir: File) -> Result<(), WinError> {
     let mut restart = true;
     'outer: while let Some(dir) = dirlist.pop() {
         let more_data = dir.fill_dir_buff(&mut buffer, restart)?;
-        for (name, is_directory) in buffer.iter() {
-            let name = unicode_str!(&name);
-            if is_directory {
+        for entry in buffer.iter() {
+            let name = unicode_str!(&entry.name);
+            if entry.is_directory() {
                 let Some(subdir) = open_dir(&dir, name)? else { continue };
                 dirlist.push(dir);
                 dirlist.push(subdir);
@@ -0,0 +1,127 @@
+//! The Windows implementation of the directory streams used by
+//! `std::fs::walk_dir` and `std::fs::copy_dir_all`.
+//!
+//! Like `remove_dir_all`, this lists a directory through its handle, and opens
+//! subdirectories and files relative to that handle with `NtOpenFile` without
+//! following reparse points. Replacing a directory that is being walked with a
+//! symlink or a junction therefore can't redirect the walk elsewhere.
+
+use super::remove_dir_all::open_link_no_reparse;
+use super::{DirBuff, DirBuffIter, DirEntry, File, OpenOptions};
+use crate::ffi::OsStr;
+use crate::io;
+use crate::os::windows::ffi::OsStrExt;
+use crate::path::{Path, PathBuf};
+use crate::sync::Arc;
+use crate::sys::c;
+use crate::sys::pal::api::{WinError, unicode_str};
+
+/// A directory that is being walked.
+pub struct WalkDirStream {
+    dir: File,
+    root: Arc<PathBuf>,
+    buffer: DirBuff,
+    /// The offset of the next entry in `buffer`, or `None` if it has to be
+    /// refilled.
+    cursor: Option<usize>,
+    /// Whether the listing has not been read yet.
+    restart: bool,
+    done: bool,
+}
+
+impl WalkDirStream {
+    pub fn open(path: &Path) -> io::Result<WalkDirStream> {
+        let mut opts = OpenOptions::new();
+        opts.access_mode(c::SYNCHRONIZE | c::FILE_LIST_DIRECTORY);
+        // `FILE_FLAG_BACKUP_SEMANTICS` allows opening directories.
+        opts.custom_flags(c::FILE_FLAG_BACKUP_SEMANTICS);
+        let dir = File::open(path, &opts)?;
+        if dir.basic_info()?.FileAttributes & c::FILE_ATTRIBUTE_DIRECTORY == 0 {
+            return Err(io::Error::from_raw_os_error(c::ERROR_DIRECTORY as _));
+        }
+        Ok(Self::from_handle(dir, path))
+    }
+
+    /// Opens the subdirectory `name`, whose full path is `path`. Unless
+    /// `follow_links` is set, this fails if `name` is a symlink or a junction.
+    pub fn open_child(
+        &self,
+        name: &OsStr,
+        path: &Path,
+        follow_links: bool,
+    ) -> io::Result<WalkDirStream> {
+        if follow_links {
+            return Self::open(path);
+        }
+        let dir = self.open_relative(
+            name,
+            c::SYNCHRONIZE | c::FILE_LIST_DIRECTORY,
+            c::FILE_DIRECTORY_FILE,
+        )?;
+        if dir.basic_info()?.FileAttributes & c::FILE_ATTRIBUTE_REPARSE_POINT != 0 {
+            return Err(io::const_error!(
+                io::ErrorKind::NotADirectory,
+                "the directory was replaced with a symlink",
+            ));
+        }
+        Ok(Self::from_handle(dir, path))
+    }
+
+    /// Opens the file `name` for reading without following symlinks.
+    pub fn open_file(&self, name: &OsStr, _path: &Path) -> io::Result<File> {
+        self.open_relative(name, c::FILE_GENERIC_READ, c::FILE_NON_DIRECTORY_FILE)
+    }
+
+    fn open_relative(&self, name: &OsStr, access: u32, options: u32) -> io::Result<File> {
+        let name: Vec<u16> = name.encode_wide().collect();
+        // "_IO_NONALERT" means that a synchronous call won't be interrupted.
+        let options = c::FILE_SYNCHRONOUS_IO_NONALERT | options;
+        match open_link_no_reparse(&self.dir, unicode_str!(&name), access, options) {
+            Ok(Some(file)) => Ok(file),
+            Ok(None) => Err(io::Error::from(io::ErrorKind::NotFound)),
+            Err(err) => Err(io::Error::from_raw_os_error(err.code as i32)),
+        }
+    }
+
+    fn from_handle(dir: File, path: &Path) -> WalkDirStream {
+        WalkDirStream {
+            dir,
+            root: Arc::new(path.to_path_buf()),
+            buffer: DirBuff::new(),
+            cursor: None,
+            restart: true,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for WalkDirStream {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        loop {
+            if let Some(cursor) = self.cursor {
+                let mut entries = DirBuffIter { buffer: Some(self.buffer.as_ref()), cursor };
+                let entry = entries.next();
+                self.cursor = entries.buffer.map(|_| entries.cursor);
+                if let Some(entry) = entry {
+                    return Some(DirEntry::from_dir_buff(&self.root, &entry));
+                }
+            }
+            if self.done {
+                return None;
+            }
+            match self.dir.fill_dir_buff(&mut self.buffer, self.restart) {
+                Ok(true) => {
+                    self.restart = false;
+                    self.cursor = Some(0);
+                }
+                Ok(false) => self.done = true,
+                Err(WinError { code }) => {
+                    self.done = true;
+                    return Some(Err(io::Error::from_raw_os_error(code as i32)));
+                }
+            }
+        }
+    }
+}
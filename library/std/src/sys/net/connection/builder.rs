@@ -0,0 +1,78 @@
+//! A `SocketBuilder` for platforms without BSD sockets, which can't configure
+//! sockets before they are bound or connected. It only supports setting
+//! options to their defaults.
+
+use super::{TcpListener, TcpStream, UdpSocket};
+use crate::io;
+use crate::net::{SocketAddr, ToSocketAddrs};
+use crate::time::Duration;
+
+/// The backlog that most platforms use by default.
+const DEFAULT_BACKLOG: u32 = 128;
+
+#[derive(Clone, Debug)]
+pub struct SocketBuilder {
+    // Whether an option was set to something other than its default.
+    configured: bool,
+}
+
+impl SocketBuilder {
+    pub fn new() -> SocketBuilder {
+        SocketBuilder { configured: false }
+    }
+
+    pub fn reuse_address(&mut self, reuse_address: bool) {
+        self.configured |= reuse_address;
+    }
+
+    pub fn reuse_port(&mut self, reuse_port: bool) {
+        self.configured |= reuse_port;
+    }
+
+    pub fn only_v6(&mut self, only_v6: bool) {
+        self.configured |= only_v6;
+    }
+
+    pub fn backlog(&mut self, backlog: u32) {
+        self.configured |= backlog != DEFAULT_BACKLOG;
+    }
+
+    pub fn recv_buffer_size(&mut self, _size: usize) {
+        self.configured = true;
+    }
+
+    pub fn send_buffer_size(&mut self, _size: usize) {
+        self.configured = true;
+    }
+
+    fn check(&self) -> io::Result<()> {
+        if self.configured {
+            Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "configuring sockets is not supported on this platform",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn listen<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpListener> {
+        self.check()?;
+        TcpListener::bind(addr)
+    }
+
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpStream> {
+        self.check()?;
+        TcpStream::connect(addr)
+    }
+
+    pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.check()?;
+        TcpStream::connect_timeout(addr, timeout)
+    }
+
+    pub fn bind_udp<A: ToSocketAddrs>(&self, addr: A) -> io::Result<UdpSocket> {
+        self.check()?;
+        UdpSocket::bind(addr)
+    }
+}
@@ -9,29 +9,38 @@
         mod socket;
         pub use socket::*;
     }
-    all(target_vendor = "fortanix", target_env = "sgx") => {
-        mod sgx;
-        pub use sgx::*;
-    }
-    all(target_os = "wasi", target_env = "p1") => {
-        mod wasip1;
-        pub use wasip1::*;
-    }
-    target_os = "motor" => {
-        mod motor;
-        pub use motor::*;
-    }
-    target_os = "xous" => {
-        mod xous;
-        pub use xous::*;
-    }
-    target_os = "uefi" => {
-        mod uefi;
-        pub use uefi::*;
-    }
     _ => {
-        mod unsupported;
-        pub use unsupported::*;
+        // Without BSD sockets, sockets can't be configured before they are
+        // bound or connected.
+        mod builder;
+        pub use builder::SocketBuilder;
+
+        cfg_select! {
+            all(target_vendor = "fortanix", target_env = "sgx") => {
+                mod sgx;
+                pub use sgx::*;
+            }
+            all(target_os = "wasi", target_env = "p1") => {
+                mod wasip1;
+                pub use wasip1::*;
+            }
+            target_os = "motor" => {
+                mod motor;
+                pub use motor::*;
+            }
+            target_os = "xous" => {
+                mod xous;
+                pub use xous::*;
+            }
+            target_os = "uefi" => {
+                mod uefi;
+                pub use uefi::*;
+            }
+            _ => {
+                mod unsupported;
+                pub use unsupported::*;
+            }
+        }
     }
 }
 
@@ -534,46 +534,7 @@ pub struct TcpListener {
 
 impl TcpListener {
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<TcpListener> {
-        init();
-        return each_addr(addr, inner);
-
-        fn inner(addr: &SocketAddr) -> io::Result<TcpListener> {
-            let sock = Socket::new(addr_family(addr), c::SOCK_STREAM)?;
-
-            // On platforms with Berkeley-derived sockets, this allows to quickly
-            // rebind a socket, without needing to wait for the OS to clean up the
-            // previous one.
-            //
-            // On Windows, this allows rebinding sockets which are actively in use,
-            // which allows “socket hijacking”, so we explicitly don't set it here.
-            // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
-            #[cfg(not(windows))]
-            unsafe {
-                setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, 1 as c_int)?
-            };
-
-            // Bind our new socket
-            let (addr, len) = socket_addr_to_c(addr);
-            cvt(unsafe { c::bind(sock.as_raw(), addr.as_ptr(), len as _) })?;
-
-            let backlog = if cfg!(target_os = "horizon") {
-                // The 3DS doesn't support a big connection backlog. Sometimes
-                // it allows up to about 37, but other times it doesn't even
-                // accept 32. There may be a global limitation causing this.
-                20
-            } else if cfg!(target_os = "haiku") {
-                // Haiku does not support a queue length > 32
-                // https://github.com/haiku/haiku/blob/979a0bc487864675517fb2fab28f87dc8bf43041/headers/posix/sys/socket.h#L81
-                32
-            } else {
-                // The default for all other platforms
-                128
-            };
-
-            // Start listening
-            cvt(unsafe { c::listen(sock.as_raw(), backlog) })?;
-            Ok(TcpListener { inner: sock })
-        }
+        SocketBuilder::new().listen(addr)
     }
 
     #[inline]
@@ -885,3 +846,197 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         res.field(name, &self.inner.as_raw()).finish()
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Socket builder
+////////////////////////////////////////////////////////////////////////////////
+
+cfg_select! {
+    any(
+        target_os = "linux", target_os = "android",
+        target_os = "dragonfly", target_os = "freebsd",
+        target_os = "openbsd", target_os = "netbsd",
+        target_vendor = "apple",
+    ) => {
+        fn set_reuse_port(sock: &Socket, reuse_port: bool) -> io::Result<()> {
+            unsafe { setsockopt(sock, c::SOL_SOCKET, c::SO_REUSEPORT, reuse_port as c_int) }
+        }
+    }
+    _ => {
+        fn set_reuse_port(_sock: &Socket, reuse_port: bool) -> io::Result<()> {
+            // Without `SO_REUSEPORT`, ports are never shared, as if it were disabled.
+            if !reuse_port {
+                return Ok(());
+            }
+            Err(io::const_error!(
+                ErrorKind::Unsupported,
+                "`SO_REUSEPORT` is not supported on this platform",
+            ))
+        }
+    }
+}
+
+#[cfg(not(target_os = "solid_asp3"))]
+fn set_buffer_size(sock: &Socket, option_name: c_int, size: usize) -> io::Result<()> {
+    let size = c_int::try_from(size).unwrap_or(c_int::MAX);
+    unsafe { setsockopt(sock, c::SOL_SOCKET, option_name, size) }
+}
+
+#[derive(Clone, Debug)]
+pub struct SocketBuilder {
+    reuse_address: Option<bool>,
+    reuse_port: Option<bool>,
+    only_v6: Option<bool>,
+    backlog: Option<u32>,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+}
+
+impl SocketBuilder {
+    pub fn new() -> SocketBuilder {
+        SocketBuilder {
+            reuse_address: None,
+            reuse_port: None,
+            only_v6: None,
+            backlog: None,
+            recv_buffer_size: None,
+            send_buffer_size: None,
+        }
+    }
+
+    pub fn reuse_address(&mut self, reuse_address: bool) {
+        self.reuse_address = Some(reuse_address);
+    }
+
+    pub fn reuse_port(&mut self, reuse_port: bool) {
+        self.reuse_port = Some(reuse_port);
+    }
+
+    pub fn only_v6(&mut self, only_v6: bool) {
+        self.only_v6 = Some(only_v6);
+    }
+
+    pub fn backlog(&mut self, backlog: u32) {
+        self.backlog = Some(backlog);
+    }
+
+    pub fn recv_buffer_size(&mut self, size: usize) {
+        self.recv_buffer_size = Some(size);
+    }
+
+    pub fn send_buffer_size(&mut self, size: usize) {
+        self.send_buffer_size = Some(size);
+    }
+
+    /// Creates a socket for `addr` and applies the options to it.
+    fn socket(&self, addr: &SocketAddr, ty: c_int) -> io::Result<Socket> {
+        let sock = Socket::new(addr_family(addr), ty)?;
+
+        if let Some(reuse_address) = self.reuse_address {
+            unsafe { setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, reuse_address as c_int)? };
+        }
+        if let Some(reuse_port) = self.reuse_port {
+            set_reuse_port(&sock, reuse_port)?;
+        }
+        // `IPV6_V6ONLY` can't be set on IPv4 sockets, and has no meaning for them.
+        if let Some(only_v6) = self.only_v6
+            && addr.is_ipv6()
+        {
+            unsafe { setsockopt(&sock, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)? };
+        }
+
+        #[cfg(not(target_os = "solid_asp3"))]
+        {
+            if let Some(size) = self.recv_buffer_size {
+                set_buffer_size(&sock, c::SO_RCVBUF, size)?;
+            }
+            if let Some(size) = self.send_buffer_size {
+                set_buffer_size(&sock, c::SO_SNDBUF, size)?;
+            }
+        }
+        #[cfg(target_os = "solid_asp3")]
+        if self.recv_buffer_size.is_some() || self.send_buffer_size.is_some() {
+            return Err(io::const_error!(
+                ErrorKind::Unsupported,
+                "setting the buffer sizes of sockets is not supported on this platform",
+            ));
+        }
+
+        Ok(sock)
+    }
+
+    fn bind(sock: &Socket, addr: &SocketAddr) -> io::Result<()> {
+        let (addr, len) = socket_addr_to_c(addr);
+        cvt(unsafe { c::bind(sock.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(())
+    }
+
+    pub fn listen<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpListener> {
+        init();
+        each_addr(addr, |addr| {
+            let sock = self.socket(addr, c::SOCK_STREAM)?;
+
+            // On platforms with Berkeley-derived sockets, this allows to quickly
+            // rebind a socket, without needing to wait for the OS to clean up the
+            // previous one.
+            //
+            // On Windows, this allows rebinding sockets which are actively in use,
+            // which allows “socket hijacking”, so we explicitly don't set it here
+            // unless it was requested.
+            // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
+            #[cfg(not(windows))]
+            if self.reuse_address.is_none() {
+                unsafe { setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, 1 as c_int)? };
+            }
+
+            // Bind our new socket
+            Self::bind(&sock, addr)?;
+
+            let backlog = if let Some(backlog) = self.backlog {
+                c_int::try_from(backlog).unwrap_or(c_int::MAX)
+            } else if cfg!(target_os = "horizon") {
+                // The 3DS doesn't support a big connection backlog. Sometimes
+                // it allows up to about 37, but other times it doesn't even
+                // accept 32. There may be a global limitation causing this.
+                20
+            } else if cfg!(target_os = "haiku") {
+                // Haiku does not support a queue length > 32
+                // https://github.com/haiku/haiku/blob/979a0bc487864675517fb2fab28f87dc8bf43041/headers/posix/sys/socket.h#L81
+                32
+            } else {
+                // The default for all other platforms
+                128
+            };
+
+            // Start listening
+            cvt(unsafe { c::listen(sock.as_raw(), backlog) })?;
+            Ok(TcpListener { inner: sock })
+        })
+    }
+
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpStream> {
+        init();
+        each_addr(addr, |addr| {
+            let sock = self.socket(addr, c::SOCK_STREAM)?;
+            sock.connect(addr)?;
+            Ok(TcpStream { inner: sock })
+        })
+    }
+
+    pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        init();
+
+        let sock = self.socket(addr, c::SOCK_STREAM)?;
+        sock.connect_timeout(addr, timeout)?;
+        Ok(TcpStream { inner: sock })
+    }
+
+    pub fn bind_udp<A: ToSocketAddrs>(&self, addr: A) -> io::Result<UdpSocket> {
+        init();
+        each_addr(addr, |addr| {
+            let sock = self.socket(addr, c::SOCK_DGRAM)?;
+            Self::bind(&sock, addr)?;
+            Ok(UdpSocket { inner: sock })
+        })
+    }
+}
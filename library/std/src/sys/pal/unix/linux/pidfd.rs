@@ -1,8 +1,9 @@
-use crate::io;
 use crate::os::fd::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use crate::sys::fd::FileDesc;
 use crate::sys::process::ExitStatus;
 use crate::sys::{AsInner, FromInner, IntoInner, cvt};
+use crate::time::{Duration, Instant};
+use crate::{cmp, io};
 
 #[cfg(test)]
 mod tests;
@@ -106,6 +107,40 @@ pub fn wait(&self) -> io::Result<ExitStatus> {
     pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
         self.waitid(libc::WEXITED | libc::WNOHANG)
     }
+
+    pub fn wait_timeout(&self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        let start = Instant::now();
+        loop {
+            if let Some(exit_status) = self.try_wait()? {
+                return Ok(Some(exit_status));
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Ok(None);
+            }
+
+            // A pidfd becomes readable once the process it refers to has terminated.
+            // Round the remaining time up to whole milliseconds so that we never wake
+            // up before the deadline and report a timeout for a child that was about
+            // to exit.
+            let remaining = timeout - elapsed;
+            let millis = remaining
+                .as_secs()
+                .saturating_mul(1_000)
+                .saturating_add(remaining.subsec_nanos().div_ceil(1_000_000) as u64);
+            let millis = cmp::min(millis, libc::c_int::MAX as u64) as libc::c_int;
+
+            let mut pollfd =
+                libc::pollfd { fd: self.0.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            if unsafe { libc::poll(&mut pollfd, 1, millis) } == -1 {
+                let err = io::Error::last_os_error();
+                if !err.is_interrupted() {
+                    return Err(err);
+                }
+            }
+        }
+    }
 }
 
 impl AsInner<FileDesc> for PidFd {
@@ -5,6 +5,7 @@
 use crate::os::unix::process::{CommandExt as _, ExitStatusExt};
 use crate::process::Command;
 use crate::sys::AsInner;
+use crate::time::Duration;
 
 #[test]
 fn test_command_pidfd() {
@@ -29,8 +30,12 @@ fn test_command_pidfd() {
 
     let mut child = Command::new("sleep").arg("1000").create_pidfd(true).spawn().unwrap();
     assert_matches!(child.try_wait(), Ok(None));
+    assert_matches!(child.wait_timeout(Duration::from_millis(50)), Ok(None));
     child.kill().expect("failed to kill child");
-    let status = child.wait().expect("error waiting on pidfd");
+    let status = child
+        .wait_timeout(Duration::from_secs(60))
+        .expect("error waiting on pidfd")
+        .expect("child did not exit after being killed");
     assert_eq!(status.signal(), Some(libc::SIGKILL));
 
     let _ = Command::new("echo")
@@ -86,6 +91,7 @@ fn test_pidfd() {
     let fd = child.into_pidfd().unwrap();
 
     assert_matches!(fd.try_wait(), Ok(None));
+    assert_matches!(fd.wait_timeout(Duration::from_millis(50)), Ok(None));
     fd.kill().expect("kill failed");
     fd.kill().expect("sending kill twice failed");
     let status = fd.wait().expect("1st wait failed");
@@ -8,6 +8,17 @@
 pub mod futex;
 #[cfg(target_os = "linux")]
 pub mod linux;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+    target_os = "solaris",
+    target_vendor = "apple",
+))]
+pub mod signal;
 pub mod stack_overflow;
 pub mod sync;
 pub mod thread_parking;
@@ -0,0 +1,280 @@
+//! Delivery of signals to registered file descriptors.
+//!
+//! Every [`Signals`] owns one slot in a fixed-size table, holding the write
+//! end of a pipe and the set of signals it is interested in. While at least one
+//! slot wants a signal, [`handler`] is installed for it and forwards every
+//! delivery to the matching pipes by writing the signal number as a single byte.
+//!
+//! The handler only uses atomics, `write` and `close`, which keeps it
+//! async-signal-safe. To close the write end of a pipe without racing a handler
+//! that may still be using it, slots are reference counted: the `Signals` holds
+//! one reference, and every handler holds one while it uses the slot. Whoever
+//! drops the last reference closes the pipe and frees the slot, so dropping a
+//! `Signals` never has to wait for handlers running on other threads.
+
+use crate::os::fd::{AsFd, AsRawFd, BorrowedFd, IntoRawFd, RawFd};
+use crate::sync::Mutex;
+use crate::sync::atomic::Ordering::SeqCst;
+use crate::sync::atomic::{Atomic, AtomicI32, AtomicU32, AtomicUsize};
+use crate::sys::fd::FileDesc;
+use crate::sys::pal::{cvt, cvt_r};
+use crate::{fmt, io, mem, ptr};
+
+/// The number of signals that fit in the mask of a slot.
+const MASK_BITS: libc::c_int = 128;
+
+/// The maximum number of [`Signals`] that can exist at the same time.
+const SLOTS: usize = 32;
+
+struct Slot {
+    /// The number of references to the slot, see the module documentation. A
+    /// handler may only take a reference while another one is held.
+    refs: Atomic<usize>,
+    /// The write end of the pipe, or -1 once it has been closed. The slot is
+    /// free when this is -1 and there are no references left.
+    fd: Atomic<RawFd>,
+    /// Bit `n` is set if signal `n + 1` should be forwarded to `fd`.
+    mask: [Atomic<u32>; MASK_BITS as usize / 32],
+}
+
+impl Slot {
+    const fn new() -> Slot {
+        Slot {
+            refs: AtomicUsize::new(0),
+            fd: AtomicI32::new(-1),
+            mask: [const { AtomicU32::new(0) }; MASK_BITS as usize / 32],
+        }
+    }
+
+    fn is_free(&self) -> bool {
+        self.refs.load(SeqCst) == 0 && self.fd.load(SeqCst) < 0
+    }
+
+    fn wants(&self, signal: libc::c_int) -> bool {
+        let bit = (signal - 1) as usize;
+        self.mask[bit / 32].load(SeqCst) & (1 << (bit % 32)) != 0
+    }
+
+    /// Takes a reference to the slot if it is in use.
+    fn acquire(&self) -> bool {
+        let mut refs = self.refs.load(SeqCst);
+        while refs != 0 {
+            match self.refs.compare_exchange_weak(refs, refs + 1, SeqCst, SeqCst) {
+                Ok(_) => return true,
+                Err(current) => refs = current,
+            }
+        }
+        false
+    }
+
+    /// Drops a reference to the slot, closing the pipe if it was the last one.
+    fn release(&self) {
+        if self.refs.fetch_sub(1, SeqCst) == 1 {
+            let fd = self.fd.swap(-1, SeqCst);
+            unsafe { libc::close(fd) };
+        }
+    }
+}
+
+static TABLE: [Slot; SLOTS] = [const { Slot::new() }; SLOTS];
+
+/// The actions that were installed before ours, for each signal we took over.
+/// Also serializes all changes to [`TABLE`].
+static PREVIOUS: Mutex<Vec<(libc::c_int, libc::sigaction)>> = Mutex::new(Vec::new());
+
+extern "C" fn handler(signal: libc::c_int) {
+    // Writing to or closing the pipe may clobber `errno` of the interrupted code.
+    let errno = crate::sys::io::errno();
+
+    let byte = signal as u8;
+    for slot in &TABLE {
+        if !slot.acquire() {
+            continue;
+        }
+        if slot.wants(signal) {
+            // The pipe is non-blocking: if it is full, a byte for this signal
+            // is most likely already pending, so it can be dropped.
+            unsafe { libc::write(slot.fd.load(SeqCst), (&raw const byte).cast(), 1) };
+        }
+        slot.release();
+    }
+
+    crate::sys::io::set_errno(errno);
+}
+
+/// The highest signal number that can be registered.
+fn max_signal() -> libc::c_int {
+    let max = cfg_select! {
+        any(target_os = "linux", target_os = "android") => libc::SIGRTMAX(),
+        _ => libc::NSIG - 1,
+    };
+    max.min(MASK_BITS)
+}
+
+/// Signals that std relies on itself, that the C library reserves, or that
+/// can't be caught at all.
+fn is_forbidden(signal: libc::c_int) -> bool {
+    // The C library uses the real-time signals below `SIGRTMIN` internally,
+    // e.g. glibc uses 32 and 33 for thread cancellation and `setxid`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if (32..libc::SIGRTMIN()).contains(&signal) {
+        return true;
+    }
+    matches!(
+        signal,
+        // Used by the stack overflow handler, and synchronous faults can't be
+        // handled by writing to a pipe anyway.
+        libc::SIGSEGV | libc::SIGBUS | libc::SIGILL | libc::SIGFPE
+            // Can't be caught or ignored.
+            | libc::SIGKILL | libc::SIGSTOP
+    )
+}
+
+/// Installs [`handler`] for `signal`, and returns the previous action.
+unsafe fn install(signal: libc::c_int) -> io::Result<libc::sigaction> {
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        // Run on the alternate stack set up by the stack overflow handler when
+        // there is one, and don't make the interrupted code deal with `EINTR`
+        // just because a signal was forwarded.
+        action.sa_flags = libc::SA_ONSTACK | libc::SA_RESTART;
+        cvt(libc::sigemptyset(&mut action.sa_mask))?;
+        let mut old: libc::sigaction = mem::zeroed();
+        cvt(libc::sigaction(signal, &action, &mut old))?;
+        Ok(old)
+    }
+}
+
+pub struct Signals {
+    slot: usize,
+    read: FileDesc,
+    /// The write end of the pipe, owned by the slot.
+    write: RawFd,
+}
+
+impl Signals {
+    pub fn new(signals: &[libc::c_int]) -> io::Result<Signals> {
+        let mut mask = [0u32; MASK_BITS as usize / 32];
+        for &signal in signals {
+            if !(1..=max_signal()).contains(&signal) || is_forbidden(signal) {
+                return Err(io::const_error!(
+                    io::ErrorKind::InvalidInput,
+                    "signal cannot be listened for",
+                ));
+            }
+            let bit = (signal - 1) as usize;
+            mask[bit / 32] |= 1 << (bit % 32);
+        }
+
+        let (read, write) = crate::sys::pipe::pipe()?;
+        read.set_nonblocking(true)?;
+        write.set_nonblocking(true)?;
+
+        let mut previous = PREVIOUS.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(slot) = TABLE.iter().position(Slot::is_free) else {
+            return Err(io::const_error!(
+                io::ErrorKind::QuotaExceeded,
+                "too many signal listeners",
+            ));
+        };
+
+        // Install the handler for every signal that doesn't have it yet, before
+        // making the slot visible to it. If that fails, give the signals taken
+        // over so far back, as if this was never called.
+        let taken_over = previous.len();
+        for &signal in signals {
+            if previous.iter().any(|&(s, _)| s == signal) {
+                continue;
+            }
+            match unsafe { install(signal) } {
+                Ok(old) => previous.push((signal, old)),
+                Err(err) => {
+                    for (signal, old) in previous.drain(taken_over..) {
+                        unsafe { libc::sigaction(signal, &old, ptr::null_mut()) };
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        let entry = &TABLE[slot];
+        for (word, bits) in entry.mask.iter().zip(mask) {
+            word.store(bits, SeqCst);
+        }
+        let write = write.into_raw_fd();
+        entry.fd.store(write, SeqCst);
+        entry.refs.store(1, SeqCst);
+        drop(previous);
+
+        Ok(Signals { slot, read, write })
+    }
+
+    pub fn try_recv(&self) -> io::Result<Option<libc::c_int>> {
+        let mut byte = 0u8;
+        match self.read.read(crate::slice::from_mut(&mut byte)) {
+            Ok(0) => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            Ok(_) => Ok(Some(byte as libc::c_int)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn recv(&self) -> io::Result<libc::c_int> {
+        loop {
+            if let Some(signal) = self.try_recv()? {
+                return Ok(signal);
+            }
+            let mut pollfd =
+                libc::pollfd { fd: self.read.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            cvt_r(|| unsafe { libc::poll(&mut pollfd, 1, -1) })?;
+        }
+    }
+}
+
+impl Drop for Signals {
+    fn drop(&mut self) {
+        let mut previous = PREVIOUS.lock().unwrap_or_else(|e| e.into_inner());
+
+        let entry = &TABLE[self.slot];
+        for word in &entry.mask {
+            word.store(0, SeqCst);
+        }
+
+        // Give signals nobody listens for anymore back to whoever had them before.
+        previous.retain(|(signal, old)| {
+            if TABLE.iter().any(|slot| slot.refs.load(SeqCst) != 0 && slot.wants(*signal)) {
+                return true;
+            }
+            unsafe { libc::sigaction(*signal, old, ptr::null_mut()) };
+            false
+        });
+
+        // A handler that is still using the slot closes the write end of the
+        // pipe when it is done, otherwise this does.
+        entry.release();
+        drop(previous);
+    }
+}
+
+impl AsFd for Signals {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.read.as_fd()
+    }
+}
+
+impl AsRawFd for Signals {
+    fn as_raw_fd(&self) -> RawFd {
+        self.read.as_raw_fd()
+    }
+}
+
+impl fmt::Debug for Signals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signals")
+            .field("read", &self.read.as_raw_fd())
+            .field("write", &self.write)
+            .finish()
+    }
+}
@@ -86,3 +86,35 @@ pub fn output(cmd: &mut Command) -> crate::io::Result<(ExitStatus, Vec<u8>, Vec<
     target_os = "motor"
 )))]
 pub use imp::output;
+
+/// Waits for a child to exit by repeatedly calling `try_wait` until it reports
+/// an exit status or `timeout` has elapsed.
+///
+/// This is the fallback for platforms which have no way to block on the exit of
+/// a child process with a deadline. The polling interval starts small so that
+/// short-lived children are noticed quickly, and backs off to bound the number
+/// of wakeups for long-running ones.
+// Unused on platforms which either have a native timed wait or no processes at all.
+#[allow(dead_code)]
+fn wait_timeout_by_polling<S>(
+    timeout: crate::time::Duration,
+    mut try_wait: impl FnMut() -> crate::io::Result<Option<S>>,
+) -> crate::io::Result<Option<S>> {
+    use crate::time::{Duration, Instant};
+
+    const MAX_INTERVAL: Duration = Duration::from_millis(50);
+
+    let start = Instant::now();
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status));
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Ok(None);
+        }
+        crate::thread::sleep(interval.min(timeout - elapsed));
+        interval = (interval * 2).min(MAX_INTERVAL);
+    }
+}
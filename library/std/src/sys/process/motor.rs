@@ -9,6 +9,7 @@
 use crate::process::StdioPipes;
 use crate::sys::fs::File;
 use crate::sys::{AsInner, FromInner, map_motor_error};
+use crate::time::Duration;
 use crate::{fmt, io};
 
 pub enum Stdio {
@@ -282,6 +283,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        super::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
+
     #[allow(unused)]
     pub fn handle(&self) -> u64 {
         self.handle
@@ -17,6 +17,7 @@
 use crate::sys::cvt;
 #[cfg(target_os = "linux")]
 use crate::sys::pal::linux::pidfd::PidFd;
+use crate::time::Duration;
 use crate::{fmt, mem, sys};
 
 cfg_select! {
@@ -1040,6 +1041,21 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(pid_fd) = self.pidfd.as_ref() {
+            let status = pid_fd.wait_timeout(timeout)?;
+            if let Some(status) = status {
+                self.status = Some(status)
+            }
+            return Ok(status);
+        }
+        crate::sys::process::wait_timeout_by_polling(timeout, || self.try_wait())
+    }
 }
 
 /// Unix exit statuses
@@ -3,6 +3,7 @@
 mod once;
 mod once_box;
 mod rwlock;
+mod semaphore;
 mod thread_parking;
 
 pub use condvar::Condvar;
@@ -11,4 +12,5 @@
 #[allow(unused)] // Only used on some platforms.
 use once_box::OnceBox;
 pub use rwlock::RwLock;
+pub use semaphore::{MAX_PERMITS as SEMAPHORE_MAX_PERMITS, Semaphore};
 pub use thread_parking::Parker;
@@ -0,0 +1,135 @@
+use crate::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use crate::sys::futex::{Futex, Primitive, futex_wait, futex_wake_all};
+use crate::time::{Duration, Instant};
+
+pub struct Semaphore {
+    // The state consists of a 31-bit permit counter and a 'waiters' flag.
+    // Bits 0..31: The number of permits that are currently available.
+    // Bit 31: Threads are waiting on this futex for permits to be released.
+    futex: Futex,
+}
+
+const MASK: Primitive = (1 << 31) - 1;
+const WAITERS: Primitive = 1 << 31;
+
+pub const MAX_PERMITS: usize = MASK as usize;
+
+#[inline]
+fn permits(state: Primitive) -> usize {
+    (state & MASK) as usize
+}
+
+impl Semaphore {
+    #[inline]
+    pub const fn new(permits: usize) -> Self {
+        assert!(permits <= MAX_PERMITS, "too many permits for a semaphore");
+        Self { futex: Futex::new(permits as Primitive) }
+    }
+
+    #[inline]
+    pub fn available(&self) -> usize {
+        permits(self.futex.load(Relaxed))
+    }
+
+    #[inline]
+    pub fn try_acquire(&self, n: usize) -> bool {
+        let mut state = self.futex.load(Relaxed);
+        loop {
+            if permits(state) < n {
+                return false;
+            }
+            // The waiters flag is left untouched: other threads might still be
+            // waiting for more permits than are left after this.
+            match self.futex.compare_exchange_weak(state, state - n as Primitive, Acquire, Relaxed)
+            {
+                Ok(_) => return true,
+                Err(s) => state = s,
+            }
+        }
+    }
+
+    /// Takes `n` permits, blocking until they are available or until `timeout`
+    /// has elapsed. Returns `false` on timeout.
+    #[inline]
+    pub fn acquire(&self, n: usize, timeout: Option<Duration>) -> bool {
+        self.try_acquire(n) || self.acquire_contended(n, timeout)
+    }
+
+    #[cold]
+    fn acquire_contended(&self, n: usize, timeout: Option<Duration>) -> bool {
+        // Waiting for more permits than the semaphore can ever hold would never finish.
+        assert!(n <= MAX_PERMITS, "cannot acquire more permits than a semaphore can hold");
+
+        // A timeout too large to be represented is treated as no timeout at all.
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        let mut state = self.futex.load(Relaxed);
+
+        loop {
+            if permits(state) >= n {
+                match self.futex.compare_exchange_weak(
+                    state,
+                    state - n as Primitive,
+                    Acquire,
+                    Relaxed,
+                ) {
+                    Ok(_) => return true,
+                    Err(s) => state = s,
+                }
+                continue;
+            }
+
+            // Set the waiters flag so that the next release will wake us up.
+            if state & WAITERS == 0 {
+                if let Err(s) =
+                    self.futex.compare_exchange_weak(state, state | WAITERS, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+                state |= WAITERS;
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => return false,
+                },
+                None => None,
+            };
+
+            // Wait for permits to be released, assuming the state did not change
+            // since we looked at it.
+            futex_wait(&self.futex, state, remaining);
+            state = self.futex.load(Relaxed);
+        }
+    }
+
+    pub fn release(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        // Add the permits and clear the waiters flag in one go. Any thread that
+        // still can't make progress afterwards will set the flag again.
+        let mut state = self.futex.load(Relaxed);
+        loop {
+            let available = permits(state);
+            assert!(n <= MAX_PERMITS - available, "semaphore permit count overflow");
+            match self.futex.compare_exchange_weak(
+                state,
+                (available + n) as Primitive,
+                Release,
+                Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(s) => state = s,
+            }
+        }
+
+        if state & WAITERS != 0 {
+            // Waiters might need different numbers of permits,
+            // so let all of them check whether they can proceed now.
+            futex_wake_all(&self.futex);
+        }
+    }
+}
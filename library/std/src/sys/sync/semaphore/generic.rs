@@ -0,0 +1,96 @@
+use crate::cell::UnsafeCell;
+use crate::sys::sync::{Condvar, Mutex};
+use crate::time::{Duration, Instant};
+
+/// A semaphore for platforms without futex support, built on the
+/// platform's mutex and condition variable.
+pub struct Semaphore {
+    mutex: Mutex,
+    condvar: Condvar,
+    permits: UnsafeCell<usize>,
+}
+
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+// Same limit as the futex-based implementation, so that it doesn't depend on the platform.
+pub const MAX_PERMITS: usize = (u32::MAX >> 1) as usize;
+
+impl Semaphore {
+    #[inline]
+    pub const fn new(permits: usize) -> Self {
+        assert!(permits <= MAX_PERMITS, "too many permits for a semaphore");
+        Self { mutex: Mutex::new(), condvar: Condvar::new(), permits: UnsafeCell::new(permits) }
+    }
+
+    /// Runs `f` on the permit counter with the mutex locked.
+    fn with_permits<R>(&self, f: impl FnOnce(&mut usize) -> R) -> R {
+        self.mutex.lock();
+        // SAFETY: the counter is only ever accessed with the mutex locked.
+        let r = f(unsafe { &mut *self.permits.get() });
+        // SAFETY: we locked the mutex above.
+        unsafe { self.mutex.unlock() };
+        r
+    }
+
+    pub fn available(&self) -> usize {
+        self.with_permits(|permits| *permits)
+    }
+
+    pub fn try_acquire(&self, n: usize) -> bool {
+        self.with_permits(|permits| {
+            let acquired = *permits >= n;
+            if acquired {
+                *permits -= n;
+            }
+            acquired
+        })
+    }
+
+    /// Takes `n` permits, blocking until they are available or until `timeout`
+    /// has elapsed. Returns `false` on timeout.
+    pub fn acquire(&self, n: usize, timeout: Option<Duration>) -> bool {
+        assert!(n <= MAX_PERMITS, "cannot acquire more permits than a semaphore can hold");
+
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        self.mutex.lock();
+        let acquired = loop {
+            // SAFETY: the mutex is locked.
+            let permits = unsafe { &mut *self.permits.get() };
+            if *permits >= n {
+                *permits -= n;
+                break true;
+            }
+
+            match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => unsafe {
+                        self.condvar.wait_timeout(&self.mutex, remaining);
+                    },
+                    _ => break false,
+                },
+                None => unsafe { self.condvar.wait(&self.mutex) },
+            }
+        };
+        // SAFETY: we locked the mutex above, and `wait` re-locks it before returning.
+        unsafe { self.mutex.unlock() };
+        acquired
+    }
+
+    pub fn release(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        // Don't panic with the mutex locked.
+        let ok = self.with_permits(|permits| {
+            let ok = n <= MAX_PERMITS - *permits;
+            if ok {
+                *permits += n;
+            }
+            ok
+        });
+        assert!(ok, "semaphore permit count overflow");
+        self.condvar.notify_all();
+    }
+}
@@ -50,7 +50,7 @@
     }
     any(target_family = "unix", target_os = "wasi") => {
         mod unix;
-        pub use unix::{Thread, available_parallelism, current_os_id, sleep, yield_now, DEFAULT_MIN_STACK_SIZE};
+        pub use unix::{SpawnOptions, Thread, available_parallelism, current_os_id, sleep, yield_now, DEFAULT_MIN_STACK_SIZE};
         #[cfg(not(any(
             target_env = "newlib",
             target_os = "l4re",
@@ -121,6 +121,42 @@
     }
 }
 
+/// Platform-specific attributes for a new thread. Only the pthread-based
+/// implementation supports any so far.
+#[cfg(not(any(target_family = "unix", target_os = "wasi")))]
+#[derive(Debug, Clone, Default)]
+pub struct SpawnOptions {}
+
+/// Spawns a new native thread.
+///
+/// # Safety
+///
+/// See `thread::Builder::spawn_unchecked` for the safety requirements.
+#[cfg(any(target_family = "unix", target_os = "wasi"))]
+#[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+pub unsafe fn spawn(
+    stack: usize,
+    options: &SpawnOptions,
+    init: Box<crate::thread::ThreadInit>,
+) -> crate::io::Result<Thread> {
+    unsafe { Thread::new(stack, options, init) }
+}
+
+/// Spawns a new native thread.
+///
+/// # Safety
+///
+/// See `thread::Builder::spawn_unchecked` for the safety requirements.
+#[cfg(not(any(target_family = "unix", target_os = "wasi")))]
+#[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
+pub unsafe fn spawn(
+    stack: usize,
+    _options: &SpawnOptions,
+    init: Box<crate::thread::ThreadInit>,
+) -> crate::io::Result<Thread> {
+    unsafe { Thread::new(stack, init) }
+}
+
 #[cfg(not(any(
     target_os = "freebsd",
     target_os = "netbsd",
@@ -40,10 +40,133 @@ pub struct Thread {
 unsafe impl Send for Thread {}
 unsafe impl Sync for Thread {}
 
+/// Platform-specific attributes for a new thread, set through the `BuilderExt`
+/// traits in `std::os`.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnOptions {
+    guard_size: Option<usize>,
+    sched_policy: Option<libc::c_int>,
+    sched_priority: Option<libc::c_int>,
+    #[cfg(target_os = "linux")]
+    affinity: Option<Vec<usize>>,
+}
+
+#[cfg_attr(target_os = "wasi", allow(dead_code))]
+impl SpawnOptions {
+    pub fn guard_size(&mut self, size: usize) {
+        self.guard_size = Some(size);
+    }
+
+    pub fn sched_policy(&mut self, policy: libc::c_int) {
+        self.sched_policy = Some(policy);
+    }
+
+    pub fn sched_priority(&mut self, priority: libc::c_int) {
+        self.sched_priority = Some(priority);
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn affinity(&mut self, cpus: Vec<usize>) {
+        self.affinity = Some(cpus);
+    }
+
+    /// Applies the options to `attr`, before the thread is created.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+        target_os = "solaris",
+        target_os = "illumos",
+        target_vendor = "apple",
+    ))]
+    unsafe fn apply(&self, attr: *mut libc::pthread_attr_t) -> io::Result<()> {
+        fn cvt_nz(ret: libc::c_int) -> io::Result<()> {
+            if ret == 0 { Ok(()) } else { Err(io::Error::from_raw_os_error(ret)) }
+        }
+
+        if let Some(size) = self.guard_size {
+            cvt_nz(libc::pthread_attr_setguardsize(attr, size))?;
+        }
+
+        if self.sched_policy.is_some() || self.sched_priority.is_some() {
+            // Without this, the new thread would just inherit the scheduling
+            // attributes of the spawning thread and ignore the ones set below.
+            cvt_nz(libc::pthread_attr_setinheritsched(attr, libc::PTHREAD_EXPLICIT_SCHED))?;
+            if let Some(policy) = self.sched_policy {
+                cvt_nz(libc::pthread_attr_setschedpolicy(attr, policy))?;
+            }
+            if let Some(priority) = self.sched_priority {
+                let mut param: libc::sched_param = mem::zeroed();
+                param.sched_priority = priority;
+                cvt_nz(libc::pthread_attr_setschedparam(attr, &param))?;
+            }
+        }
+
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        if let Some(cpus) = &self.affinity {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            for &cpu in cpus {
+                if cpu >= libc::CPU_SETSIZE as usize {
+                    return Err(io::const_error!(
+                        io::ErrorKind::InvalidInput,
+                        "CPU index out of range for the affinity mask",
+                    ));
+                }
+                libc::CPU_SET(cpu, &mut set);
+            }
+            cvt_nz(libc::pthread_attr_setaffinity_np(
+                attr,
+                mem::size_of::<libc::cpu_set_t>(),
+                &set,
+            ))?;
+        }
+
+        // Only glibc lets us set the affinity before the thread starts.
+        #[cfg(all(target_os = "linux", not(target_env = "gnu")))]
+        if self.affinity.is_some() {
+            return Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "setting the CPU affinity of a new thread is not supported on this target",
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+        target_os = "solaris",
+        target_os = "illumos",
+        target_vendor = "apple",
+    )))]
+    unsafe fn apply(&self, _attr: *mut libc::pthread_attr_t) -> io::Result<()> {
+        if self.guard_size.is_some() || self.sched_policy.is_some() || self.sched_priority.is_some()
+        {
+            return Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "thread spawn options are not supported on this target",
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl Thread {
     // unsafe: see thread::Builder::spawn_unchecked for safety requirements
     #[cfg_attr(miri, track_caller)] // even without panics, this helps for Miri backtraces
-    pub unsafe fn new(stack: usize, init: Box<ThreadInit>) -> io::Result<Thread> {
+    pub unsafe fn new(
+        stack: usize,
+        options: &SpawnOptions,
+        init: Box<ThreadInit>,
+    ) -> io::Result<Thread> {
         let data = init;
         let mut attr: mem::MaybeUninit<libc::pthread_attr_t> = mem::MaybeUninit::uninit();
         assert_eq!(libc::pthread_attr_init(attr.as_mut_ptr()), 0);
@@ -93,6 +216,8 @@ pub unsafe fn new(stack: usize, init: Box<ThreadInit>) -> io::Result<Thread> {
             };
         }
 
+        options.apply(attr.as_mut_ptr())?;
+
         let data = Box::into_raw(data);
         let mut native: libc::pthread_t = mem::zeroed();
         let ret = libc::pthread_create(&mut native, attr.as_ptr(), thread_start, data as *mut _);
@@ -1,6 +1,7 @@
 use super::join_handle::JoinHandle;
 use super::lifecycle::spawn_unchecked;
 use crate::io;
+use crate::sys::{AsInnerMut, thread as imp};
 
 /// Thread factory, which can be used in order to configure the properties of
 /// a new thread.
@@ -53,6 +54,8 @@ pub struct Builder {
     pub(super) stack_size: Option<usize>,
     /// Skip running and inheriting the thread spawn hooks
     pub(super) no_hooks: bool,
+    /// Platform-specific attributes, set through the `BuilderExt` traits in `std::os`
+    pub(super) options: imp::SpawnOptions,
 }
 
 impl Builder {
@@ -76,7 +79,12 @@ impl Builder {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Builder {
-        Builder { name: None, stack_size: None, no_hooks: false }
+        Builder {
+            name: None,
+            stack_size: None,
+            no_hooks: false,
+            options: imp::SpawnOptions::default(),
+        }
     }
 
     /// Names the thread-to-be. Currently the name is used for identification
@@ -257,7 +265,16 @@ pub unsafe fn spawn_unchecked<F, T>(self, f: F) -> io::Result<JoinHandle<T>>
         F: Send,
         T: Send,
     {
-        let Builder { name, stack_size, no_hooks } = self;
-        Ok(JoinHandle(unsafe { spawn_unchecked(name, stack_size, no_hooks, None, f) }?))
+        let Builder { name, stack_size, no_hooks, options } = self;
+        Ok(JoinHandle(unsafe { spawn_unchecked(name, stack_size, no_hooks, options, None, f) }?))
+    }
+}
+
+impl crate::sealed::Sealed for Builder {}
+
+impl AsInnerMut<imp::SpawnOptions> for Builder {
+    #[inline]
+    fn as_inner_mut(&mut self) -> &mut imp::SpawnOptions {
+        &mut self.options
     }
 }
@@ -18,6 +18,7 @@ pub(super) unsafe fn spawn_unchecked<'scope, F, T>(
     name: Option<String>,
     stack_size: Option<usize>,
     no_hooks: bool,
+    options: imp::SpawnOptions,
     scope_data: Option<Arc<ScopeData>>,
     f: F,
 ) -> io::Result<JoinInner<'scope, T>>
@@ -123,7 +124,7 @@ fn drop(&mut self) {
     Ok(JoinInner {
         // SAFETY:
         //
-        // `imp::Thread::new` takes a closure with a `'static` lifetime, since it's passed
+        // `imp::spawn` takes a closure with a `'static` lifetime, since it's passed
         // through FFI or otherwise used with low-level threading primitives that have no
         // notion of or way to enforce lifetimes.
         //
@@ -134,7 +135,7 @@ fn drop(&mut self) {
         // Similarly, the `sys` implementation must guarantee that no references to the closure
         // exist after the thread has terminated, which is signaled by `Thread::join`
         // returning.
-        native: unsafe { imp::Thread::new(stack_size, init)? },
+        native: unsafe { imp::spawn(stack_size, &options, init)? },
         thread,
         packet: my_packet,
     })
@@ -425,3 +425,38 @@ fn test_minimal_thread_stack() {
     assert_eq!(before, 0);
     assert_eq!(COUNT.load(Ordering::Relaxed), 1);
 }
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[test]
+fn test_spawn_options() {
+    use crate::io::ErrorKind;
+    use crate::os::linux::thread::BuilderExt as _;
+    use crate::os::unix::thread::BuilderExt as _;
+
+    // Pin the thread to a CPU that the current thread is allowed to run on, CPU 0 may not be
+    // part of the cpuset of the test process.
+    let cpu = unsafe {
+        let mut set: libc::cpu_set_t = crate::mem::zeroed();
+        assert_eq!(libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &mut set), 0);
+        (0..libc::CPU_SETSIZE as usize).find(|&cpu| libc::CPU_ISSET(cpu, &set)).unwrap()
+    };
+
+    let guard_size = 16 * 4096;
+    let result = Builder::new().guard_size(guard_size).affinity([cpu]).spawn(move || unsafe {
+        let mut attr: libc::pthread_attr_t = crate::mem::zeroed();
+        assert_eq!(libc::pthread_getattr_np(libc::pthread_self(), &mut attr), 0);
+        let mut size = 0;
+        assert_eq!(libc::pthread_attr_getguardsize(&attr, &mut size), 0);
+        assert_eq!(libc::pthread_attr_destroy(&mut attr), 0);
+        assert!(size >= guard_size);
+
+        let mut set: libc::cpu_set_t = crate::mem::zeroed();
+        assert_eq!(libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &mut set), 0);
+        assert_eq!(libc::CPU_COUNT(&set), 1);
+        assert!(libc::CPU_ISSET(cpu, &set));
+    });
+    result.unwrap().join().unwrap();
+
+    let err = Builder::new().affinity([usize::MAX]).spawn(|| {}).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
@@ -0,0 +1,460 @@
+//! Conversion between [`SystemTime`] and UTC calendar dates and times.
+//!
+//! The conversions use the proleptic Gregorian calendar and ignore leap
+//! seconds, like the POSIX `time_t` that [`SystemTime`] is based on.
+
+use super::{Duration, SystemTime, UNIX_EPOCH};
+use crate::error::Error;
+use crate::fmt;
+use crate::str::FromStr;
+
+const SECS_PER_DAY: i64 = 86_400;
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+const MIN_YEAR: i64 = 0;
+const MAX_YEAR: i64 = 9999;
+
+/// A date and time in UTC, as a year, month, day, hour, minute, second and
+/// nanosecond.
+///
+/// A `UtcDateTime` can be converted to and from a [`SystemTime`], and
+/// formatted and parsed as an [RFC 3339] timestamp like
+/// `2024-02-29T13:45:07.25Z`.
+///
+/// Dates use the proleptic Gregorian calendar, and only the years 0 to 9999
+/// are supported, which are the ones an RFC 3339 timestamp can represent.
+/// Leap seconds are not supported, since a [`SystemTime`] can't represent
+/// them either.
+///
+/// `UtcDateTime`s are ordered chronologically.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+///
+/// # Examples
+///
+/// ```
+/// #![feature(time_utc_date_time)]
+/// use std::time::{Duration, UNIX_EPOCH, UtcDateTime};
+///
+/// let time = UNIX_EPOCH + Duration::from_secs(1_709_214_307);
+/// let date_time = UtcDateTime::from_system_time(time).unwrap();
+/// assert_eq!((date_time.year(), date_time.month(), date_time.day()), (2024, 2, 29));
+/// assert_eq!(date_time.to_string(), "2024-02-29T13:45:07Z");
+///
+/// let parsed: UtcDateTime = "2024-02-29T14:45:07+01:00".parse().unwrap();
+/// assert_eq!(parsed, date_time);
+/// assert_eq!(parsed.to_system_time(), Some(time));
+/// ```
+#[unstable(feature = "time_utc_date_time", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct UtcDateTime {
+    // The field order makes the derived `Ord` chronological.
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+impl UtcDateTime {
+    /// Creates a `UtcDateTime` from a date and a time of day.
+    ///
+    /// Returns `None` if the year is greater than 9999, if the month, day,
+    /// hour, minute or second is out of range, or if the day doesn't exist in
+    /// the given month.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_utc_date_time)]
+    /// use std::time::UtcDateTime;
+    ///
+    /// assert!(UtcDateTime::new(2024, 2, 29, 12, 0, 0).is_some());
+    /// assert!(UtcDateTime::new(2023, 2, 29, 12, 0, 0).is_none());
+    /// assert!(UtcDateTime::new(2024, 1, 1, 24, 0, 0).is_none());
+    /// ```
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Option<UtcDateTime> {
+        if year as i64 > MAX_YEAR
+            || month < 1
+            || month > 12
+            || day < 1
+            || day > days_in_month(year as i64, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+        Some(UtcDateTime { year, month, day, hour, minute, second, nanosecond: 0 })
+    }
+
+    /// Returns this date and time with the nanosecond set to `nanosecond`,
+    /// or `None` if it is not less than 1,000,000,000.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn with_nanosecond(self, nanosecond: u32) -> Option<UtcDateTime> {
+        if nanosecond >= NANOS_PER_SEC {
+            return None;
+        }
+        Some(UtcDateTime { nanosecond, ..self })
+    }
+
+    /// Returns the current date and time in UTC.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system clock is set to a time outside of the years 0 to
+    /// 9999.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub fn now() -> UtcDateTime {
+        UtcDateTime::from_system_time(SystemTime::now())
+            .expect("system time is outside of the supported range of years")
+    }
+
+    /// Converts a [`SystemTime`] to a date and time in UTC.
+    ///
+    /// Returns `None` if `time` is outside of the years 0 to 9999.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Option<UtcDateTime> {
+        let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => (i64::try_from(after.as_secs()).ok()?, after.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                let secs = i64::try_from(before.as_secs()).ok()?;
+                match before.subsec_nanos() {
+                    0 => (-secs, 0),
+                    nanos => (-secs - 1, NANOS_PER_SEC - nanos),
+                }
+            }
+        };
+        UtcDateTime::from_unix_parts(secs, nanos)
+    }
+
+    /// Converts this date and time to a [`SystemTime`].
+    ///
+    /// Returns `None` if the [`SystemTime`] of this platform can't represent
+    /// it. For example, some platforms don't support times before the Unix
+    /// epoch.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let secs = self.unix_secs();
+        if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::new(secs as u64, self.nanosecond))
+        } else if self.nanosecond == 0 {
+            UNIX_EPOCH.checked_sub(Duration::new(secs.unsigned_abs(), 0))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::new(
+                secs.unsigned_abs() - 1,
+                NANOS_PER_SEC - self.nanosecond,
+            ))
+        }
+    }
+
+    /// Returns the year, from 0 to 9999.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, from 1 to 12.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, from 1 to 31.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour, from 0 to 23.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, from 0 to 59.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, from 0 to 59.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns the fraction of the second in nanoseconds, from 0 to
+    /// 999,999,999.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Returns the day of the week, from 0 for Monday to 6 for Sunday.
+    #[unstable(feature = "time_utc_date_time", issue = "none")]
+    #[must_use]
+    pub const fn weekday(&self) -> u8 {
+        // 1970-01-01 was a Thursday.
+        (days_from_civil(self.year as i64, self.month, self.day) + 3).rem_euclid(7) as u8
+    }
+
+    /// Returns the number of seconds since the Unix epoch, ignoring the
+    /// nanoseconds.
+    fn unix_secs(&self) -> i64 {
+        days_from_civil(self.year as i64, self.month, self.day) * SECS_PER_DAY
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+    }
+
+    fn from_unix_parts(secs: i64, nanosecond: u32) -> Option<UtcDateTime> {
+        let days = secs.div_euclid(SECS_PER_DAY);
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+            return None;
+        }
+        Some(UtcDateTime {
+            year: year as u16,
+            month,
+            day,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8,
+            nanosecond,
+        })
+    }
+}
+
+const fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+const fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between 1970-01-01 and the given date.
+///
+/// This is the `days_from_civil` algorithm from Howard Hinnant's "chrono-
+/// Compatible Low-Level Date Algorithms", which counts in eras of 400 years
+/// that start on March 1st, so that the leap day is the last day of a year.
+const fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of [`days_from_civil`].
+const fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u8;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month as u8, day)
+}
+
+/// Formats the date and time as an RFC 3339 timestamp in UTC, like
+/// `2024-02-29T13:45:07.25Z`.
+///
+/// The fraction of the second is printed with as many digits as needed, and
+/// omitted if it is zero. The precision of the formatter, like in `{:.3}`,
+/// sets the number of digits instead, up to 9.
+#[unstable(feature = "time_utc_date_time", issue = "none")]
+impl fmt::Display for UtcDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second,
+        )?;
+
+        let digits = match f.precision() {
+            Some(precision) => precision.min(9),
+            None => {
+                let mut digits = 9;
+                let mut nanosecond = self.nanosecond;
+                while digits > 0 && nanosecond % 10 == 0 {
+                    nanosecond /= 10;
+                    digits -= 1;
+                }
+                digits
+            }
+        };
+        if digits > 0 {
+            let fraction = self.nanosecond / 10u32.pow(9 - digits as u32);
+            write!(f, ".{fraction:0digits$}")?;
+        }
+        f.write_str("Z")
+    }
+}
+
+/// Parses an RFC 3339 timestamp, like `2024-02-29T13:45:07.25Z` or
+/// `2024-02-29T14:45:07.25+01:00`.
+///
+/// A timestamp with an offset from UTC is converted to UTC. As permitted by
+/// RFC 3339, the `T` and `Z` may be lowercase, and the `T` may be replaced by
+/// a space. Fractions of a second beyond nanoseconds are truncated.
+#[unstable(feature = "time_utc_date_time", issue = "none")]
+impl FromStr for UtcDateTime {
+    type Err = ParseUtcDateTimeError;
+
+    fn from_str(s: &str) -> Result<UtcDateTime, ParseUtcDateTimeError> {
+        let invalid = ParseUtcDateTimeError(ParseErrorKind::Invalid);
+        let mut parser = Parser { bytes: s.as_bytes() };
+
+        let year = parser.digits(4).ok_or(invalid)?;
+        parser.expect(|b| b == b'-').ok_or(invalid)?;
+        let month = parser.digits(2).ok_or(invalid)?;
+        parser.expect(|b| b == b'-').ok_or(invalid)?;
+        let day = parser.digits(2).ok_or(invalid)?;
+        parser.expect(|b| matches!(b, b'T' | b't' | b' ')).ok_or(invalid)?;
+        let hour = parser.digits(2).ok_or(invalid)?;
+        parser.expect(|b| b == b':').ok_or(invalid)?;
+        let minute = parser.digits(2).ok_or(invalid)?;
+        parser.expect(|b| b == b':').ok_or(invalid)?;
+        let second = parser.digits(2).ok_or(invalid)?;
+
+        let mut nanosecond = 0;
+        if parser.expect(|b| b == b'.').is_some() {
+            let mut digits = 0;
+            while let Some(digit) = parser.expect(|b| b.is_ascii_digit()) {
+                if digits < 9 {
+                    nanosecond = nanosecond * 10 + (digit - b'0') as u32;
+                }
+                digits += 1;
+            }
+            if digits == 0 {
+                return Err(invalid);
+            }
+            for _ in digits..9 {
+                nanosecond *= 10;
+            }
+        }
+
+        let offset = match parser.expect(|_| true).ok_or(invalid)? {
+            b'Z' | b'z' => 0,
+            sign @ (b'+' | b'-') => {
+                let hours = parser.digits(2).ok_or(invalid)?;
+                parser.expect(|b| b == b':').ok_or(invalid)?;
+                let minutes = parser.digits(2).ok_or(invalid)?;
+                if hours > 23 || minutes > 59 {
+                    return Err(invalid);
+                }
+                let offset = hours as i64 * 3600 + minutes as i64 * 60;
+                if sign == b'-' { -offset } else { offset }
+            }
+            _ => return Err(invalid),
+        };
+        if !parser.bytes.is_empty() {
+            return Err(invalid);
+        }
+
+        // Validate the fields as written, before the offset is applied.
+        let valid = |second| {
+            UtcDateTime::new(year, month as u8, day as u8, hour as u8, minute as u8, second)
+        };
+        let local = match valid(second as u8) {
+            Some(local) => local,
+            None if second == 60 && valid(59).is_some() => {
+                return Err(ParseUtcDateTimeError(ParseErrorKind::LeapSecond));
+            }
+            None => return Err(invalid),
+        };
+
+        UtcDateTime::from_unix_parts(local.unix_secs() - offset, nanosecond)
+            .ok_or(ParseUtcDateTimeError(ParseErrorKind::OutOfRange))
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+}
+
+impl Parser<'_> {
+    /// Consumes the next byte if it matches `pred`.
+    fn expect(&mut self, pred: impl FnOnce(u8) -> bool) -> Option<u8> {
+        let (&first, rest) = self.bytes.split_first()?;
+        if !pred(first) {
+            return None;
+        }
+        self.bytes = rest;
+        Some(first)
+    }
+
+    /// Consumes exactly `n` decimal digits and returns their value.
+    fn digits(&mut self, n: usize) -> Option<u16> {
+        let mut value = 0;
+        for _ in 0..n {
+            let digit = self.expect(|b| b.is_ascii_digit())?;
+            value = value * 10 + (digit - b'0') as u16;
+        }
+        Some(value)
+    }
+}
+
+/// An error which can be returned when parsing a [`UtcDateTime`] from an
+/// RFC 3339 timestamp.
+#[unstable(feature = "time_utc_date_time", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ParseUtcDateTimeError(ParseErrorKind);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ParseErrorKind {
+    Invalid,
+    LeapSecond,
+    OutOfRange,
+}
+
+#[unstable(feature = "time_utc_date_time", issue = "none")]
+impl fmt::Display for ParseUtcDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.0 {
+            ParseErrorKind::Invalid => "invalid RFC 3339 timestamp",
+            ParseErrorKind::LeapSecond => "leap seconds are not supported",
+            ParseErrorKind::OutOfRange => "timestamp is outside of the years 0 to 9999 in UTC",
+        })
+    }
+}
+
+#[unstable(feature = "time_utc_date_time", issue = "none")]
+impl Error for ParseUtcDateTimeError {}
@@ -0,0 +1,66 @@
+#![feature(alloc_stats)]
+#![feature(cfg_target_thread_local)]
+
+use std::alloc::{CountingAlloc, GlobalAlloc, Layout, System, thread_alloc_stats};
+use std::thread;
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc::new(System);
+
+#[test]
+fn global_stats() {
+    let before = GLOBAL.stats();
+    let v = vec![0u8; 4096];
+    let during = GLOBAL.stats();
+    drop(v);
+    let after = GLOBAL.stats();
+
+    // Other tests may allocate concurrently, so only check lower bounds.
+    assert!(during.allocations() > before.allocations());
+    assert!(during.peak_bytes_in_use() >= 4096);
+    assert!(after.deallocations() > before.deallocations());
+}
+
+#[test]
+#[cfg_attr(not(target_thread_local), ignore = "requires native thread-local storage")]
+fn thread_stats() {
+    thread::spawn(|| {
+        let before = thread_alloc_stats();
+        let mut v: Vec<u8> = Vec::with_capacity(4096);
+        v.reserve_exact(8192);
+        let during = thread_alloc_stats();
+        drop(v);
+        let after = thread_alloc_stats();
+
+        assert_eq!(during.allocations() - before.allocations(), 1);
+        assert_eq!(during.reallocations() - before.reallocations(), 1);
+        assert_eq!(after.deallocations() - before.deallocations(), 1);
+        // Spawning the thread may have freed memory allocated by the parent,
+        // so the bytes in use may have started out clamped at zero.
+        assert!(during.bytes_in_use() > before.bytes_in_use());
+        assert!(during.peak_bytes_in_use() >= during.bytes_in_use());
+        assert!(after.bytes_in_use() < during.bytes_in_use());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn wraps_other_allocators() {
+    let counting = CountingAlloc::new(System);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    unsafe {
+        let ptr = counting.alloc_zeroed(layout);
+        assert!(!ptr.is_null());
+        let ptr = counting.realloc(ptr, layout, 32);
+        assert!(!ptr.is_null());
+        counting.dealloc(ptr, Layout::from_size_align(32, 8).unwrap());
+    }
+
+    let stats = counting.stats();
+    assert_eq!(stats.allocations(), 1);
+    assert_eq!(stats.reallocations(), 1);
+    assert_eq!(stats.deallocations(), 1);
+    assert_eq!(stats.bytes_in_use(), 0);
+    assert_eq!(stats.peak_bytes_in_use(), 64);
+}
@@ -1,5 +1,6 @@
 #![feature(mapped_lock_guards)]
 #![feature(mpmc_channel)]
+#![feature(mpmc_select)]
 #![feature(oneshot_channel)]
 #![feature(once_cell_try)]
 #![feature(lock_value_accessors)]
@@ -9,6 +10,8 @@
 #![feature(nonpoison_condvar)]
 #![feature(nonpoison_mutex)]
 #![feature(nonpoison_rwlock)]
+#![feature(nonpoison_semaphore)]
+#![feature(semaphore)]
 #![allow(internal_features)]
 #![feature(macro_metavar_expr_concat)] // For concatenating identifiers in macros.
 
@@ -18,6 +21,8 @@
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
 mod mpmc;
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
+mod mpmc_select;
+#[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
 mod mpsc;
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
 mod mpsc_sync;
@@ -32,6 +37,8 @@
 mod reentrant_lock;
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
 mod rwlock;
+#[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
+mod semaphore;
 
 #[path = "../common/mod.rs"]
 mod common;
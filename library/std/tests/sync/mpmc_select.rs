@@ -0,0 +1,245 @@
+use std::sync::mpmc::*;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (s1, r1) = channel::<i32>();
+    let (s2, r2) = channel::<i32>();
+
+    s1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&r1);
+    let oper2 = sel.recv(&r2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&r1), Ok(1));
+
+    s2.send(2).unwrap();
+
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&r2), Ok(2));
+}
+
+#[test]
+fn try_select_empty() {
+    let (_s, r) = channel::<i32>();
+
+    let mut sel = Select::new();
+    assert_eq!(sel.try_select().unwrap_err(), TrySelectError);
+
+    sel.recv(&r);
+    assert_eq!(sel.try_select().unwrap_err(), TrySelectError);
+}
+
+#[test]
+#[should_panic(expected = "no operations have been added to `Select`")]
+fn select_nothing() {
+    let _ = Select::new().select();
+}
+
+#[test]
+fn timeout() {
+    let (_s1, r1) = channel::<i32>();
+    let (s2, r2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&r1);
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(100)).unwrap_err(), SelectTimeoutError);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(100));
+            s2.send(2).unwrap();
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&r1);
+        let oper2 = sel.recv(&r2);
+        let oper = sel.select_timeout(Duration::from_secs(10)).unwrap();
+        assert_ne!(oper.index(), oper1);
+        assert_eq!(oper.index(), oper2);
+        assert_eq!(oper.recv(&r2), Ok(2));
+    });
+}
+
+#[test]
+fn disconnected() {
+    let (s1, r1) = channel::<i32>();
+    let (s2, r2) = channel::<i32>();
+
+    drop(s1);
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&r1);
+    let oper2 = sel.recv(&r2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&r1), Err(RecvError));
+
+    sel.remove(oper1);
+    s2.send(2).unwrap();
+
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&r2), Ok(2));
+}
+
+#[test]
+fn send_and_recv() {
+    let (s1, r1) = sync_channel::<i32>(1);
+    let (s2, r2) = sync_channel::<i32>(1);
+
+    // `s1` is full and `r2` is empty, so neither operation is ready.
+    s1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.send(&s1);
+    let oper2 = sel.recv(&r2);
+    assert!(sel.try_select().is_err());
+
+    assert_eq!(r1.recv(), Ok(1));
+
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.send(&s1, 10), Ok(()));
+
+    // `s1` is full again, so only the receive operation can become ready.
+    s2.send(20).unwrap();
+
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&r2), Ok(20));
+    assert_eq!(r1.recv(), Ok(10));
+}
+
+#[test]
+fn zero_capacity() {
+    let (s1, r1) = sync_channel::<i32>(0);
+    let (s2, r2) = sync_channel::<i32>(0);
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            let mut sel = Select::new();
+            let oper1 = sel.send(&s1);
+            let oper2 = sel.send(&s2);
+            let oper = sel.select();
+            match oper.index() {
+                i if i == oper1 => oper.send(&s1, 1).unwrap(),
+                i if i == oper2 => oper.send(&s2, 2).unwrap(),
+                _ => unreachable!(),
+            }
+        });
+
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(r2.recv(), Ok(2));
+        assert!(r1.try_recv().is_err());
+    });
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(100));
+            s1.send(1).unwrap();
+        });
+
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&r1);
+        let oper2 = sel.recv(&r2);
+        let oper = sel.select();
+        assert_ne!(oper.index(), oper2);
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.recv(&r1), Ok(1));
+    });
+}
+
+#[test]
+fn biased() {
+    let (s1, r1) = channel::<i32>();
+    let (s2, r2) = channel::<i32>();
+
+    for _ in 0..10 {
+        s1.send(1).unwrap();
+        s2.send(2).unwrap();
+
+        let mut sel = Select::new_biased();
+        let oper1 = sel.recv(&r1);
+        sel.recv(&r2);
+        let oper = sel.select();
+        assert_eq!(oper.index(), oper1);
+        assert_eq!(oper.recv(&r1), Ok(1));
+        assert_eq!(r2.recv(), Ok(2));
+    }
+}
+
+#[test]
+#[should_panic(expected = "passed a receiver that wasn't selected")]
+fn wrong_receiver() {
+    let (s1, r1) = channel::<i32>();
+    let (_s2, r2) = channel::<i32>();
+
+    s1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&r1);
+    let oper = sel.select();
+    let _ = oper.recv(&r2);
+}
+
+#[test]
+fn stress() {
+    const COUNT: usize = 10_000;
+
+    let (s1, r1) = sync_channel::<usize>(0);
+    let (s2, r2) = channel::<usize>();
+    let (s3, r3) = sync_channel::<usize>(5);
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..COUNT {
+                let mut sel = Select::new();
+                let oper1 = sel.send(&s1);
+                let oper2 = sel.send(&s3);
+                let oper = sel.select();
+                match oper.index() {
+                    ix if ix == oper1 => oper.send(&s1, i).unwrap(),
+                    ix if ix == oper2 => oper.send(&s3, i).unwrap(),
+                    _ => unreachable!(),
+                }
+            }
+            drop(s2);
+        });
+
+        let mut received = 0;
+        let mut done = false;
+        while !done {
+            let mut sel = Select::new();
+            let oper1 = sel.recv(&r1);
+            let oper2 = sel.recv(&r2);
+            let oper3 = sel.recv(&r3);
+            let oper = sel.select();
+            match oper.index() {
+                ix if ix == oper1 => {
+                    oper.recv(&r1).unwrap();
+                    received += 1;
+                }
+                ix if ix == oper2 => {
+                    // The producer finished and dropped `s2`.
+                    assert!(oper.recv(&r2).is_err());
+                    done = true;
+                }
+                ix if ix == oper3 => {
+                    oper.recv(&r3).unwrap();
+                    received += 1;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        received += r3.try_iter().count();
+        assert_eq!(received, COUNT);
+    });
+}
@@ -0,0 +1,178 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Semaphore, TryLockError};
+use std::thread;
+use std::time::Duration;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Nonpoison & Poison Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::nonpoison_and_poison_unwrap_test;
+
+nonpoison_and_poison_unwrap_test!(
+    name: smoke,
+    test_body: {
+        use locks::Semaphore;
+
+        let s = Semaphore::new(1);
+        drop(maybe_unwrap(s.acquire()));
+        drop(maybe_unwrap(s.acquire()));
+        assert_eq!(s.available_permits(), 1);
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: try_acquire,
+    test_body: {
+        use locks::Semaphore;
+
+        let s = Semaphore::new(2);
+        let a = s.try_acquire().unwrap();
+        let b = s.try_acquire().unwrap();
+        assert!(s.try_acquire().is_err());
+        drop(a);
+        assert_eq!(s.available_permits(), 1);
+        drop(b);
+        assert_eq!(s.available_permits(), 2);
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: acquire_many,
+    test_body: {
+        use locks::Semaphore;
+
+        let s = Semaphore::new(5);
+        let permit = maybe_unwrap(s.acquire_many(3));
+        assert_eq!(permit.num_permits(), 3);
+        assert_eq!(s.available_permits(), 2);
+
+        // Not enough permits left: nothing must be taken.
+        assert!(s.try_acquire_many(3).is_err());
+        assert_eq!(s.available_permits(), 2);
+
+        drop(permit);
+        assert_eq!(s.available_permits(), 5);
+        drop(s.try_acquire_many(5).unwrap());
+        drop(maybe_unwrap(s.acquire_many(0)));
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: timeout,
+    test_body: {
+        use locks::Semaphore;
+
+        let s = Semaphore::new(1);
+        let permit = s.acquire_timeout(Duration::from_millis(1)).unwrap();
+        assert!(s.acquire_timeout(Duration::from_millis(10)).is_err());
+        assert!(s.acquire_many_timeout(2, Duration::ZERO).is_err());
+        drop(permit);
+        assert!(s.acquire_many_timeout(1, Duration::MAX).is_ok());
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: add_and_forget,
+    test_body: {
+        use locks::Semaphore;
+
+        let s = Semaphore::new(0);
+        s.add_permits(3);
+        assert_eq!(s.available_permits(), 3);
+        maybe_unwrap(s.acquire_many(2)).forget();
+        assert_eq!(s.available_permits(), 1);
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: wakes_waiters,
+    test_body: {
+        use locks::Semaphore;
+
+        let s = Arc::new(Semaphore::new(0));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let s = s.clone();
+                thread::spawn(move || maybe_unwrap(s.acquire_many(2)).forget())
+            })
+            .collect();
+
+        s.add_permits(8);
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(s.available_permits(), 0);
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: limits_concurrency,
+    test_body: {
+        use locks::Semaphore;
+
+        const THREADS: usize = 8;
+        const PERMITS: usize = 3;
+
+        let s = Arc::new(Semaphore::new(PERMITS));
+        let active = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let s = s.clone();
+                let active = active.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let _permit = maybe_unwrap(s.acquire());
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        assert!(now <= PERMITS);
+                        thread::yield_now();
+                        active.fetch_sub(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(s.available_permits(), PERMITS);
+    }
+);
+
+#[test]
+#[should_panic = "too many permits"]
+fn too_many_permits() {
+    let _ = Semaphore::new(Semaphore::MAX_PERMITS + 1);
+}
+
+#[test]
+#[should_panic = "permit count overflow"]
+fn add_permits_overflow() {
+    let s = Semaphore::new(Semaphore::MAX_PERMITS);
+    s.add_permits(1);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn poison() {
+    let s = Arc::new(Semaphore::new(1));
+    assert!(!s.is_poisoned());
+
+    let s2 = s.clone();
+    let _ = thread::spawn(move || {
+        let _permit = s2.acquire().unwrap();
+        panic!("test panic to poison semaphore");
+    })
+    .join();
+
+    assert!(s.is_poisoned());
+    // The permit was still released, and can be acquired through the error.
+    assert_eq!(s.available_permits(), 1);
+    match s.try_acquire() {
+        Err(TryLockError::Poisoned(e)) => assert_eq!(e.into_inner().num_permits(), 1),
+        _ => panic!("expected a poisoned semaphore"),
+    }
+
+    s.clear_poison();
+    assert!(!s.is_poisoned());
+    drop(s.acquire().unwrap());
+}
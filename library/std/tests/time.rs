@@ -1,9 +1,10 @@
 #![feature(duration_constants)]
 #![feature(time_systemtime_limits)]
 #![feature(time_saturating_systemtime)]
+#![feature(time_utc_date_time)]
 
 use std::fmt::Debug;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH, UtcDateTime};
 
 macro_rules! assert_almost_eq {
     ($a:expr, $b:expr) => {{
@@ -308,3 +309,94 @@ fn system_time_saturating() {
         Duration::ZERO
     );
 }
+
+#[test]
+fn utc_date_time_from_system_time() {
+    let time = UNIX_EPOCH + Duration::new(951_827_696, 500_000_000);
+    let date_time = UtcDateTime::from_system_time(time).unwrap();
+    assert_eq!(date_time, UtcDateTime::new(2000, 2, 29, 12, 34, 56).unwrap());
+    assert_eq!(date_time.nanosecond(), 500_000_000);
+    assert_eq!(date_time.weekday(), 1);
+    assert_eq!(date_time.to_system_time(), Some(time));
+
+    let epoch = UtcDateTime::from_system_time(UNIX_EPOCH).unwrap();
+    assert_eq!(epoch, UtcDateTime::new(1970, 1, 1, 0, 0, 0).unwrap());
+    assert_eq!(epoch.to_system_time(), Some(UNIX_EPOCH));
+}
+
+#[test]
+fn utc_date_time_before_epoch() {
+    let Some(time) = UNIX_EPOCH.checked_sub(Duration::new(1, 250_000_000)) else {
+        return;
+    };
+    let date_time = UtcDateTime::from_system_time(time).unwrap();
+    assert_eq!(date_time.to_string(), "1969-12-31T23:59:58.75Z");
+    assert_eq!(date_time.to_system_time(), Some(time));
+}
+
+#[test]
+fn utc_date_time_display() {
+    let date_time = UtcDateTime::new(987, 6, 5, 4, 3, 2).unwrap();
+    assert_eq!(date_time.to_string(), "0987-06-05T04:03:02Z");
+    assert_eq!(format!("{date_time:.3}"), "0987-06-05T04:03:02.000Z");
+
+    let date_time = date_time.with_nanosecond(120_000).unwrap();
+    assert_eq!(date_time.to_string(), "0987-06-05T04:03:02.00012Z");
+    assert_eq!(format!("{date_time:.3}"), "0987-06-05T04:03:02.000Z");
+    assert_eq!(format!("{date_time:.12}"), "0987-06-05T04:03:02.000120000Z");
+}
+
+#[test]
+fn utc_date_time_parse() {
+    let parse = |s: &str| s.parse::<UtcDateTime>().map(|date_time| date_time.to_string());
+
+    assert_eq!(parse("2024-02-29T13:45:07Z").unwrap(), "2024-02-29T13:45:07Z");
+    assert_eq!(parse("2024-02-29t13:45:07.5z").unwrap(), "2024-02-29T13:45:07.5Z");
+    assert_eq!(parse("2024-02-29 13:45:07.1234567891Z").unwrap(), "2024-02-29T13:45:07.123456789Z");
+    assert_eq!(parse("2024-03-01T00:30:00+01:00").unwrap(), "2024-02-29T23:30:00Z");
+    assert_eq!(parse("2023-12-31T23:30:00-00:45").unwrap(), "2024-01-01T00:15:00Z");
+
+    for invalid in [
+        "",
+        "2024-02-29",
+        "2024-02-29T13:45:07",
+        "2024-02-29T13:45:07.Z",
+        "2024-02-29T13:45:07+0100",
+        "2024-02-29T13:45:07Z ",
+        "2023-02-29T13:45:07Z",
+        "2024-02-29T24:00:00Z",
+        "2024-02-29T13:45:61Z",
+        "+2024-02-29T13:45:07Z",
+    ] {
+        assert!(parse(invalid).is_err(), "{invalid:?} should be invalid");
+    }
+
+    let leap_second = parse("2016-12-31T23:59:60Z").unwrap_err();
+    assert_eq!(leap_second.to_string(), "leap seconds are not supported");
+    assert!(parse("0000-01-01T00:30:00+01:00").is_err());
+    assert!(parse("9999-12-31T23:30:00-01:00").is_err());
+}
+
+#[test]
+fn utc_date_time_round_trip() {
+    let mut date_time = UtcDateTime::new(1600, 1, 1, 1, 2, 3).unwrap();
+    for _ in 0..1000 {
+        let parsed: UtcDateTime = date_time.to_string().parse().unwrap();
+        assert_eq!(parsed, date_time);
+        if let Some(time) = date_time.to_system_time() {
+            assert_eq!(UtcDateTime::from_system_time(time), Some(date_time));
+        }
+
+        let next = UtcDateTime::new(
+            date_time.year() + 1,
+            date_time.month() % 12 + 1,
+            date_time.day() % 28 + 1,
+            date_time.hour(),
+            date_time.minute(),
+            date_time.second(),
+        )
+        .unwrap();
+        assert!(next > date_time);
+        date_time = next;
+    }
+}
@@ -237,6 +237,7 @@ mod directives {
     pub const AUX_CRATE: &'static str = "aux-crate";
     pub const PROC_MACRO: &'static str = "proc-macro";
     pub const AUX_CODEGEN_BACKEND: &'static str = "aux-codegen-backend";
+    pub const AUX_LINT_PLUGIN: &'static str = "aux-lint-plugin";
     pub const EXEC_ENV: &'static str = "exec-env";
     pub const RUSTC_ENV: &'static str = "rustc-env";
     pub const UNSET_EXEC_ENV: &'static str = "unset-exec-env";
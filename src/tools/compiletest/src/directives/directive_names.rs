@@ -15,6 +15,7 @@
     "aux-build",
     "aux-codegen-backend",
     "aux-crate",
+    "aux-lint-plugin",
     "build-aux-docs",
     "build-fail",
     "build-pass",
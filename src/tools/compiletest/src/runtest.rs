@@ -1331,6 +1331,16 @@ fn build_all_auxiliary(&self, aux_dir: &Utf8Path, rustc: &mut Command) {
                 rustc.arg(format!("-Zcodegen-backend={}", lib_path));
             }
         }
+
+        // Build any `//@ aux-lint-plugin`, and pass the resulting libraries
+        // to `-Zlint-plugin` when compiling the test file.
+        for aux_file in &self.props.aux.lint_plugins {
+            let aux_type = self.build_auxiliary(aux_file, aux_dir, None);
+            if let Some(lib_name) = get_lib_name(aux_file.trim_end_matches(".rs"), aux_type) {
+                let lib_path = aux_dir.join(&lib_name);
+                rustc.arg(format!("-Zlint-plugin={}", lib_path));
+            }
+        }
     }
 
     /// `root_testpaths` refers to the path of the original test. the auxiliary and the test with an
@@ -2,7 +2,8 @@
 
 use rustc_proc_macro::bridge as pm_bridge;
 
-pub use pm_bridge::{DelimSpan, Diagnostic, ExpnGlobals, LitKind};
+pub use pm_bridge::{Cfg, DelimSpan, Diagnostic, ExpnGlobals, LitKind};
+pub use rustc_proc_macro::context::Edition;
 
 pub type TokenTree<S> =
     pm_bridge::TokenTree<crate::token_stream::TokenStream<S>, S, intern::Symbol>;
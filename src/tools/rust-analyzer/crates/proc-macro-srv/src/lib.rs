@@ -12,7 +12,12 @@
 
 #![cfg(feature = "sysroot-abi")]
 #![cfg_attr(feature = "in-rust-tree", feature(rustc_private))]
-#![feature(proc_macro_internals, proc_macro_diagnostic, proc_macro_span)]
+#![feature(
+    proc_macro_internals,
+    proc_macro_diagnostic,
+    proc_macro_span,
+    proc_macro_expansion_context
+)]
 #![allow(
     unreachable_pub,
     internal_features,
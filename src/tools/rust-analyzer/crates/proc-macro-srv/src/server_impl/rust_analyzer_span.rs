@@ -15,7 +15,7 @@
 
 use crate::{
     ProcMacroClientHandle,
-    bridge::{Diagnostic, ExpnGlobals, Literal, TokenTree},
+    bridge::{Cfg, Diagnostic, Edition, ExpnGlobals, Literal, TokenTree},
     server_impl::literal_from_str,
 };
 
@@ -71,6 +71,35 @@ fn emit_diagnostic(&mut self, _: Diagnostic<Self::Span>) {
         // FIXME handle diagnostic
     }
 
+    fn expansion_crate_name(&mut self) -> String {
+        // FIXME: the name of the crate is not passed to the server
+        String::new()
+    }
+
+    fn expansion_edition(&mut self) -> Edition {
+        match self.call_site.anchor.file_id.edition() {
+            span::Edition::Edition2015 => Edition::Edition2015,
+            span::Edition::Edition2018 => Edition::Edition2018,
+            span::Edition::Edition2021 => Edition::Edition2021,
+            span::Edition::Edition2024 => Edition::Edition2024,
+        }
+    }
+
+    fn expansion_cfgs(&mut self) -> Vec<Cfg> {
+        // FIXME: the cfg options of the crate are not passed to the server
+        Vec::new()
+    }
+
+    fn expansion_module_path(&mut self) -> String {
+        // FIXME: the module of the macro call is not passed to the server
+        String::new()
+    }
+
+    fn expansion_item_path(&mut self) -> String {
+        // FIXME: the item of the macro call is not passed to the server
+        String::new()
+    }
+
     fn ts_drop(&mut self, stream: Self::TokenStream) {
         drop(stream);
     }
@@ -10,7 +10,7 @@
 
 use crate::{
     ProcMacroClientHandle,
-    bridge::{Diagnostic, ExpnGlobals, Literal, TokenTree},
+    bridge::{Cfg, Diagnostic, Edition, ExpnGlobals, Literal, TokenTree},
     server_impl::literal_from_str,
 };
 
@@ -74,6 +74,31 @@ fn literal_from_str(&mut self, s: &str) -> Result<Literal<Self::Span>, String> {
 
     fn emit_diagnostic(&mut self, _: Diagnostic<Self::Span>) {}
 
+    fn expansion_crate_name(&mut self) -> String {
+        // FIXME: the name of the crate is not passed to the server
+        String::new()
+    }
+
+    fn expansion_edition(&mut self) -> Edition {
+        // FIXME: span ids don't carry the edition of the macro call
+        Edition::Edition2015
+    }
+
+    fn expansion_cfgs(&mut self) -> Vec<Cfg> {
+        // FIXME: the cfg options of the crate are not passed to the server
+        Vec::new()
+    }
+
+    fn expansion_module_path(&mut self) -> String {
+        // FIXME: the module of the macro call is not passed to the server
+        String::new()
+    }
+
+    fn expansion_item_path(&mut self) -> String {
+        // FIXME: the item of the macro call is not passed to the server
+        String::new()
+    }
+
     fn ts_drop(&mut self, stream: Self::TokenStream) {
         drop(stream);
     }
@@ -0,0 +1,32 @@
+//! Checks that debuginfo is emitted for functions that are polymorphized with `-Zpolymorphize`,
+//! whose generic arguments still contain the parameters that their body does not depend on.
+
+//@ ignore-msvc the types are named differently
+//@ compile-flags: -Cdebuginfo=2 -Copt-level=0 -Zinline-mir=no -Zpolymorphize
+
+#![crate_type = "lib"]
+
+use std::marker::PhantomData;
+
+// CHECK-DAG: !DISubprogram(name: "unused<T>"
+#[inline(never)]
+fn unused<T>() -> u32 {
+    42
+}
+
+// CHECK-DAG: !DISubprogram(name: "phantom<T>"
+// CHECK-DAG: !DICompositeType(tag: DW_TAG_structure_type, name: "PhantomData<T>"
+#[inline(never)]
+fn phantom<T>(marker: PhantomData<T>) -> u32 {
+    let local = marker;
+    let _ = local;
+    7
+}
+
+#[no_mangle]
+pub fn start() -> u32 {
+    unused::<u8>()
+        + unused::<String>()
+        + phantom::<u8>(PhantomData)
+        + phantom::<String>(PhantomData)
+}
@@ -0,0 +1,38 @@
+//! Checks that with `-Zpolymorphize`, instantiations of a function that only differ in generic
+//! parameters its body does not depend on call one shared function.
+
+//@ compile-flags: -Copt-level=0 -Zinline-mir=no -Zpolymorphize
+
+#![crate_type = "lib"]
+
+use std::marker::PhantomData;
+
+#[inline(never)]
+fn unused<T>() -> u32 {
+    42
+}
+
+#[inline(never)]
+fn phantom<T>(_: PhantomData<T>) -> u32 {
+    7
+}
+
+// CHECK-LABEL: @call_unused
+#[no_mangle]
+pub fn call_unused() {
+    // CHECK: call {{.*}}@[[UNUSED:[_a-zA-Z0-9$.]*unused[_a-zA-Z0-9$.]*]]()
+    // CHECK: call {{.*}}@[[UNUSED]]()
+    // CHECK: call {{.*}}@[[UNUSED]]()
+    unused::<u32>();
+    unused::<u64>();
+    unused::<String>();
+}
+
+// CHECK-LABEL: @call_phantom
+#[no_mangle]
+pub fn call_phantom() {
+    // CHECK: call {{.*}}@[[PHANTOM:[_a-zA-Z0-9$.]*phantom[_a-zA-Z0-9$.]*]]()
+    // CHECK: call {{.*}}@[[PHANTOM]]()
+    phantom::<u8>(PhantomData);
+    phantom::<String>(PhantomData);
+}
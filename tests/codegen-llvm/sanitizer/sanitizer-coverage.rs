@@ -0,0 +1,32 @@
+// Verifies that `-Zsanitizer-coverage` instruments functions, except for those that are listed
+// in `-Zsanitizer-coverage-ignorelist`.
+//
+//@ revisions: GUARD COUNTERS CMP
+//@ compile-flags: -Copt-level=0
+//@ compile-flags: -Zsanitizer-coverage-ignorelist={{src-base}}/sanitizer/sanitizer-coverage-ignorelist.txt
+//@[GUARD] compile-flags: -Zsanitizer-coverage=trace-pc-guard
+//@[COUNTERS] compile-flags: -Zsanitizer-coverage=inline-8bit-counters,pc-table
+//@[CMP] compile-flags: -Zsanitizer-coverage=trace-cmp
+
+#![crate_type = "lib"]
+
+// COUNTERS: @__sancov_gen_{{.*}} = private global [{{[0-9]+}} x i8] zeroinitializer, section "{{.*}}__sancov_cntrs"
+// COUNTERS: section "{{.*}}__sancov_pcs"
+
+// CHECK-LABEL: define{{.*}}@compare(
+#[no_mangle]
+pub fn compare(a: u64, b: u64) -> bool {
+    // GUARD: call void @__sanitizer_cov_trace_pc_guard(
+    // COUNTERS: load i8, ptr @__sancov_gen_
+    // CMP: call void @__sanitizer_cov_trace_cmp8(i64
+    a < b
+}
+
+// CHECK-LABEL: define{{.*}}@compare_ignored(
+#[no_mangle]
+pub fn compare_ignored(a: u64, b: u64) -> bool {
+    // CHECK-NOT: __sanitizer_cov
+    // CHECK-NOT: __sancov_gen_
+    // CHECK: ret i1
+    a < b
+}
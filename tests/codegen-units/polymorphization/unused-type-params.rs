@@ -0,0 +1,51 @@
+//@ compile-flags:-Clink-dead-code -Zinline-mir=no -Zpolymorphize
+
+#![crate_type = "lib"]
+
+use std::marker::PhantomData;
+
+// Instantiations of a function whose body does not depend on `T` share one mono item.
+//~ MONO_ITEM fn unused::<T>
+fn unused<T>() -> u32 {
+    42
+}
+
+// Only `U` is used, so there is one mono item per `U`.
+//~ MONO_ITEM fn partially_used::<T, u32>
+//~ MONO_ITEM fn partially_used::<T, u64>
+fn partially_used<T, U: Default>() -> U {
+    U::default()
+}
+
+// `PhantomData<T>` is a ZST whatever `T` is, so it does not make `T` used.
+//~ MONO_ITEM fn phantom::<T>
+fn phantom<T>(_: PhantomData<T>) -> u32 {
+    7
+}
+
+// Moving a value of type `T` depends on its layout.
+//~ MONO_ITEM fn used::<u32>
+//~ MONO_ITEM fn used::<u64>
+fn used<T>(t: T) -> T {
+    t
+}
+
+//~ MONO_ITEM fn start
+#[no_mangle]
+pub fn start(_: isize, _: *const *const u8) -> isize {
+    let _ = unused::<u32>();
+    let _ = unused::<u64>();
+    let _ = unused::<String>();
+
+    let _: u32 = partially_used::<u8, u32>();
+    let _: u32 = partially_used::<u16, u32>();
+    let _: u64 = partially_used::<u8, u64>();
+
+    let _ = phantom::<u8>(PhantomData);
+    let _ = phantom::<String>(PhantomData);
+
+    let _ = used(1u32);
+    let _ = used(1u64);
+
+    0
+}
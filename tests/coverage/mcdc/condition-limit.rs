@@ -0,0 +1,62 @@
+#![feature(coverage_attribute)]
+//@ edition: 2021
+//@ compile-flags: -Zcoverage-options=mcdc
+//@ llvm-cov-flags: --show-branches=count --show-mcdc
+
+// A decision whose test vectors don't fit into the bitmap of a function is
+// not instrumented for MC/DC, and its conditions are instrumented as ordinary
+// branches instead.
+
+fn accept_7_conditions(a: bool, b: bool, c: bool, d: bool, e: bool, f: bool, g: bool) {
+    if a && b && c && d && e && f && g {
+        core::hint::black_box("accepted");
+    }
+}
+
+fn too_many_test_vectors(a: bool, b: bool) {
+    // 31 `(a || b)` conditions joined by `&&` have 2^32 - 1 test vectors.
+    if (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+        && (a || b)
+    {
+        core::hint::black_box("accepted");
+    }
+}
+
+#[coverage(off)]
+fn main() {
+    accept_7_conditions(false, false, false, false, false, false, false);
+    accept_7_conditions(true, true, true, true, true, true, false);
+    accept_7_conditions(true, true, true, true, true, true, true);
+
+    too_many_test_vectors(false, true);
+    too_many_test_vectors(true, false);
+}
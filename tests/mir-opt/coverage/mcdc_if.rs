@@ -0,0 +1,24 @@
+#![feature(coverage_attribute)]
+//@ test-mir-pass: InstrumentCoverage
+//@ compile-flags: -Cinstrument-coverage -Zno-profiler-runtime -Zcoverage-options=mcdc
+// skip-filecheck
+
+// EMIT_MIR mcdc_if.and_or.InstrumentCoverage.diff
+fn and_or(a: bool, b: bool, c: bool) {
+    if (a && b) || c {
+        consume(true);
+    } else {
+        consume(false);
+    }
+}
+
+#[inline(never)]
+#[coverage(off)]
+fn consume(x: bool) {
+    core::hint::black_box(x);
+}
+
+#[coverage(off)]
+fn main() {
+    and_or(true, false, true);
+}
@@ -0,0 +1,9 @@
+#[inline(never)]
+pub fn checksum(data: &[u8]) -> u32 {
+    data.iter().fold(0u32, |sum, &byte| sum.rotate_left(5) ^ u32::from(byte))
+}
+
+fn main() {
+    let data: Vec<u8> = std::env::args().flat_map(|arg| arg.into_bytes()).collect();
+    std::process::exit(checksum(&data) as i32 & 1);
+}
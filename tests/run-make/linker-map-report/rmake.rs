@@ -0,0 +1,45 @@
+// Checks that `-Zlinker-map-report` makes the linker write a map next to the artifact, and that
+// the size report written from it attributes code to the local crate, with both GNU ld and lld.
+
+//@ needs-rust-lld
+//@ only-x86_64-unknown-linux-gnu
+
+use run_make_support::serde_json::{self, Value};
+use run_make_support::{path, rfs, rustc};
+
+fn check_report(lld: bool, expected_format: &str) {
+    rustc()
+        .input("main.rs")
+        .crate_name("size_report_test")
+        .output("main")
+        .arg("-Zlinker-map-report")
+        .arg("-Zunstable-options")
+        // The value of `rust.lld` is different between CI and locally, pick the linker explicitly.
+        .arg(if lld { "-Clinker-features=+lld" } else { "-Clinker-features=-lld" })
+        .arg(if lld { "-Clink-self-contained=+linker" } else { "-Clink-self-contained=-linker" })
+        .run();
+
+    assert!(path("main.map").is_file(), "the linker did not write a map");
+    let report: Value = serde_json::from_str(&rfs::read_to_string("main.size.json")).unwrap();
+    assert_eq!(report["map_format"], expected_format);
+
+    let crates = report["crates"].as_array().unwrap();
+    let local = crates.iter().find(|krate| krate["name"] == "size_report_test");
+    let local = local.unwrap_or_else(|| panic!("the local crate is missing from {crates:#?}"));
+    assert!(local["size"].as_u64().unwrap() > 0);
+
+    let symbols = report["symbols"].as_array().unwrap();
+    assert!(
+        symbols.iter().any(|symbol| symbol["crate"] == "size_report_test"
+            && symbol["name"].as_str().is_some_and(|name| name.ends_with("checksum"))),
+        "`checksum` is not attributed to the local crate"
+    );
+
+    rfs::remove_file("main.map");
+    rfs::remove_file("main.size.json");
+}
+
+fn main() {
+    check_report(false, "gnu");
+    check_report(true, "lld");
+}
@@ -0,0 +1,9 @@
+fn inner<T>(_: T) {}
+
+fn outer<T>(t: T) {
+    inner(t);
+}
+
+pub fn bar() {
+    outer(1u32);
+}
@@ -0,0 +1,38 @@
+//@ needs-target-std
+//
+// Checks that `-Zdump-mono-usage-graph` outputs the uses between mono items, and that
+// `-Zexplain-mono-item` prints the chain of uses leading to each instantiation of an item.
+
+use run_make_support::{cwd, rfs, rustc};
+
+fn main() {
+    rustc()
+        .crate_type("lib")
+        .input("foo.rs")
+        .arg(format!("-Zdump-mono-usage-graph={}", cwd().display()))
+        .arg("-Zdump-mono-stats-format=json")
+        .run();
+    let graph = rfs::read_to_string("foo.mono_usage.json");
+    assert!(graph.contains(r#""roots":["fn bar"]"#));
+    assert!(graph.contains(r#""user":"fn bar","used":"fn outer::<u32>""#));
+    assert!(graph.contains(r#""user":"fn outer::<u32>","used":"fn inner::<u32>""#));
+
+    rustc()
+        .crate_type("lib")
+        .input("foo.rs")
+        .arg("-Zexplain-mono-item=inner")
+        .run()
+        .assert_stdout_contains(
+            "instantiation chain of `fn inner::<u32>`:\n    \
+            `fn bar` (root)\n    \
+            `fn outer::<u32>` used at foo.rs:8:5: 8:16\n    \
+            `fn inner::<u32>` used at foo.rs:4:5: 4:13\n",
+        );
+
+    rustc()
+        .crate_type("lib")
+        .input("foo.rs")
+        .arg("-Zexplain-mono-item=missing")
+        .run()
+        .assert_stderr_contains("no monomorphized item named `missing` was collected");
+}
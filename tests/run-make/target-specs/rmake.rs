@@ -96,4 +96,41 @@ fn main() {
         .arg("-Ctarget-cpu=generic")
         .run();
     rustc().arg("-Zunstable-options").target("require-explicit-cpu").print("target-cpus").run();
+    rustc()
+        .arg("-Zunstable-options")
+        .target("inherits-builtin.json")
+        .print("target-spec-json")
+        .run()
+        .assert_stdout_contains(r#""llvm-target": "x86_64-unknown-linux-gnu""#)
+        .assert_stdout_contains(r#""cpu": "x86-64-v3""#);
+    rustc()
+        .arg("-Zunstable-options")
+        .target("inherits-file.json")
+        .print("target-spec-json")
+        .run()
+        .assert_stdout_contains(r#""llvm-target": "i686-unknown-linux-gnu""#)
+        .assert_stdout_contains(r#""vendor": "awesome""#);
+    rustc()
+        .arg("-Zunstable-options")
+        .env("RUST_TARGET_PATH", ".")
+        .input("foo.rs")
+        .target("inherits-file")
+        .crate_type("lib")
+        .emit("asm")
+        .run();
+    rustc()
+        .arg("-Zunstable-options")
+        .input("foo.rs")
+        .target("inherits-cycle-a.json")
+        .run_fail()
+        .assert_stderr_contains("target specifications inherit from each other in a cycle: ")
+        .assert_stderr_contains(r#"inherits-cycle-b.json" -> "#);
+    rustc()
+        .arg("-Zunstable-options")
+        .input("foo.rs")
+        .target("inherits-unknown-base.json")
+        .run_fail()
+        .assert_stderr_contains(
+            "base target `x86_64-unknown-awesome` is neither a built-in target nor a path",
+        );
 }
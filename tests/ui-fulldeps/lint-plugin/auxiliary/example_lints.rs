@@ -0,0 +1,78 @@
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_public;
+
+use rustc_public::CrateDef;
+use rustc_public::hir::{Block, Expr, ExprKind, Item, ItemKind, StmtKind};
+use rustc_public::lint::{Level, Lint, LintContext, LintId, LintPass, LintRegistry};
+
+/// Lints items named `forbidden`.
+struct ForbiddenName(LintId);
+
+impl LintPass for ForbiddenName {
+    fn check_item(&self, cx: &LintContext, item: &Item) {
+        if item.name.as_deref() == Some("forbidden") {
+            cx.emit(self.0, item.span, "item named `forbidden`");
+        }
+    }
+}
+
+/// Lints `unsafe` blocks in the bodies of functions, at the node of the block.
+struct UnsafeBlock(LintId);
+
+impl UnsafeBlock {
+    fn check_block(&self, cx: &LintContext, block: &Block) {
+        for stmt in &block.stmts {
+            match &stmt.kind {
+                StmtKind::Let(local) => {
+                    if let Some(init) = &local.init {
+                        self.check_expr(cx, init);
+                    }
+                }
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => self.check_expr(cx, expr),
+                StmtKind::Item(_) => {}
+            }
+        }
+        if let Some(expr) = &block.expr {
+            self.check_expr(cx, expr);
+        }
+    }
+
+    fn check_expr(&self, cx: &LintContext, expr: &Expr) {
+        if let ExprKind::Block(block, _) = &expr.kind {
+            if block.is_unsafe {
+                cx.emit_at(self.0, expr.id, expr.span, "unsafe block");
+            }
+            self.check_block(cx, block);
+        }
+    }
+}
+
+impl LintPass for UnsafeBlock {
+    fn check_item(&self, cx: &LintContext, item: &Item) {
+        if let ItemKind::Fn { has_body: true } = item.kind {
+            let body = cx.body(item.def.def_id()).unwrap();
+            self.check_expr(cx, &body.value);
+        }
+    }
+}
+
+fn register(registry: &mut LintRegistry) {
+    let forbidden_item_name = registry.register_lint(Lint {
+        name: "forbidden_item_name",
+        default_level: Level::Deny,
+        description: "detects items named `forbidden`",
+    });
+    let plugin_unsafe_block = registry.register_lint(Lint {
+        name: "plugin_unsafe_block",
+        default_level: Level::Warn,
+        description: "detects `unsafe` blocks",
+    });
+    registry.register_pass(ForbiddenName(forbidden_item_name));
+    registry.register_pass(UnsafeBlock(plugin_unsafe_block));
+}
+
+rustc_public::declare_lint_plugin!(register);
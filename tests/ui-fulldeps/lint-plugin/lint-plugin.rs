@@ -0,0 +1,40 @@
+//@ edition: 2021
+//@ ignore-stage1 (requires matching sysroot built with in-tree compiler)
+//@ aux-lint-plugin: example_lints.rs
+//@ revisions: default cli
+//@ [cli] compile-flags: -A forbidden_item_name -D plugin_unsafe_block
+
+// Checks that the lints of a plugin loaded with `-Zlint-plugin` are registered with their default
+// levels, that their levels can be changed on the command line, and that `#[allow]` is taken into
+// account on items, statements and expressions.
+
+#![crate_type = "lib"]
+#![feature(stmt_expr_attributes)]
+#![allow(unused_unsafe)]
+
+pub fn forbidden() {}
+//[default]~^ ERROR item named `forbidden`
+
+#[allow(forbidden_item_name)]
+pub mod allowed {
+    pub fn forbidden() {}
+}
+
+pub fn unsafe_blocks() {
+    unsafe {}
+    //[default]~^ WARN unsafe block
+    //[cli]~^^ ERROR unsafe block
+
+    #[allow(plugin_unsafe_block)]
+    let _ = unsafe { 1 };
+
+    let _ = #[allow(plugin_unsafe_block)]
+    unsafe {
+        2
+    };
+}
+
+#[allow(plugin_unsafe_block)]
+pub fn allowed_unsafe_block() {
+    unsafe {}
+}
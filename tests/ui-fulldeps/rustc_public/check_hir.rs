@@ -0,0 +1,164 @@
+//@ run-pass
+//! Test the HIR view of the local crate.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+#[macro_use]
+extern crate rustc_public;
+
+use rustc_public::hir::{
+    self, BinOp, Expansion, ExpansionKind, Expr, ExprKind, Item, ItemKind, MacroKind, PatKind, Res,
+    StmtKind, UseKind, Visibility,
+};
+use rustc_public::ty::{Ty, UintTy};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_hir() -> ControlFlow<()> {
+    let items: Vec<Item> = hir::root_items().iter().map(|item| item.item().unwrap()).collect();
+
+    // Modules and visibilities as written.
+    let inner = find(&items, "inner");
+    assert_eq!(inner.vis, Visibility::Crate);
+    assert_eq!(inner.vis_span.unwrap().snippet().unwrap(), "pub(crate)");
+    let ItemKind::Mod { items: inner_items } = &inner.kind else { panic!("{inner:?}") };
+    let inner_items: Vec<Item> = inner_items.iter().map(|item| item.item().unwrap()).collect();
+    let helper = find(&inner_items, "helper");
+    assert_eq!(helper.vis, Visibility::Super);
+    assert_eq!(find(&inner_items, "hidden").vis, Visibility::Inherited);
+
+    // Nested imports are split into one item per import plus the list stem.
+    let uses: Vec<_> = items
+        .iter()
+        .filter_map(|item| match &item.kind {
+            ItemKind::Use(tree) => Some(tree),
+            _ => None,
+        })
+        .collect();
+    assert!(uses.iter().any(|tree| tree.kind == UseKind::ListStem));
+    assert!(uses.iter().any(|tree| tree.kind == UseKind::Single("helper".into())));
+    let renamed = uses.iter().find(|tree| tree.kind == UseKind::Single("other".into())).unwrap();
+    let path: Vec<_> = renamed.path.iter().map(|segment| segment.name.as_str()).collect();
+    assert_eq!(path, ["inner", "helper"]);
+    assert!(renamed.res.contains(&Res::Def(helper.def.0)));
+
+    // Attributes, including doc comments.
+    let compute = find(&items, "compute");
+    assert_eq!(compute.vis, Visibility::Public);
+    assert!(compute.attrs.iter().any(|attr| attr.doc.as_deref() == Some(" Computes things.")));
+    let skip = compute.attrs.iter().find(|attr| attr.path.is_some()).unwrap();
+    assert_eq!(skip.path.as_deref().unwrap(), ["rustfmt", "skip"]);
+    assert_eq!(skip.span.unwrap().snippet().unwrap(), "#[rustfmt::skip]");
+
+    // The expression tree of the body.
+    let body = compute.body().unwrap();
+    let PatKind::Binding { name, .. } = &body.params[0].pat.kind else { panic!("{body:?}") };
+    assert_eq!(name, "x");
+    let ExprKind::Block(block, None) = &body.value.kind else { panic!("{body:?}") };
+    let StmtKind::Let(local) = &block.stmts[0].kind else { panic!("{block:?}") };
+    let PatKind::Binding { id: y, .. } = local.pat.kind else { panic!("{local:?}") };
+    let init = local.init.as_ref().unwrap();
+    assert_eq!(init.ty, Some(Ty::unsigned_ty(UintTy::U32)));
+    assert!(matches!(init.kind, ExprKind::Binary(BinOp::Mul, ..)));
+    let Some(Expansion { kind: ExpansionKind::Macro(MacroKind::Bang, macro_name), .. }) =
+        init.span.expansion()
+    else {
+        panic!("{init:?}")
+    };
+    assert_eq!(macro_name, "double");
+
+    let ExprKind::If { cond, then, .. } = &peel(block.expr.as_ref().unwrap()).kind else {
+        panic!("{block:?}")
+    };
+    let cond = peel(cond);
+    assert!(matches!(cond.kind, ExprKind::Binary(BinOp::Gt, ..)));
+    assert_eq!(cond.span.expansion(), None);
+    let ExprKind::Block(then, _) = &then.kind else { panic!("{then:?}") };
+    let ExprKind::Binary(BinOp::Sub, lhs, rhs) = &then.expr.as_ref().unwrap().kind else {
+        panic!("{then:?}")
+    };
+    let ExprKind::Path(lhs) = &lhs.kind else { panic!("{lhs:?}") };
+    assert_eq!(lhs.res, Res::Local(y));
+    let ExprKind::Call(callee, _) = &rhs.kind else { panic!("{rhs:?}") };
+    let ExprKind::Path(callee) = &callee.kind else { panic!("{callee:?}") };
+    assert_eq!(callee.res, Res::Def(helper.def.0));
+
+    // Only free items of the local crate have a HIR item.
+    let foreign = rustc_public::find_crates("std")[0].fn_defs()[0];
+    assert_eq!(hir::ItemDef(foreign.0).item(), None);
+
+    ControlFlow::Continue(())
+}
+
+fn find<'a>(items: &'a [Item], name: &str) -> &'a Item {
+    items.iter().find(|item| item.name.as_deref() == Some(name)).unwrap()
+}
+
+/// Removes the scopes for temporaries that the compiler introduces, e.g., around conditions.
+fn peel(mut expr: &Expr) -> &Expr {
+    while let ExprKind::DropTemps(inner) = &expr.kind {
+        expr = inner;
+    }
+    expr
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `RustcPublic` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "hir_input.rs";
+    generate_input(&path).unwrap();
+    let args = &[
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_hir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+pub(crate) mod inner {{
+    pub(super) fn helper() -> u32 {{
+        1
+    }}
+
+    fn hidden() {{}}
+}}
+
+#[allow(unused_imports)]
+use inner::{{helper, helper as other}};
+
+macro_rules! double {{
+    ($e:expr) => {{
+        $e * 2
+    }};
+}}
+
+/// Computes things.
+#[rustfmt::skip]
+pub fn compute(x: u32) -> u32 {{
+    let y = double!(x);
+    if y > 10 {{ y - inner::helper() }} else {{ y + 1 }}
+}}
+"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//@ run-pass
+//! Test that drivers can register MIR passes that change the optimized MIR of the local crate.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+#[macro_use]
+extern crate rustc_public;
+
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::transform::{MirPass, register_mir_pass};
+use rustc_public::mir::{
+    Body, ConstOperand, Mutability, Operand, Place, RETURN_LOCAL, Rvalue, Statement, StatementKind,
+};
+use rustc_public::ty::{ConstantKind, MirConst, Ty, UintTy};
+use rustc_public::{CrateDef, CrateItem};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Makes `input::answer` return 42 through a new local.
+struct ChangeAnswer;
+
+impl MirPass for ChangeAnswer {
+    fn name(&self) -> &str {
+        "ChangeAnswer"
+    }
+
+    fn run_pass(&self, item: CrateItem, body: &mut Body) {
+        if item.name() != "input::answer" {
+            return;
+        }
+        let span = body.span;
+        let local = body.new_local(Ty::unsigned_ty(UintTy::U32), span, Mutability::Mut);
+        let value = ConstOperand {
+            span,
+            user_ty: None,
+            const_: MirConst::try_from_uint(42, UintTy::U32).unwrap(),
+        };
+        let block = &mut body.blocks[0];
+        for statement in &mut block.statements {
+            if let StatementKind::Assign(place, rvalue) = &mut statement.kind
+                && place.local == RETURN_LOCAL
+            {
+                *rvalue = Rvalue::Use(Operand::Copy(Place::from(local)));
+            }
+        }
+        block.statements.insert(
+            0,
+            Statement {
+                kind: StatementKind::Assign(
+                    Place::from(local),
+                    Rvalue::Use(Operand::Constant(value)),
+                ),
+                span,
+            },
+        );
+    }
+}
+
+/// Check that the optimized MIR seen by the driver is the one produced by the pass.
+fn test_mir_pass() -> ControlFlow<()> {
+    let items = rustc_public::all_local_items();
+    let answer = items.iter().find(|item| item.name() == "input::answer").unwrap();
+    let body = Instance::try_from(*answer).unwrap().body().unwrap();
+    let new_local = body.locals().len() - 1;
+    assert_eq!(body.locals()[new_local].ty, Ty::unsigned_ty(UintTy::U32));
+
+    let statements = &body.blocks[0].statements;
+    let StatementKind::Assign(place, Rvalue::Use(Operand::Constant(value))) = &statements[0].kind
+    else {
+        panic!("expected the assignment added by the pass, found {:?}", statements[0])
+    };
+    assert_eq!(place.local, new_local);
+    let ConstantKind::Allocated(alloc) = value.const_.kind() else { unreachable!() };
+    assert_eq!(alloc.read_uint().unwrap(), 42);
+
+    assert!(statements.iter().any(|statement| matches!(
+        &statement.kind,
+        StatementKind::Assign(place, Rvalue::Use(Operand::Copy(value)))
+            if place.local == RETURN_LOCAL && value.local == new_local
+    )));
+
+    // Other items are left untouched.
+    let other = items.iter().find(|item| item.name() == "input::other").unwrap();
+    let body = Instance::try_from(*other).unwrap().body().unwrap();
+    assert_eq!(body.locals().len(), 1);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will register the pass and run the compiler, including code generation, which
+/// validates the transformed MIR.
+fn main() {
+    let path = "mir_pass_input.rs";
+    generate_input(&path).unwrap();
+    let args = &[
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    register_mir_pass(ChangeAnswer);
+    run!(args, test_mir_pass).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn answer() -> u32 {{
+            1
+        }}
+
+        pub fn other() -> u32 {{
+            2
+        }}
+        "#
+    )?;
+    Ok(())
+}
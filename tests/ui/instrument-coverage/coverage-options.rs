@@ -1,4 +1,4 @@
-//@ revisions: block branch condition bad
+//@ revisions: block branch condition mcdc bad
 //@ compile-flags: -Cinstrument-coverage -Zno-profiler-runtime
 
 //@ [block] check-pass
@@ -10,6 +10,9 @@
 //@ [condition] check-pass
 //@ [condition] compile-flags: -Zcoverage-options=condition
 
+//@ [mcdc] check-pass
+//@ [mcdc] compile-flags: -Zcoverage-options=mcdc
+
 //@ [bad] check-fail
 //@ [bad] compile-flags: -Zcoverage-options=bad
 
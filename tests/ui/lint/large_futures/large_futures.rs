@@ -0,0 +1,50 @@
+#![deny(large_futures)]
+#![cfg_attr(attribute, feature(large_futures))]
+#![cfg_attr(attribute, future_size_limit = "1000")]
+//@ build-fail
+//@ only-64bit
+//@ revisions: attribute option
+//@ [option]compile-flags: -Zfuture-size-limit=1000
+//@ ignore-parallel-frontend post-monomorphization errors
+//@ edition:2018
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Waker};
+
+async fn small() {}
+
+async fn large_local() {
+    //~^ ERROR this future is
+    let buf = [0u8; 4096];
+    small().await;
+    drop(buf);
+}
+
+async fn large_argument(buf: [u8; 4096]) {
+    //~^ ERROR this future is
+    small().await;
+    drop(buf);
+}
+
+async fn dropped_before_await() {
+    let buf = [0u8; 4096];
+    drop(buf);
+    small().await;
+}
+
+fn poll<F: Future>(fut: F) {
+    let _ = pin!(fut).poll(&mut Context::from_waker(Waker::noop()));
+}
+
+fn main() {
+    poll(large_local());
+    poll(large_argument([0; 4096]));
+    poll(dropped_before_await());
+    poll(async {
+        //~^ ERROR this future is
+        let buf = [0u8; 4096];
+        small().await;
+        drop(buf);
+    });
+}
@@ -0,0 +1,7 @@
+//@ compile-flags: -Zlint-plugin=/non-existing-lint-plugin.so
+
+// Check that a missing lint plugin is reported as an error.
+
+fn main() {}
+
+//~? ERROR couldn't find lint plugin `/non-existing-lint-plugin.so`
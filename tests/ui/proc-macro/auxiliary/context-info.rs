@@ -0,0 +1,21 @@
+#![feature(proc_macro_expansion_context)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro::context;
+
+#[proc_macro]
+pub fn context_info(_: TokenStream) -> TokenStream {
+    let has_cfg = context::cfgs().iter().any(|cfg| cfg.name() == "context_info_cfg");
+    format!(
+        "({:?}, {:?}, {:?}, {:?}, {})",
+        context::crate_name(),
+        format!("{:?}", context::edition()),
+        context::module_path(),
+        context::item_path(),
+        has_cfg,
+    )
+    .parse()
+    .unwrap()
+}
@@ -0,0 +1,20 @@
+#![feature(proc_macro_diagnostic, proc_macro_diagnostic_suggestion)]
+
+extern crate proc_macro;
+
+use proc_macro::{Applicability, Diagnostic, Level, TokenStream, TokenTree};
+
+#[proc_macro]
+pub fn rename_to_bar(input: TokenStream) -> TokenStream {
+    for tt in input {
+        if let TokenTree::Ident(ident) = tt
+            && ident.to_string() != "bar"
+        {
+            Diagnostic::spanned(ident.span(), Level::Warning, "identifier should be `bar`")
+                .span_suggestion(ident.span(), "rename it", "bar", Applicability::MachineApplicable)
+                .emit();
+        }
+    }
+
+    TokenStream::new()
+}
@@ -0,0 +1,13 @@
+//@ proc-macro: diagnostic-suggestion.rs
+//@ run-rustfix
+//@ check-pass
+
+// Checks that suggestions added to a `proc_macro::Diagnostic` are emitted as
+// code suggestions that can be applied by rustfix.
+
+extern crate diagnostic_suggestion;
+
+diagnostic_suggestion::rename_to_bar!(foo);
+//~^ WARN identifier should be `bar`
+
+fn main() {}
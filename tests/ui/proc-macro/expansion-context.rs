@@ -0,0 +1,32 @@
+//@ run-pass
+//@ proc-macro: context-info.rs
+//@ edition: 2021
+//@ compile-flags: --cfg context_info_cfg
+
+// Checks the information that `proc_macro::context` reports about the
+// surroundings of a macro invocation.
+
+extern crate context_info;
+
+use context_info::context_info;
+
+mod outer {
+    pub mod inner {
+        pub fn get() -> (&'static str, &'static str, &'static str, &'static str, bool) {
+            crate::context_info!()
+        }
+    }
+}
+
+fn main() {
+    let (crate_name, edition, module_path, item_path, has_cfg) = outer::inner::get();
+    assert_eq!(crate_name, "expansion_context");
+    assert_eq!(edition, "Edition2021");
+    assert_eq!(module_path, "expansion_context::outer::inner");
+    assert_eq!(item_path, "expansion_context::outer::inner::get");
+    assert!(has_cfg);
+
+    let (_, _, module_path, item_path, _) = context_info!();
+    assert_eq!(module_path, "expansion_context");
+    assert_eq!(item_path, "expansion_context::main");
+}
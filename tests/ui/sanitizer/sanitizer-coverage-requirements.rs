@@ -0,0 +1,13 @@
+// Verifies the requirements of the `-Zsanitizer-coverage` options.
+//
+//@ revisions: pc_table ignorelist_without_coverage ignorelist_not_found
+//@[pc_table] compile-flags: -Zsanitizer-coverage=trace-cmp,pc-table
+//@[ignorelist_without_coverage] compile-flags: -Zsanitizer-coverage-ignorelist={{src-base}}/sanitizer/sanitizer-coverage-ignorelist.txt
+//@[ignorelist_not_found] compile-flags: -Zsanitizer-coverage=trace-pc-guard -Zsanitizer-coverage-ignorelist=does-not-exist.txt
+
+//[pc_table]~? ERROR `-Zsanitizer-coverage=pc-table` requires `-Zsanitizer-coverage=trace-pc-guard` or `-Zsanitizer-coverage=inline-8bit-counters`
+//[ignorelist_without_coverage]~? ERROR `-Zsanitizer-coverage-ignorelist` requires `-Zsanitizer-coverage`
+//[ignorelist_not_found]~? ERROR SanitizerCoverage ignorelist `does-not-exist.txt` does not exist or is not a file
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -0,0 +1,6 @@
+//@ no-prefer-dynamic
+//@ compile-flags: -Zsanitizer-coverage=trace-pc-guard
+
+#![feature(no_core)]
+#![crate_type = "rlib"]
+#![no_core]
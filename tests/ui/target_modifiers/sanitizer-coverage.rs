@@ -0,0 +1,19 @@
+// Verifies that crates instrumented with different kinds of SanitizerCoverage instrumentation
+// cannot be mixed, while crates without any instrumentation can be linked with them.
+//
+//@ aux-build:sanitizer-coverage-guard.rs
+
+//@ revisions: good uninstrumented mismatch
+//@[good] compile-flags: -Zsanitizer-coverage=trace-pc-guard
+// [uninstrumented] no additional compile-flags
+//@[mismatch] compile-flags: -Zsanitizer-coverage=inline-8bit-counters
+//@[good] check-pass
+//@[uninstrumented] check-pass
+//@ ignore-backends: gcc
+
+#![feature(no_core)]
+//[mismatch]~^ ERROR mixing `-Zsanitizer-coverage` will cause an ABI mismatch in crate `sanitizer_coverage`
+#![crate_type = "rlib"]
+#![no_core]
+
+extern crate sanitizer_coverage_guard;